@@ -0,0 +1,287 @@
+//! Conversion of binary table columns into Apache Arrow arrays.
+//!
+//! Scalar columns (repeat count 1) map onto the matching primitive Arrow
+//! array. Columns with a repeat count greater than one become
+//! `FixedSizeList` arrays, variable-length (`P`/`Q`) columns become `List`
+//! arrays, and complex values are `FixedSizeList<2>` of `(real, imag)`.
+//! Integer elements equal to `TNULLn` are marked null in the validity bitmap.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use arrow_array::types::{
+    ArrowPrimitiveType, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, UInt8Type,
+};
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, FixedSizeListArray, Float32Array, Float64Array,
+    Int16Array, Int32Array, Int64Array, ListArray, RecordBatch, RecordBatchOptions, StringArray,
+    UInt8Array,
+};
+use arrow_schema::{Field, Schema};
+
+use crate::bintable::{
+    column_display_name, parse_binary_table_columns, read_binary_column, BinaryColumnData,
+    BinaryColumnDescriptor,
+};
+use crate::error::{Error, Result};
+use crate::hdu::{Hdu, HduInfo};
+use crate::header::{keyword_value, Card};
+use crate::value::Value;
+
+fn card_int_value(cards: &[Card], keyword: &str) -> Option<i64> {
+    match keyword_value(cards, keyword) {
+        Some(Value::Integer(n)) => Some(*n),
+        _ => None,
+    }
+}
+
+/// Map integer values to `None` where they equal `tnull`.
+fn with_nulls<T: Copy + Into<i64>>(values: &[T], tnull: Option<i64>) -> Vec<Option<T>> {
+    values
+        .iter()
+        .map(|&v| match tnull {
+            Some(null) if v.into() == null => None,
+            _ => Some(v),
+        })
+        .collect()
+}
+
+/// Wrap a flat values array into fixed-size lists of `repeat` elements.
+fn fixed_size_list(values: ArrayRef, repeat: usize) -> ArrayRef {
+    let field = Arc::new(Field::new("item", values.data_type().clone(), true));
+    Arc::new(FixedSizeListArray::new(field, repeat as i32, values, None))
+}
+
+/// Group flat values per row when `repeat > 1`.
+fn per_row(values: ArrayRef, repeat: usize) -> ArrayRef {
+    if repeat == 1 {
+        values
+    } else {
+        fixed_size_list(values, repeat)
+    }
+}
+
+fn var_list<T>(rows: &[Vec<T::Native>], tnull: Option<i64>) -> ArrayRef
+where
+    T: ArrowPrimitiveType,
+    T::Native: Into<i64>,
+{
+    Arc::new(ListArray::from_iter_primitive::<T, _, _>(
+        rows.iter().map(|row| Some(with_nulls(row, tnull))),
+    ))
+}
+
+fn var_float_list<T: ArrowPrimitiveType>(rows: &[Vec<T::Native>]) -> ArrayRef {
+    Arc::new(ListArray::from_iter_primitive::<T, _, _>(
+        rows.iter().map(|row| Some(row.iter().map(|&v| Some(v)))),
+    ))
+}
+
+fn column_to_arrow(data: &BinaryColumnData, repeat: usize, tnull: Option<i64>) -> ArrayRef {
+    match data {
+        BinaryColumnData::Logical(v) => per_row(Arc::new(BooleanArray::from(v.clone())), repeat),
+        BinaryColumnData::Byte(v) => {
+            per_row(Arc::new(UInt8Array::from(with_nulls(v, tnull))), repeat)
+        }
+        BinaryColumnData::Short(v) => {
+            per_row(Arc::new(Int16Array::from(with_nulls(v, tnull))), repeat)
+        }
+        BinaryColumnData::Int(v) => {
+            per_row(Arc::new(Int32Array::from(with_nulls(v, tnull))), repeat)
+        }
+        BinaryColumnData::Long(v) => {
+            per_row(Arc::new(Int64Array::from(with_nulls(v, tnull))), repeat)
+        }
+        BinaryColumnData::Float(v) => per_row(Arc::new(Float32Array::from(v.clone())), repeat),
+        BinaryColumnData::Double(v) => per_row(Arc::new(Float64Array::from(v.clone())), repeat),
+        BinaryColumnData::ComplexFloat(v) => {
+            let flat: Vec<f32> = v.iter().flat_map(|&(re, im)| [re, im]).collect();
+            let pairs = fixed_size_list(Arc::new(Float32Array::from(flat)), 2);
+            per_row(pairs, repeat)
+        }
+        BinaryColumnData::ComplexDouble(v) => {
+            let flat: Vec<f64> = v.iter().flat_map(|&(re, im)| [re, im]).collect();
+            let pairs = fixed_size_list(Arc::new(Float64Array::from(flat)), 2);
+            per_row(pairs, repeat)
+        }
+        BinaryColumnData::Ascii(v) => Arc::new(StringArray::from(v.clone())),
+        BinaryColumnData::AsciiArray(rows) => {
+            let per_row_count = rows.first().map_or(0, Vec::len);
+            let flat: Vec<String> = rows.iter().flatten().cloned().collect();
+            fixed_size_list(Arc::new(StringArray::from(flat)), per_row_count)
+        }
+        BinaryColumnData::Bit(rows) => Arc::new(BinaryArray::from_iter_values(rows.iter())),
+        BinaryColumnData::VarByte(rows) => var_list::<UInt8Type>(rows, tnull),
+        BinaryColumnData::VarShort(rows) => var_list::<Int16Type>(rows, tnull),
+        BinaryColumnData::VarInt(rows) => var_list::<Int32Type>(rows, tnull),
+        BinaryColumnData::VarLong(rows) => var_list::<Int64Type>(rows, tnull),
+        BinaryColumnData::VarFloat(rows) => var_float_list::<Float32Type>(rows),
+        BinaryColumnData::VarDouble(rows) => var_float_list::<Float64Type>(rows),
+    }
+}
+
+/// The row count and column descriptors of a binary table HDU.
+fn table_columns(hdu: &Hdu) -> Result<(usize, Vec<BinaryColumnDescriptor>)> {
+    match &hdu.info {
+        HduInfo::BinaryTable {
+            naxis2, tfields, ..
+        } => Ok((*naxis2, parse_binary_table_columns(&hdu.cards, *tfields)?)),
+        _ => Err(Error::InvalidHeader("not a binary table HDU")),
+    }
+}
+
+/// Read a binary table column as an Arrow array.
+///
+/// `col_index` is 0-based. Integer elements equal to the column's `TNULLn`
+/// value are null.
+pub fn binary_column_to_arrow(fits_data: &[u8], hdu: &Hdu, col_index: usize) -> Result<ArrayRef> {
+    let (_, columns) = table_columns(hdu)?;
+    let col = columns.get(col_index).ok_or(Error::InvalidValue)?;
+    let data = read_binary_column(fits_data, hdu, col_index)?;
+    let tnull = card_int_value(&hdu.cards, &format!("TNULL{}", col_index + 1));
+    Ok(column_to_arrow(&data, col.repeat, tnull))
+}
+
+/// Read every column of a binary table HDU into an Arrow `RecordBatch`.
+///
+/// Field names are those of [`column_display_name`], so unnamed and
+/// duplicate `TTYPEn` columns get unique names. All fields are nullable.
+pub fn table_to_record_batch(fits_data: &[u8], hdu: &Hdu) -> Result<RecordBatch> {
+    let (naxis2, columns) = table_columns(hdu)?;
+    let mut fields = Vec::with_capacity(columns.len());
+    let mut arrays = Vec::with_capacity(columns.len());
+
+    for i in 0..columns.len() {
+        let array = binary_column_to_arrow(fits_data, hdu, i)?;
+        let name = column_display_name(&columns, i);
+        fields.push(Field::new(name, array.data_type().clone(), true));
+        arrays.push(array);
+    }
+
+    // The row count is needed explicitly when the table has no columns.
+    let options = RecordBatchOptions::new().with_row_count(Some(naxis2));
+    RecordBatch::try_new_with_options(Arc::new(Schema::new(fields)), arrays, &options)
+        .map_err(|_| Error::InvalidValue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bintable::{
+        binary_type_byte_size, build_fits_with_binary_table, parse_tform_binary,
+    };
+    use crate::hdu::parse_fits;
+    use alloc::vec;
+    use arrow_array::cast::AsArray;
+    use arrow_array::Array;
+
+    fn descriptor(name: &str, tform: &str) -> BinaryColumnDescriptor {
+        let (repeat, col_type) = parse_tform_binary(tform).unwrap();
+        BinaryColumnDescriptor {
+            name: Some(String::from(name)),
+            repeat,
+            byte_width: repeat * binary_type_byte_size(&col_type),
+            col_type,
+            tdim: None,
+        }
+    }
+
+    #[test]
+    fn three_column_table_to_record_batch() {
+        let columns = vec![
+            descriptor("ID", "1J"),
+            descriptor("FLUX", "1D"),
+            descriptor("NAME", "8A"),
+        ];
+        let data = vec![
+            BinaryColumnData::Int(vec![1, -99, 3]),
+            BinaryColumnData::Double(vec![1.5, 2.5, 3.5]),
+            BinaryColumnData::Ascii(vec![
+                String::from("alpha"),
+                String::from("beta"),
+                String::from("gamma"),
+            ]),
+        ];
+        let bytes = build_fits_with_binary_table(&columns, &data, 3).unwrap();
+
+        // Mark -99 as the null sentinel for ID.
+        let mut fits = parse_fits(&bytes).unwrap();
+        let hdu = &mut fits.hdus[1];
+        hdu.cards.push(Card {
+            keyword: *b"TNULL1  ",
+            value: Some(Value::Integer(-99)),
+            comment: None,
+        });
+
+        let batch = table_to_record_batch(&bytes, hdu).unwrap();
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.num_columns(), 3);
+        assert_eq!(batch.schema().field(1).name(), "FLUX");
+
+        let ids = batch.column(0).as_primitive::<Int32Type>();
+        assert_eq!(ids.value(0), 1);
+        assert!(ids.is_null(1));
+        assert_eq!(ids.null_count(), 1);
+
+        let flux = batch.column(1).as_primitive::<Float64Type>();
+        assert_eq!(flux.value(2), 3.5);
+
+        let names = batch.column(2).as_string::<i32>();
+        assert_eq!(names.value(1), "beta");
+    }
+
+    #[test]
+    fn repeated_column_becomes_fixed_size_list() {
+        let columns = vec![descriptor("VEC", "3I")];
+        let data = vec![BinaryColumnData::Short(vec![1, 2, 3, 4, 5, 6])];
+        let bytes = build_fits_with_binary_table(&columns, &data, 2).unwrap();
+        let fits = parse_fits(&bytes).unwrap();
+
+        let array = binary_column_to_arrow(&bytes, &fits.hdus[1], 0).unwrap();
+        let list = array.as_fixed_size_list();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.value_length(), 3);
+        let second = list.value(1);
+        assert_eq!(second.as_primitive::<Int16Type>().values(), &[4, 5, 6]);
+    }
+
+    #[test]
+    fn non_table_hdu_is_rejected() {
+        let columns = vec![descriptor("ID", "1J")];
+        let data = vec![BinaryColumnData::Int(vec![1])];
+        let bytes = build_fits_with_binary_table(&columns, &data, 1).unwrap();
+        let fits = parse_fits(&bytes).unwrap();
+        assert!(table_to_record_batch(&bytes, fits.primary()).is_err());
+    }
+
+    #[test]
+    fn record_batch_names_match_display_names() {
+        let mut unnamed = descriptor("", "1J");
+        unnamed.name = None;
+        let columns = vec![descriptor("X", "1J"), unnamed, descriptor("X", "1E")];
+        let data = vec![
+            BinaryColumnData::Int(vec![1, 2]),
+            BinaryColumnData::Int(vec![3, 4]),
+            BinaryColumnData::Float(vec![5.0, 6.0]),
+        ];
+        let bytes = build_fits_with_binary_table(&columns, &data, 2).unwrap();
+        let fits = parse_fits(&bytes).unwrap();
+
+        let batch = table_to_record_batch(&bytes, &fits.hdus[1]).unwrap();
+        let schema = batch.schema();
+        let names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(names, ["X_1", "col2", "X_2"]);
+    }
+
+    #[test]
+    fn table_without_columns_keeps_row_count() {
+        let bytes = build_fits_with_binary_table(&[], &[], 4).unwrap();
+        let fits = parse_fits(&bytes).unwrap();
+
+        let batch = table_to_record_batch(&bytes, &fits.hdus[1]).unwrap();
+        assert_eq!(batch.num_columns(), 0);
+        assert_eq!(batch.num_rows(), 4);
+    }
+}
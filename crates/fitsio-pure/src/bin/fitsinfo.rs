@@ -1,3 +1,4 @@
+use fitsio_pure::bintable::{column_display_name, parse_binary_table_columns};
 use fitsio_pure::hdu::{FitsData, Hdu, HduInfo};
 use fitsio_pure::header::Card;
 use fitsio_pure::value::Value;
@@ -71,6 +72,14 @@ fn format_hdu(index: usize, hdu: &Hdu) -> String {
             };
             out.push_str(&format!("HDU {}: BINTABLE extension{}\n", index, ext_label));
             out.push_str(&format!("  Columns: {}\n", tfields));
+            if let Ok(columns) = parse_binary_table_columns(&hdu.cards, *tfields) {
+                if !columns.is_empty() {
+                    let names: Vec<String> = (0..columns.len())
+                        .map(|i| column_display_name(&columns, i))
+                        .collect();
+                    out.push_str(&format!("  Column names: {}\n", names.join(", ")));
+                }
+            }
             out.push_str(&format!("  Rows: {}\n", naxis2));
             out.push_str(&format!("  Row width: {} bytes\n", naxis1));
             out.push_str(&format!("  Data size: {} bytes\n", naxis1 * naxis2));
@@ -396,6 +405,35 @@ mod tests {
         assert!(!output.contains("Heap size:"));
     }
 
+    #[test]
+    fn format_bintable_column_names() {
+        let mut ext_cards = bintable_extension_header(12, 1, 0, 3, None);
+        for (i, (tform, ttype)) in [("1J", Some("ID")), ("1E", Some("ID")), ("1E", None)]
+            .iter()
+            .enumerate()
+        {
+            let n = i + 1;
+            ext_cards.push(card(
+                &format!("TFORM{}", n),
+                Value::String(tform.to_string()),
+            ));
+            if let Some(name) = ttype {
+                ext_cards.push(card(
+                    &format!("TTYPE{}", n),
+                    Value::String(name.to_string()),
+                ));
+            }
+        }
+
+        let mut data = serialize_header(&primary_header_naxis0()).unwrap();
+        data.extend_from_slice(&serialize_header(&ext_cards).unwrap());
+        data.resize(data.len() + padded_byte_len(12), 0u8);
+
+        let fits = fitsio_pure::hdu::parse_fits(&data).unwrap();
+        let output = format_fits_info(&fits, false);
+        assert!(output.contains("Column names: ID_1, ID_2, col3"));
+    }
+
     #[test]
     fn verbose_shows_header_cards() {
         let cards = primary_header_image(16, &[100, 200]);
@@ -4,20 +4,21 @@ use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
 
-use crate::block::padded_byte_len;
+use crate::block::{pad_to_block, Serialized, DATA_PAD_BYTE};
 use crate::endian::{
-    read_f32_be, read_f64_be, read_i16_be, read_i32_be, read_i64_be, write_f32_be, write_f64_be,
-    write_i16_be, write_i32_be, write_i64_be,
+    read_f32_be, read_f64_be, read_i16_be, read_i32_be, read_i64_be, read_u8, try_read_u32_be,
+    try_read_u64_be, write_f32_be, write_f64_be, write_i16_be, write_i32_be, write_i64_be,
 };
 use crate::error::{Error, Result};
 use crate::hdu::{Hdu, HduInfo};
-use crate::header::{serialize_header, Card};
+use crate::header::{has_keyword, keyword_value, serialize_header, Card};
 use crate::value::Value;
 
 /// The data type of a column in a FITS binary table.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BinaryColumnType {
-    /// L -- logical, stored as a single byte (T/F/0).
+    /// L -- logical, stored as a single byte (T/F, 1/0, or 0x00 for undefined).
     Logical,
     /// X -- bit array.
     Bit,
@@ -39,6 +40,9 @@ pub enum BinaryColumnType {
     ComplexDouble,
     /// A -- ASCII character.
     Ascii,
+    /// `rA:SSTRw` -- an `A` column holding `r / w` fixed-width substrings.
+    /// The `usize` is the substring width `w`.
+    AsciiArray(usize),
     /// P -- 32-bit variable-length array descriptor (8 bytes: count + heap offset).
     /// The `char` is the element type code (e.g. `'J'` for i32).
     VarArrayP(char),
@@ -49,6 +53,7 @@ pub enum BinaryColumnType {
 
 /// Describes one column in a binary table.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BinaryColumnDescriptor {
     /// Column name (from TTYPEn), if present.
     pub name: Option<String>,
@@ -88,6 +93,8 @@ pub enum BinaryColumnData {
     ComplexDouble(Vec<(f64, f64)>),
     /// ASCII string (A) column.
     Ascii(Vec<String>),
+    /// Substring array (`rA:SSTRw`) column: one inner Vec of substrings per row.
+    AsciiArray(Vec<Vec<String>>),
     /// Bit array (X) column, one byte-vec per row.
     Bit(Vec<Vec<u8>>),
     /// Variable-length byte array column: one inner Vec per row.
@@ -120,7 +127,7 @@ pub fn binary_type_byte_size(col_type: &BinaryColumnType) -> usize {
         BinaryColumnType::Double => 8,
         BinaryColumnType::ComplexFloat => 8,
         BinaryColumnType::ComplexDouble => 16,
-        BinaryColumnType::Ascii => 1,
+        BinaryColumnType::Ascii | BinaryColumnType::AsciiArray(_) => 1,
         BinaryColumnType::VarArrayP(_) => 8,
         BinaryColumnType::VarArrayQ(_) => 16,
     }
@@ -128,6 +135,12 @@ pub fn binary_type_byte_size(col_type: &BinaryColumnType) -> usize {
 
 /// Parse a TFORMn value like "1J", "10E", "20A", "1024X", "1PB(200)", "1QJ".
 ///
+/// The `rA:SSTRw` substring-array convention (e.g. "60A:SSTR20") is
+/// recognized and returned as [`BinaryColumnType::AsciiArray`].
+///
+/// Trailing junk after the type code is rejected; see
+/// [`parse_tform_binary_lenient`] to tolerate it.
+///
 /// Returns the repeat count and the column type.
 pub fn parse_tform_binary(s: &str) -> Result<(usize, BinaryColumnType)> {
     let s = s.trim();
@@ -135,6 +148,19 @@ pub fn parse_tform_binary(s: &str) -> Result<(usize, BinaryColumnType)> {
         return Err(Error::InvalidValue);
     }
 
+    if let Some((base, convention)) = s.split_once(':') {
+        let width = convention
+            .trim()
+            .strip_prefix("SSTR")
+            .and_then(|w| w.parse::<usize>().ok())
+            .filter(|&w| w > 0)
+            .ok_or(Error::InvalidValue)?;
+        return match parse_tform_binary(base)? {
+            (repeat, BinaryColumnType::Ascii) => Ok((repeat, BinaryColumnType::AsciiArray(width))),
+            _ => Err(Error::InvalidValue),
+        };
+    }
+
     // Strip optional (maxlen) suffix for variable-length arrays.
     let s = if let Some(paren) = s.find('(') {
         &s[..paren]
@@ -154,14 +180,7 @@ pub fn parse_tform_binary(s: &str) -> Result<(usize, BinaryColumnType)> {
                 b'L' | b'X' | b'B' | b'I' | b'J' | b'K' | b'E' | b'D' | b'C' | b'M' | b'A' => {}
                 _ => return Err(Error::InvalidValue),
             }
-            let repeat_str = &s[..s.len() - 2];
-            let repeat = if repeat_str.is_empty() {
-                1
-            } else {
-                repeat_str
-                    .parse::<usize>()
-                    .map_err(|_| Error::InvalidValue)?
-            };
+            let repeat = parse_repeat(&s[..s.len() - 2])?;
             let elem_char = last as char;
             let col_type = if second_last == b'P' {
                 BinaryColumnType::VarArrayP(elem_char)
@@ -174,15 +193,7 @@ pub fn parse_tform_binary(s: &str) -> Result<(usize, BinaryColumnType)> {
 
     // Find the last character, which is the type code.
     let type_char = s.as_bytes()[s.len() - 1];
-    let repeat_str = &s[..s.len() - 1];
-
-    let repeat = if repeat_str.is_empty() {
-        1
-    } else {
-        repeat_str
-            .parse::<usize>()
-            .map_err(|_| Error::InvalidValue)?
-    };
+    let repeat = parse_repeat(&s[..s.len() - 1])?;
 
     let col_type = match type_char {
         b'L' => BinaryColumnType::Logical,
@@ -202,15 +213,72 @@ pub fn parse_tform_binary(s: &str) -> Result<(usize, BinaryColumnType)> {
     Ok((repeat, col_type))
 }
 
+/// Parse a TFORM repeat count, defaulting to 1 when empty.
+fn parse_repeat(s: &str) -> Result<usize> {
+    if s.is_empty() {
+        return Ok(1);
+    }
+    s.parse::<usize>().map_err(|_| Error::InvalidValue)
+}
+
+/// Parse a TFORMn value like [`parse_tform_binary`], tolerating defects
+/// seen in real files.
+///
+/// A single trailing character that is not a letter or digit (e.g.
+/// `"1J."`) is ignored. Column descriptors
+/// are always parsed strictly; [`parse_fits_with`] in lenient mode rewrites
+/// such TFORMn values with this function's repairs.
+///
+/// [`parse_fits_with`]: crate::hdu::parse_fits_with
+pub fn parse_tform_binary_lenient(s: &str) -> Result<(usize, BinaryColumnType)> {
+    parse_tform_binary(repair_tform(s))
+}
+
+/// `s` trimmed and with the defects tolerated by
+/// [`parse_tform_binary_lenient`] removed. Returned unchanged apart from
+/// trimming when no repair makes it parse.
+pub(crate) fn repair_tform(s: &str) -> &str {
+    let s = s.trim();
+    if parse_tform_binary(s).is_ok() {
+        return s;
+    }
+    match s.char_indices().next_back() {
+        Some((i, c))
+            if i > 0 && !c.is_ascii_alphanumeric() && parse_tform_binary(&s[..i]).is_ok() =>
+        {
+            &s[..i]
+        }
+        _ => s,
+    }
+}
+
 /// Compute the byte width of a column given its repeat count and type.
 fn compute_byte_width(repeat: usize, col_type: &BinaryColumnType) -> usize {
     match col_type {
         BinaryColumnType::Bit => repeat.div_ceil(8),
         // VarArray descriptors have a fixed size regardless of repeat count
-        BinaryColumnType::VarArrayP(_) => 8 * repeat,
-        BinaryColumnType::VarArrayQ(_) => 16 * repeat,
-        _ => repeat * binary_type_byte_size(col_type),
+        BinaryColumnType::VarArrayP(_) => repeat.saturating_mul(8),
+        BinaryColumnType::VarArrayQ(_) => repeat.saturating_mul(16),
+        _ => repeat.saturating_mul(binary_type_byte_size(col_type)),
+    }
+}
+
+/// Check that `columns` fit within a row of `naxis1` bytes.
+///
+/// Unused bytes after the last column are allowed. Returns
+/// [`Error::ColumnLayoutMismatch`] when the column widths add up to more
+/// than `naxis1`.
+pub fn check_column_layout(columns: &[BinaryColumnDescriptor], naxis1: usize) -> Result<()> {
+    let width = columns
+        .iter()
+        .fold(0usize, |sum, col| sum.saturating_add(col.byte_width));
+    if width > naxis1 {
+        return Err(Error::ColumnLayoutMismatch {
+            expected_naxis1: naxis1,
+            got: width,
+        });
     }
+    Ok(())
 }
 
 fn make_keyword(name: &str) -> [u8; 8] {
@@ -222,30 +290,18 @@ fn make_keyword(name: &str) -> [u8; 8] {
 }
 
 fn card_float_value(cards: &[Card], keyword: &str) -> Option<f64> {
-    cards.iter().find_map(|c| {
-        if c.keyword_str() == keyword {
-            match &c.value {
-                Some(Value::Float(f)) => Some(*f),
-                Some(Value::Integer(n)) => Some(*n as f64),
-                _ => None,
-            }
-        } else {
-            None
-        }
-    })
+    match keyword_value(cards, keyword) {
+        Some(Value::Float(f)) => Some(*f),
+        Some(Value::Integer(n)) => Some(*n as f64),
+        _ => None,
+    }
 }
 
 fn card_string_value(cards: &[Card], keyword: &str) -> Option<String> {
-    cards.iter().find_map(|c| {
-        if c.keyword_str() == keyword {
-            match &c.value {
-                Some(Value::String(s)) => Some(s.trim().into()),
-                _ => None,
-            }
-        } else {
-            None
-        }
-    })
+    match keyword_value(cards, keyword) {
+        Some(Value::String(s)) => Some(s.trim().into()),
+        _ => None,
+    }
 }
 
 /// Parse a TDIMn value like `(10,20)` or `(3,4,5)` into a vector of dimensions.
@@ -273,19 +329,15 @@ pub fn parse_tdim(s: &str) -> Option<Vec<usize>> {
 }
 
 fn card_int_value(cards: &[Card], keyword: &str) -> Option<i64> {
-    cards.iter().find_map(|c| {
-        if c.keyword_str() == keyword {
-            match &c.value {
-                Some(Value::Integer(v)) => Some(*v),
-                _ => None,
-            }
-        } else {
-            None
-        }
-    })
+    match keyword_value(cards, keyword) {
+        Some(Value::Integer(v)) => Some(*v),
+        _ => None,
+    }
 }
 
 /// Extract binary table column descriptors from header cards.
+///
+/// TFORMn values are read with [`parse_tform_binary`].
 pub fn parse_binary_table_columns(
     cards: &[Card],
     tfields: usize,
@@ -318,20 +370,61 @@ pub fn parse_binary_table_columns(
     Ok(columns)
 }
 
-/// Extract the binary table metadata from an HDU, returning (naxis1, naxis2, tfields, columns, data_start).
-fn extract_table_info(
-    fits_data: &[u8],
-    hdu: &Hdu,
-) -> Result<(usize, usize, Vec<BinaryColumnDescriptor>)> {
-    let (naxis1, naxis2, tfields) = match &hdu.info {
+/// A stable identifier for column `index` (0-based) of `columns`.
+///
+/// Returns the `TTYPEn` name when it is present and unique. Unnamed columns
+/// become `"col{index+1}"`, and a name shared by several columns gets a
+/// 1-based occurrence suffix, e.g. `"FLUX_1"` and `"FLUX_2"`.
+pub fn column_display_name(columns: &[BinaryColumnDescriptor], index: usize) -> String {
+    let names: Vec<Option<&str>> = columns.iter().map(|c| c.name.as_deref()).collect();
+    unique_column_name(&names, index)
+}
+
+/// The naming rule of [`column_display_name`] over plain `TTYPEn` values,
+/// shared with the ASCII table reader.
+pub(crate) fn unique_column_name(names: &[Option<&str>], index: usize) -> String {
+    let name = match names.get(index).copied().flatten() {
+        Some(name) if !name.is_empty() => name,
+        _ => return alloc::format!("col{}", index + 1),
+    };
+    let same_name = |n: &Option<&str>| *n == Some(name);
+    if names.iter().filter(|n| same_name(n)).count() == 1 {
+        return String::from(name);
+    }
+    let k = names[..=index].iter().filter(|n| same_name(n)).count();
+    alloc::format!("{}_{}", name, k)
+}
+
+/// The `(naxis1, naxis2, pcount, tfields)` of the binary table stored in an
+/// HDU.
+///
+/// A tile-compressed image is itself a binary table, so its tile columns
+/// can be read with the same functions as any other table.
+fn table_shape(hdu: &Hdu) -> Result<(usize, usize, usize, usize)> {
+    match &hdu.info {
         HduInfo::BinaryTable {
             naxis1,
             naxis2,
+            pcount,
+            tfields,
+        }
+        | HduInfo::CompressedImage {
+            naxis1,
+            naxis2,
+            pcount,
             tfields,
             ..
-        } => (*naxis1, *naxis2, *tfields),
-        _ => return Err(Error::InvalidHeader("not a binary table HDU")),
-    };
+        } => Ok((*naxis1, *naxis2, *pcount, *tfields)),
+        _ => Err(Error::InvalidHeader("not a binary table HDU")),
+    }
+}
+
+/// Extract the binary table metadata from an HDU, returning (naxis1, naxis2, columns).
+pub(crate) fn extract_table_info(
+    fits_data: &[u8],
+    hdu: &Hdu,
+) -> Result<(usize, usize, Vec<BinaryColumnDescriptor>)> {
+    let (naxis1, naxis2, _, tfields) = table_shape(hdu)?;
 
     if hdu.data_start + naxis1 * naxis2 > fits_data.len() {
         return Err(Error::UnexpectedEof);
@@ -342,7 +435,7 @@ fn extract_table_info(
 }
 
 /// Compute byte offsets for each column within a row.
-fn column_offsets(columns: &[BinaryColumnDescriptor]) -> Vec<usize> {
+pub(crate) fn column_offsets(columns: &[BinaryColumnDescriptor]) -> Vec<usize> {
     let mut offsets = Vec::with_capacity(columns.len());
     let mut offset = 0usize;
     for col in columns {
@@ -369,7 +462,134 @@ pub fn read_binary_column(
     let col_offset = offsets[col_index];
     let data_start = hdu.data_start;
 
-    read_column_cells(fits_data, data_start, naxis1, naxis2, col, col_offset)
+    read_column_cells(
+        fits_data,
+        data_start,
+        naxis1,
+        naxis2,
+        col,
+        col_offset,
+        hdu.little_endian,
+    )
+}
+
+/// Rows per chunk in [`read_binary_columns`]; small enough that a chunk of a
+/// wide table stays in cache while every selected column is extracted.
+const COLUMN_CHUNK_ROWS: usize = 256;
+
+/// Read several columns from all rows of a binary table HDU.
+///
+/// `indices` are 0-based and may repeat. The column descriptors are parsed
+/// once, and rows are visited in chunks so that the selected cells of a
+/// chunk are extracted together. Returns one [`BinaryColumnData`] per
+/// index, in the order given.
+pub fn read_binary_columns(
+    fits_data: &[u8],
+    hdu: &Hdu,
+    indices: &[usize],
+) -> Result<Vec<BinaryColumnData>> {
+    let (naxis1, naxis2, columns) = extract_table_info(fits_data, hdu)?;
+    if indices.iter().any(|&i| i >= columns.len()) {
+        return Err(Error::InvalidValue);
+    }
+    let offsets = column_offsets(&columns);
+
+    let mut results = indices
+        .iter()
+        .map(|&i| {
+            read_column_cells(
+                fits_data,
+                hdu.data_start,
+                naxis1,
+                0,
+                &columns[i],
+                offsets[i],
+                hdu.little_endian,
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut start_row = 0;
+    while start_row < naxis2 {
+        let num_rows = COLUMN_CHUNK_ROWS.min(naxis2 - start_row);
+        let chunk_start = hdu.data_start + start_row * naxis1;
+        for (data, &i) in results.iter_mut().zip(indices) {
+            let chunk = read_column_cells(
+                fits_data,
+                chunk_start,
+                naxis1,
+                num_rows,
+                &columns[i],
+                offsets[i],
+                hdu.little_endian,
+            )?;
+            append_column_data(data, chunk)?;
+        }
+        start_row += num_rows;
+    }
+
+    Ok(results)
+}
+
+/// Read every column of a binary table HDU, paired with its display name.
+///
+/// Names follow [`column_display_name`], so they are unique and never empty.
+/// The descriptors are parsed once; variable-length array columns are read
+/// as by [`read_binary_column_vla`] and the rest as by [`read_binary_column`].
+pub fn binary_columns_named(
+    fits_data: &[u8],
+    hdu: &Hdu,
+) -> Result<Vec<(String, BinaryColumnData)>> {
+    let (naxis1, naxis2, columns) = extract_table_info(fits_data, hdu)?;
+    let offsets = column_offsets(&columns);
+
+    columns
+        .iter()
+        .enumerate()
+        .map(|(i, col)| {
+            let data = match col.col_type {
+                BinaryColumnType::VarArrayP(_) | BinaryColumnType::VarArrayQ(_) => {
+                    read_vla_column(fits_data, hdu, &columns, i)?
+                }
+                _ => read_column_cells(
+                    fits_data,
+                    hdu.data_start,
+                    naxis1,
+                    naxis2,
+                    col,
+                    offsets[i],
+                    hdu.little_endian,
+                )?,
+            };
+            Ok((column_display_name(&columns, i), data))
+        })
+        .collect()
+}
+
+/// Append the values of `more` to `data`; both must be the same variant.
+fn append_column_data(data: &mut BinaryColumnData, more: BinaryColumnData) -> Result<()> {
+    use BinaryColumnData as D;
+    match (data, more) {
+        (D::Logical(a), D::Logical(b)) => a.extend(b),
+        (D::Byte(a), D::Byte(b)) => a.extend(b),
+        (D::Short(a), D::Short(b)) => a.extend(b),
+        (D::Int(a), D::Int(b)) => a.extend(b),
+        (D::Long(a), D::Long(b)) => a.extend(b),
+        (D::Float(a), D::Float(b)) => a.extend(b),
+        (D::Double(a), D::Double(b)) => a.extend(b),
+        (D::ComplexFloat(a), D::ComplexFloat(b)) => a.extend(b),
+        (D::ComplexDouble(a), D::ComplexDouble(b)) => a.extend(b),
+        (D::Ascii(a), D::Ascii(b)) => a.extend(b),
+        (D::AsciiArray(a), D::AsciiArray(b)) => a.extend(b),
+        (D::Bit(a), D::Bit(b)) => a.extend(b),
+        (D::VarByte(a), D::VarByte(b)) => a.extend(b),
+        (D::VarShort(a), D::VarShort(b)) => a.extend(b),
+        (D::VarInt(a), D::VarInt(b)) => a.extend(b),
+        (D::VarLong(a), D::VarLong(b)) => a.extend(b),
+        (D::VarFloat(a), D::VarFloat(b)) => a.extend(b),
+        (D::VarDouble(a), D::VarDouble(b)) => a.extend(b),
+        _ => return Err(Error::InvalidValue),
+    }
+    Ok(())
 }
 
 /// Read a single column from a range of rows in a binary table HDU.
@@ -396,7 +616,159 @@ pub fn read_binary_column_range(
     let col_offset = offsets[col_index];
     let row_data_start = hdu.data_start + start_row * naxis1;
 
-    read_column_cells(fits_data, row_data_start, naxis1, num_rows, col, col_offset)
+    read_column_cells(
+        fits_data,
+        row_data_start,
+        naxis1,
+        num_rows,
+        col,
+        col_offset,
+        hdu.little_endian,
+    )
+}
+
+/// One row of a bit array (`X`) column, packed most-significant bit first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitRow {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitRow {
+    /// Wrap `len` packed bits. Bit 0 is the most significant bit of `bytes[0]`.
+    ///
+    /// Returns `Error::InvalidValue` if `bytes` is too short to hold `len` bits.
+    pub fn new(bytes: Vec<u8>, len: usize) -> Result<Self> {
+        if bytes.len() < len.div_ceil(8) {
+            return Err(Error::InvalidValue);
+        }
+        Ok(BitRow { bytes, len })
+    }
+
+    /// Number of bits in the row (the column's repeat count).
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the row holds no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns bit `i`, or `false` if `i` is out of range.
+    pub fn get(&self, i: usize) -> bool {
+        i < self.len && self.bytes[i / 8] & (0x80 >> (i % 8)) != 0
+    }
+
+    /// Number of set bits, ignoring the padding bits of the last byte.
+    pub fn count_ones(&self) -> usize {
+        let full = self.len / 8;
+        let mut count: usize = self.bytes[..full]
+            .iter()
+            .map(|b| b.count_ones() as usize)
+            .sum();
+        let rem = self.len % 8;
+        if rem > 0 {
+            let mask = 0xFFu8 << (8 - rem);
+            count += (self.bytes[full] & mask).count_ones() as usize;
+        }
+        count
+    }
+
+    /// The packed bytes, including any padding bits in the last byte.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Read a bit array (`X`) column as one [`BitRow`] per row.
+pub fn read_binary_column_bitset(
+    fits_data: &[u8],
+    hdu: &Hdu,
+    col_index: usize,
+) -> Result<Vec<BitRow>> {
+    let (_, _, columns) = extract_table_info(fits_data, hdu)?;
+    let col = columns.get(col_index).ok_or(Error::InvalidValue)?;
+    if col.col_type != BinaryColumnType::Bit {
+        return Err(Error::InvalidValue);
+    }
+    let repeat = col.repeat;
+    match read_binary_column(fits_data, hdu, col_index)? {
+        BinaryColumnData::Bit(rows) => rows
+            .into_iter()
+            .map(|bytes| BitRow::new(bytes, repeat))
+            .collect(),
+        _ => Err(Error::InvalidValue),
+    }
+}
+
+/// Read a character column as an array of strings per row.
+///
+/// An `A` column whose TDIMn has two or more dimensions covering its repeat
+/// count is split into strings of the first dimension's length, and a
+/// `:SSTRw` column into its substrings; each string stops at the first NUL
+/// and has trailing spaces removed. Any other `A` column yields one string
+/// per row. Returns `Error::InvalidValue` for non-character columns.
+pub fn read_binary_column_strings(
+    fits_data: &[u8],
+    hdu: &Hdu,
+    col_index: usize,
+) -> Result<Vec<Vec<String>>> {
+    let (naxis1, naxis2, columns) = extract_table_info(fits_data, hdu)?;
+    let col = columns.get(col_index).ok_or(Error::InvalidValue)?;
+    let width = match (&col.col_type, &col.tdim) {
+        (BinaryColumnType::Ascii, Some(dims))
+            if dims.len() >= 2 && dims[0] > 0 && dims.iter().product::<usize>() == col.repeat =>
+        {
+            dims[0]
+        }
+        (BinaryColumnType::Ascii, _) if col.repeat > 0 => col.repeat,
+        (BinaryColumnType::AsciiArray(width), _) => *width,
+        _ => return Err(Error::InvalidValue),
+    };
+    let split = BinaryColumnDescriptor {
+        col_type: BinaryColumnType::AsciiArray(width),
+        ..col.clone()
+    };
+    let col_offset = column_offsets(&columns)[col_index];
+    match read_column_cells(
+        fits_data,
+        hdu.data_start,
+        naxis1,
+        naxis2,
+        &split,
+        col_offset,
+        hdu.little_endian,
+    )? {
+        BinaryColumnData::AsciiArray(rows) => Ok(rows),
+        _ => Err(Error::InvalidValue),
+    }
+}
+
+/// Borrow the stored bytes of one column, one slice per row.
+///
+/// Each slice is `byte_width` bytes of the main table, in stored byte order
+/// and undecoded, for callers handling encodings this crate does not interpret
+/// (for example packed structs in a `B` column). For a variable-length
+/// array column the slices hold the heap descriptors, not the arrays.
+pub fn read_binary_column_raw<'a>(
+    fits_data: &'a [u8],
+    hdu: &Hdu,
+    col_index: usize,
+) -> Result<Vec<&'a [u8]>> {
+    let (naxis1, naxis2, columns) = extract_table_info(fits_data, hdu)?;
+    let col = columns.get(col_index).ok_or(Error::InvalidValue)?;
+    let col_offset = column_offsets(&columns)[col_index];
+    if col_offset + col.byte_width > naxis1 {
+        return Err(Error::InvalidHeader("column extends past NAXIS1"));
+    }
+
+    Ok((0..naxis2)
+        .map(|row| {
+            let base = hdu.data_start + row * naxis1 + col_offset;
+            &fits_data[base..base + col.byte_width]
+        })
+        .collect())
 }
 
 /// Write column data into an existing binary table HDU in-place.
@@ -428,14 +800,76 @@ pub fn write_binary_column(
     Ok(())
 }
 
-fn read_column_cells(
+/// Decode a stored logical byte.
+///
+/// `T` and `1` are true. `F`, `0`, and the undefined-logical byte 0x00
+/// (as well as anything else) read as false.
+fn logical_byte_is_true(b: u8) -> bool {
+    matches!(b, b'T' | b'1')
+}
+
+/// Read `naxis2` cells of one fixed-width column starting at `data_start`.
+///
+/// Numeric values are stored big-endian; pass `little_endian` for an HDU
+/// marked [`Hdu::little_endian`] to have them byte-swapped after reading.
+pub(crate) fn read_column_cells(
     fits_data: &[u8],
     data_start: usize,
     naxis1: usize,
     naxis2: usize,
     col: &BinaryColumnDescriptor,
     col_offset: usize,
+    little_endian: bool,
 ) -> Result<BinaryColumnData> {
+    let mut data = read_column_cells_be(fits_data, data_start, naxis1, naxis2, col, col_offset)?;
+    if little_endian {
+        swap_column_bytes(&mut data);
+    }
+    Ok(data)
+}
+
+/// Reverse the byte order of every numeric value in `data`.
+fn swap_column_bytes(data: &mut BinaryColumnData) {
+    use BinaryColumnData as D;
+    let f32_swap = |v: f32| f32::from_bits(v.to_bits().swap_bytes());
+    let f64_swap = |v: f64| f64::from_bits(v.to_bits().swap_bytes());
+    match data {
+        D::Short(v) => v.iter_mut().for_each(|x| *x = x.swap_bytes()),
+        D::Int(v) => v.iter_mut().for_each(|x| *x = x.swap_bytes()),
+        D::Long(v) => v.iter_mut().for_each(|x| *x = x.swap_bytes()),
+        D::Float(v) => v.iter_mut().for_each(|x| *x = f32_swap(*x)),
+        D::Double(v) => v.iter_mut().for_each(|x| *x = f64_swap(*x)),
+        D::ComplexFloat(v) => v
+            .iter_mut()
+            .for_each(|(re, im)| (*re, *im) = (f32_swap(*re), f32_swap(*im))),
+        D::ComplexDouble(v) => v
+            .iter_mut()
+            .for_each(|(re, im)| (*re, *im) = (f64_swap(*re), f64_swap(*im))),
+        _ => {}
+    }
+}
+
+fn read_column_cells_be(
+    fits_data: &[u8],
+    data_start: usize,
+    naxis1: usize,
+    naxis2: usize,
+    col: &BinaryColumnDescriptor,
+    col_offset: usize,
+) -> Result<BinaryColumnData> {
+    // Every cell lies below the end of the last row's cell, so one check up
+    // front keeps the indexing below from panicking on truncated data.
+    if naxis2 > 0 {
+        let end = (naxis2 - 1)
+            .checked_mul(naxis1)
+            .and_then(|n| n.checked_add(data_start))
+            .and_then(|n| n.checked_add(col_offset))
+            .and_then(|n| n.checked_add(col.byte_width));
+        if end.is_none_or(|end| end > fits_data.len()) {
+            return Err(Error::UnexpectedEof);
+        }
+    }
+
     match col.col_type {
         BinaryColumnType::VarArrayP(_) | BinaryColumnType::VarArrayQ(_) => Err(Error::InvalidValue),
         BinaryColumnType::Logical => {
@@ -443,8 +877,7 @@ fn read_column_cells(
             for row in 0..naxis2 {
                 let base = data_start + row * naxis1 + col_offset;
                 for r in 0..col.repeat {
-                    let b = fits_data[base + r];
-                    values.push(b == b'T');
+                    values.push(logical_byte_is_true(fits_data[base + r]));
                 }
             }
             Ok(BinaryColumnData::Logical(values))
@@ -553,6 +986,26 @@ fn read_column_cells(
             }
             Ok(BinaryColumnData::Ascii(values))
         }
+        BinaryColumnType::AsciiArray(width) => {
+            let count = col.repeat / width;
+            let mut values = Vec::with_capacity(naxis2);
+            for row in 0..naxis2 {
+                let base = data_start + row * naxis1 + col_offset;
+                let mut substrings = Vec::with_capacity(count);
+                for i in 0..count {
+                    let bytes = &fits_data[base + i * width..base + (i + 1) * width];
+                    // Substrings may be NUL-terminated instead of space-padded.
+                    let len = bytes.iter().position(|&b| b == 0).unwrap_or(width);
+                    let s = core::str::from_utf8(&bytes[..len])
+                        .map_err(|_| Error::InvalidValue)?
+                        .trim_end()
+                        .into();
+                    substrings.push(s);
+                }
+                values.push(substrings);
+            }
+            Ok(BinaryColumnData::AsciiArray(values))
+        }
         BinaryColumnType::Bit => {
             let bytes_per_row = col.repeat.div_ceil(8);
             let mut values = Vec::with_capacity(naxis2);
@@ -572,6 +1025,59 @@ pub fn read_binary_row(
     hdu: &Hdu,
     row_index: usize,
 ) -> Result<Vec<BinaryColumnData>> {
+    read_row_cells(fits_data, hdu, row_index).map(|(_, cells)| cells)
+}
+
+/// A type that can be built from one row of a binary table.
+///
+/// Implementations pick the cells they need by column name from the
+/// [`BinaryRow`] and convert them into their own fields.
+pub trait FromFitsRow: Sized {
+    /// Build a value from the cells of `row`.
+    fn from_fits_row(row: &BinaryRow) -> Result<Self>;
+}
+
+/// The cells of one binary table row, looked up by column name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryRow {
+    columns: Vec<BinaryColumnDescriptor>,
+    cells: Vec<BinaryColumnData>,
+}
+
+impl BinaryRow {
+    /// Returns the cell of the first column whose TTYPEn is `name`.
+    ///
+    /// Each cell holds a single row, so scalar columns have one element.
+    pub fn get(&self, name: &str) -> Option<&BinaryColumnData> {
+        let index = self
+            .columns
+            .iter()
+            .position(|col| col.name.as_deref().map(str::trim) == Some(name))?;
+        self.cells.get(index)
+    }
+
+    /// Like [`get`](Self::get), but fails with
+    /// `Error::MissingKeyword("TTYPEn")` when no column is named `name`.
+    pub fn column(&self, name: &str) -> Result<&BinaryColumnData> {
+        self.get(name).ok_or(Error::MissingKeyword("TTYPEn"))
+    }
+}
+
+/// Read one row of a binary table into a [`FromFitsRow`] type.
+pub fn read_binary_row_as<T: FromFitsRow>(
+    fits_data: &[u8],
+    hdu: &Hdu,
+    row_index: usize,
+) -> Result<T> {
+    let (columns, cells) = read_row_cells(fits_data, hdu, row_index)?;
+    T::from_fits_row(&BinaryRow { columns, cells })
+}
+
+fn read_row_cells(
+    fits_data: &[u8],
+    hdu: &Hdu,
+    row_index: usize,
+) -> Result<(Vec<BinaryColumnDescriptor>, Vec<BinaryColumnData>)> {
     let (naxis1, naxis2, columns) = extract_table_info(fits_data, hdu)?;
 
     if row_index >= naxis2 {
@@ -591,11 +1097,12 @@ pub fn read_binary_row(
             1,
             col,
             col_offset + row_index * naxis1,
+            hdu.little_endian,
         )?;
         result.push(cell);
     }
 
-    Ok(result)
+    Ok((columns, result))
 }
 
 /// Extract TSCALn and TZEROn calibration values for a column.
@@ -612,7 +1119,9 @@ pub fn extract_column_scaling(cards: &[Card], col_number: usize) -> (f64, f64) {
 
 /// Apply TSCALn/TZEROn calibration to column data.
 ///
-/// Computes `physical = tzero + tscal * raw` for every element.
+/// Computes `physical = tzero + tscal * raw` for every element. Complex
+/// columns are scaled on both the real and imaginary parts and returned
+/// interleaved as `[re0, im0, re1, im1, ...]`.
 pub fn apply_column_scaling(data: &BinaryColumnData, tscal: f64, tzero: f64) -> Vec<f64> {
     match data {
         BinaryColumnData::Byte(v) => v.iter().map(|&x| tzero + tscal * (x as f64)).collect(),
@@ -625,9 +1134,16 @@ pub fn apply_column_scaling(data: &BinaryColumnData, tscal: f64, tzero: f64) ->
             .iter()
             .map(|&x| tzero + tscal * (x as u8 as f64))
             .collect(),
-        BinaryColumnData::ComplexFloat(_)
-        | BinaryColumnData::ComplexDouble(_)
-        | BinaryColumnData::Ascii(_)
+        BinaryColumnData::ComplexFloat(v) => v
+            .iter()
+            .flat_map(|&(re, im)| [tzero + tscal * (re as f64), tzero + tscal * (im as f64)])
+            .collect(),
+        BinaryColumnData::ComplexDouble(v) => v
+            .iter()
+            .flat_map(|&(re, im)| [tzero + tscal * re, tzero + tscal * im])
+            .collect(),
+        BinaryColumnData::Ascii(_)
+        | BinaryColumnData::AsciiArray(_)
         | BinaryColumnData::Bit(_)
         | BinaryColumnData::VarByte(_)
         | BinaryColumnData::VarShort(_)
@@ -642,62 +1158,87 @@ pub fn apply_column_scaling(data: &BinaryColumnData, tscal: f64, tzero: f64) ->
 ///
 /// Returns calibrated physical values as `Vec<f64>`. The column index
 /// is 0-based; the corresponding FITS keywords use 1-based numbering.
+///
+/// Complex columns are returned interleaved (see [`apply_column_scaling`]).
+/// TSCALn/TZEROn are not permitted on `A`, `L` and `X` columns; if either
+/// keyword is present on such a column, `Error::InvalidScaling(col_index)`
+/// is returned.
 pub fn read_binary_column_physical(
     fits_data: &[u8],
     hdu: &Hdu,
     col_index: usize,
 ) -> Result<Vec<f64>> {
     let raw = read_binary_column(fits_data, hdu, col_index)?;
+    if matches!(
+        raw,
+        BinaryColumnData::Ascii(_)
+            | BinaryColumnData::AsciiArray(_)
+            | BinaryColumnData::Logical(_)
+            | BinaryColumnData::Bit(_)
+    ) {
+        let n = col_index + 1;
+        if has_keyword(&hdu.cards, &alloc::format!("TSCAL{}", n))
+            || has_keyword(&hdu.cards, &alloc::format!("TZERO{}", n))
+        {
+            return Err(Error::InvalidScaling(col_index));
+        }
+    }
     let (tscal, tzero) = extract_column_scaling(&hdu.cards, col_index + 1);
     Ok(apply_column_scaling(&raw, tscal, tzero))
 }
 /// Read a 32-bit P-descriptor: (element_count, heap_byte_offset).
-fn read_p_descriptor(data: &[u8]) -> (usize, usize) {
-    let count = read_i32_be(data) as u32 as usize;
-    let offset = read_i32_be(&data[4..]) as u32 as usize;
-    (count, offset)
+///
+/// Returns `None` if `data` is shorter than the 8-byte descriptor.
+fn read_p_descriptor(data: &[u8]) -> Option<(usize, usize)> {
+    let count = try_read_u32_be(data)? as usize;
+    let offset = try_read_u32_be(data.get(4..)?)? as usize;
+    Some((count, offset))
 }
 
 /// Read a 64-bit Q-descriptor: (element_count, heap_byte_offset).
-fn read_q_descriptor(data: &[u8]) -> (usize, usize) {
-    let count = read_i64_be(data) as u64 as usize;
-    let offset = read_i64_be(&data[8..]) as u64 as usize;
-    (count, offset)
+///
+/// Returns `None` if `data` is shorter than the 16-byte descriptor.
+fn read_q_descriptor(data: &[u8]) -> Option<(usize, usize)> {
+    let count = try_read_u64_be(data)? as usize;
+    let offset = try_read_u64_be(data.get(8..)?)? as usize;
+    Some((count, offset))
 }
 
 /// Read a variable-length array column from all rows of a binary table HDU.
 ///
 /// The column must have a `VarArrayP` or `VarArrayQ` type.  Each row stores a
 /// P/Q descriptor in the main table that points to `count` elements in the heap
-/// area (which begins at `NAXIS1*NAXIS2 + THEAP` bytes after data_start).
+/// area, which begins `THEAP` bytes after data_start (by default
+/// `NAXIS1*NAXIS2`, right after the main table). Tables marked
+/// [`Hdu::little_endian`] are rejected with `Error::InvalidHeader`.
 pub fn read_binary_column_vla(
     fits_data: &[u8],
     hdu: &Hdu,
     col_index: usize,
 ) -> Result<BinaryColumnData> {
-    let (naxis1, naxis2, pcount) = match &hdu.info {
-        HduInfo::BinaryTable {
-            naxis1,
-            naxis2,
-            pcount,
-            ..
-        } => (*naxis1, *naxis2, *pcount),
-        _ => return Err(Error::InvalidHeader("not a binary table HDU")),
-    };
-
-    let columns = parse_binary_table_columns(
-        &hdu.cards,
-        match &hdu.info {
-            HduInfo::BinaryTable { tfields, .. } => *tfields,
-            _ => return Err(Error::InvalidHeader("not a binary table HDU")),
-        },
-    )?;
+    let (_, _, _, tfields) = table_shape(hdu)?;
+    let columns = parse_binary_table_columns(&hdu.cards, tfields)?;
+    read_vla_column(fits_data, hdu, &columns, col_index)
+}
 
+/// [`read_binary_column_vla`] with the column descriptors already parsed.
+fn read_vla_column(
+    fits_data: &[u8],
+    hdu: &Hdu,
+    columns: &[BinaryColumnDescriptor],
+    col_index: usize,
+) -> Result<BinaryColumnData> {
+    let (naxis1, naxis2, pcount, _) = table_shape(hdu)?;
     if col_index >= columns.len() {
         return Err(Error::InvalidValue);
     }
+    if hdu.little_endian {
+        return Err(Error::InvalidHeader(
+            "little-endian variable-length arrays are not supported",
+        ));
+    }
 
-    let offsets = column_offsets(&columns);
+    let offsets = column_offsets(columns);
     let col = &columns[col_index];
     let col_offset = offsets[col_index];
     let data_start = hdu.data_start;
@@ -710,13 +1251,23 @@ pub fn read_binary_column_vla(
 
     // THEAP: byte offset from start of main data to start of heap.
     // Default is NAXIS1 * NAXIS2 (heap starts right after the main table).
-    let theap = card_int_value(&hdu.cards, "THEAP")
-        .map(|v| v as usize)
-        .unwrap_or(naxis1 * naxis2);
+    // The gap plus heap fill the PCOUNT bytes after the table, so THEAP must
+    // lie between the end of the table and the end of that area.
+    let table_len = naxis1 * naxis2;
+    let theap = match card_int_value(&hdu.cards, "THEAP") {
+        Some(v) => usize::try_from(v).map_err(|_| Error::InvalidHeader("negative THEAP"))?,
+        None => table_len,
+    };
+    if theap < table_len {
+        return Err(Error::InvalidHeader("THEAP points inside the main table"));
+    }
+    if theap > table_len + pcount {
+        return Err(Error::InvalidHeader("THEAP points past the heap"));
+    }
     let heap_start = data_start + theap;
 
     // Verify heap is within bounds.
-    if heap_start + pcount > fits_data.len() {
+    if data_start + table_len + pcount > fits_data.len() {
         return Err(Error::UnexpectedEof);
     }
 
@@ -729,134 +1280,78 @@ pub fn read_binary_column_vla(
         _ => return Err(Error::InvalidValue),
     };
 
-    match elem_type {
-        'B' => {
-            let mut rows = Vec::with_capacity(naxis2);
-            for row in 0..naxis2 {
-                let desc_pos = data_start + row * naxis1 + col_offset;
-                let (count, offset) = if is_q {
-                    read_q_descriptor(&fits_data[desc_pos..])
-                } else {
-                    read_p_descriptor(&fits_data[desc_pos..])
-                };
-                let start = heap_start + offset;
-                let end = start + count * elem_size;
-                if end > fits_data.len() {
-                    return Err(Error::UnexpectedEof);
-                }
-                rows.push(fits_data[start..end].to_vec());
-            }
-            Ok(BinaryColumnData::VarByte(rows))
-        }
-        'I' => {
-            let mut rows = Vec::with_capacity(naxis2);
-            for row in 0..naxis2 {
-                let desc_pos = data_start + row * naxis1 + col_offset;
-                let (count, offset) = if is_q {
-                    read_q_descriptor(&fits_data[desc_pos..])
-                } else {
-                    read_p_descriptor(&fits_data[desc_pos..])
-                };
-                let start = heap_start + offset;
-                if start + count * elem_size > fits_data.len() {
-                    return Err(Error::UnexpectedEof);
-                }
-                let mut vals = Vec::with_capacity(count);
-                for i in 0..count {
-                    vals.push(read_i16_be(&fits_data[start + i * 2..]));
-                }
-                rows.push(vals);
-            }
-            Ok(BinaryColumnData::VarShort(rows))
-        }
-        'J' => {
-            let mut rows = Vec::with_capacity(naxis2);
-            for row in 0..naxis2 {
-                let desc_pos = data_start + row * naxis1 + col_offset;
-                let (count, offset) = if is_q {
-                    read_q_descriptor(&fits_data[desc_pos..])
-                } else {
-                    read_p_descriptor(&fits_data[desc_pos..])
-                };
-                let start = heap_start + offset;
-                if start + count * elem_size > fits_data.len() {
-                    return Err(Error::UnexpectedEof);
-                }
-                let mut vals = Vec::with_capacity(count);
-                for i in 0..count {
-                    vals.push(read_i32_be(&fits_data[start + i * 4..]));
-                }
-                rows.push(vals);
-            }
-            Ok(BinaryColumnData::VarInt(rows))
-        }
-        'K' => {
-            let mut rows = Vec::with_capacity(naxis2);
-            for row in 0..naxis2 {
-                let desc_pos = data_start + row * naxis1 + col_offset;
-                let (count, offset) = if is_q {
-                    read_q_descriptor(&fits_data[desc_pos..])
-                } else {
-                    read_p_descriptor(&fits_data[desc_pos..])
-                };
-                let start = heap_start + offset;
-                if start + count * elem_size > fits_data.len() {
-                    return Err(Error::UnexpectedEof);
-                }
-                let mut vals = Vec::with_capacity(count);
-                for i in 0..count {
-                    vals.push(read_i64_be(&fits_data[start + i * 8..]));
-                }
-                rows.push(vals);
-            }
-            Ok(BinaryColumnData::VarLong(rows))
-        }
-        'E' => {
-            let mut rows = Vec::with_capacity(naxis2);
-            for row in 0..naxis2 {
-                let desc_pos = data_start + row * naxis1 + col_offset;
-                let (count, offset) = if is_q {
-                    read_q_descriptor(&fits_data[desc_pos..])
-                } else {
-                    read_p_descriptor(&fits_data[desc_pos..])
-                };
-                let start = heap_start + offset;
-                if start + count * elem_size > fits_data.len() {
-                    return Err(Error::UnexpectedEof);
-                }
-                let mut vals = Vec::with_capacity(count);
-                for i in 0..count {
-                    vals.push(read_f32_be(&fits_data[start + i * 4..]));
-                }
-                rows.push(vals);
-            }
-            Ok(BinaryColumnData::VarFloat(rows))
+    // The heap bytes of one row's array. Descriptors come from the file, so
+    // their counts and offsets are checked before any slicing.
+    let cell = |row: usize| -> Result<&[u8]> {
+        let desc = fits_data
+            .get(data_start + row * naxis1 + col_offset..)
+            .unwrap_or(&[]);
+        let (count, offset) = if is_q {
+            read_q_descriptor(desc)
+        } else {
+            read_p_descriptor(desc)
         }
-        'D' => {
-            let mut rows = Vec::with_capacity(naxis2);
-            for row in 0..naxis2 {
-                let desc_pos = data_start + row * naxis1 + col_offset;
-                let (count, offset) = if is_q {
-                    read_q_descriptor(&fits_data[desc_pos..])
-                } else {
-                    read_p_descriptor(&fits_data[desc_pos..])
-                };
-                let start = heap_start + offset;
-                if start + count * elem_size > fits_data.len() {
-                    return Err(Error::UnexpectedEof);
-                }
-                let mut vals = Vec::with_capacity(count);
-                for i in 0..count {
-                    vals.push(read_f64_be(&fits_data[start + i * 8..]));
-                }
-                rows.push(vals);
-            }
-            Ok(BinaryColumnData::VarDouble(rows))
+        .ok_or(Error::UnexpectedEof)?;
+        let start = heap_start.checked_add(offset);
+        let end = count
+            .checked_mul(elem_size)
+            .and_then(|len| start?.checked_add(len));
+        match (start, end) {
+            (Some(start), Some(end)) if end <= fits_data.len() => Ok(&fits_data[start..end]),
+            _ => Err(Error::UnexpectedEof),
         }
+    };
+
+    match elem_type {
+        'B' => Ok(BinaryColumnData::VarByte(vla_rows(
+            naxis2, cell, 1, read_u8,
+        )?)),
+        'I' => Ok(BinaryColumnData::VarShort(vla_rows(
+            naxis2,
+            cell,
+            2,
+            read_i16_be,
+        )?)),
+        'J' => Ok(BinaryColumnData::VarInt(vla_rows(
+            naxis2,
+            cell,
+            4,
+            read_i32_be,
+        )?)),
+        'K' => Ok(BinaryColumnData::VarLong(vla_rows(
+            naxis2,
+            cell,
+            8,
+            read_i64_be,
+        )?)),
+        'E' => Ok(BinaryColumnData::VarFloat(vla_rows(
+            naxis2,
+            cell,
+            4,
+            read_f32_be,
+        )?)),
+        'D' => Ok(BinaryColumnData::VarDouble(vla_rows(
+            naxis2,
+            cell,
+            8,
+            read_f64_be,
+        )?)),
         _ => Err(Error::InvalidValue),
     }
 }
 
+/// Decode each row's heap bytes, as returned by `cell`, into elements.
+fn vla_rows<'a, T>(
+    naxis2: usize,
+    cell: impl Fn(usize) -> Result<&'a [u8]>,
+    elem_size: usize,
+    decode: fn(&[u8]) -> T,
+) -> Result<Vec<Vec<T>>> {
+    (0..naxis2)
+        .map(|row| Ok(cell(row)?.chunks_exact(elem_size).map(decode).collect()))
+        .collect()
+}
+
 /// Serialize a single cell (one column, one row) to big-endian bytes.
 pub fn serialize_binary_column_value(
     col_type: &BinaryColumnType,
@@ -944,12 +1439,62 @@ pub fn serialize_binary_column_value(
             out[..len].copy_from_slice(&s[..len]);
             Ok(out)
         }
+        (BinaryColumnType::AsciiArray(width), BinaryColumnData::AsciiArray(vals)) => {
+            let mut out = vec![b' '; repeat];
+            let row = vals.get(row_index).ok_or(Error::InvalidValue)?;
+            for (i, sub) in row.iter().take(repeat / width).enumerate() {
+                let s = sub.as_bytes();
+                let len = s.len().min(*width);
+                out[i * width..i * width + len].copy_from_slice(&s[..len]);
+            }
+            Ok(out)
+        }
         (BinaryColumnType::Bit, BinaryColumnData::Bit(vals)) => Ok(vals[row_index].clone()),
         _ => Err(Error::InvalidValue),
     }
 }
 
-fn tform_string(repeat: usize, col_type: &BinaryColumnType) -> String {
+/// Encode one row of a variable-length array column as big-endian heap bytes.
+///
+/// Returns the element count and the encoded bytes. The data variant must
+/// match the element type code of the `P`/`Q` descriptor.
+fn var_row_heap_bytes(elem: char, data: &BinaryColumnData, row: usize) -> Result<(usize, Vec<u8>)> {
+    fn encode<T: Copy>(vals: &[T], size: usize, write: fn(&mut [u8], T)) -> (usize, Vec<u8>) {
+        let mut out = vec![0u8; vals.len() * size];
+        for (i, &v) in vals.iter().enumerate() {
+            write(&mut out[i * size..], v);
+        }
+        (vals.len(), out)
+    }
+
+    match (elem, data) {
+        ('B', BinaryColumnData::VarByte(rows)) => Ok((rows[row].len(), rows[row].clone())),
+        ('I', BinaryColumnData::VarShort(rows)) => Ok(encode(&rows[row], 2, write_i16_be)),
+        ('J', BinaryColumnData::VarInt(rows)) => Ok(encode(&rows[row], 4, write_i32_be)),
+        ('K', BinaryColumnData::VarLong(rows)) => Ok(encode(&rows[row], 8, write_i64_be)),
+        ('E', BinaryColumnData::VarFloat(rows)) => Ok(encode(&rows[row], 4, write_f32_be)),
+        ('D', BinaryColumnData::VarDouble(rows)) => Ok(encode(&rows[row], 8, write_f64_be)),
+        _ => Err(Error::InvalidValue),
+    }
+}
+
+/// Longest row of a variable-length array column, or `None` for fixed-width data.
+fn var_max_len(data: &BinaryColumnData) -> Option<usize> {
+    let lens: Vec<usize> = match data {
+        BinaryColumnData::VarByte(rows) => rows.iter().map(Vec::len).collect(),
+        BinaryColumnData::VarShort(rows) => rows.iter().map(Vec::len).collect(),
+        BinaryColumnData::VarInt(rows) => rows.iter().map(Vec::len).collect(),
+        BinaryColumnData::VarLong(rows) => rows.iter().map(Vec::len).collect(),
+        BinaryColumnData::VarFloat(rows) => rows.iter().map(Vec::len).collect(),
+        BinaryColumnData::VarDouble(rows) => rows.iter().map(Vec::len).collect(),
+        _ => return None,
+    };
+    Some(lens.into_iter().max().unwrap_or(0))
+}
+
+/// Format a `TFORMn` value. `P`/`Q` descriptors get a `(max)` suffix when
+/// the maximum array length is known; other types ignore `max_len`.
+fn tform_string(repeat: usize, col_type: &BinaryColumnType, max_len: Option<usize>) -> String {
     let ch = match col_type {
         BinaryColumnType::Logical => 'L',
         BinaryColumnType::Bit => 'X',
@@ -962,11 +1507,19 @@ fn tform_string(repeat: usize, col_type: &BinaryColumnType) -> String {
         BinaryColumnType::ComplexFloat => 'C',
         BinaryColumnType::ComplexDouble => 'M',
         BinaryColumnType::Ascii => 'A',
-        BinaryColumnType::VarArrayP(elem) => {
-            return alloc::format!("{}P{}", repeat, elem);
+        BinaryColumnType::AsciiArray(width) => {
+            return alloc::format!("{}A:SSTR{}", repeat, width);
         }
-        BinaryColumnType::VarArrayQ(elem) => {
-            return alloc::format!("{}Q{}", repeat, elem);
+        BinaryColumnType::VarArrayP(elem) | BinaryColumnType::VarArrayQ(elem) => {
+            let code = if matches!(col_type, BinaryColumnType::VarArrayP(_)) {
+                'P'
+            } else {
+                'Q'
+            };
+            return match max_len {
+                Some(max) => alloc::format!("{}{}{}({})", repeat, code, elem, max),
+                None => alloc::format!("{}{}{}", repeat, code, elem),
+            };
         }
     };
     alloc::format!("{}{}", repeat, ch)
@@ -981,10 +1534,26 @@ fn make_card(keyword: &str, value: Value) -> Card {
 }
 
 /// Build the full set of header cards for a binary table extension.
+///
+/// Without the column data the maximum array length of variable-length
+/// columns is unknown, so their `TFORMn` is written as plain `rPt`/`rQt` and
+/// `pcount` must be the heap size the caller will write.
+/// [`serialize_binary_table_hdu`] derives both from the data instead.
 pub fn build_binary_table_cards(
     columns: &[BinaryColumnDescriptor],
     naxis2: usize,
     pcount: usize,
+) -> Result<Vec<Card>> {
+    binary_table_cards(columns, naxis2, pcount, &[])
+}
+
+/// Build binary table header cards, writing `TFORMn` as `rPt(max)` for
+/// variable-length columns whose entry in `var_max` is known.
+fn binary_table_cards(
+    columns: &[BinaryColumnDescriptor],
+    naxis2: usize,
+    pcount: usize,
+    var_max: &[Option<usize>],
 ) -> Result<Vec<Card>> {
     let naxis1: usize = columns.iter().map(|c| c.byte_width).sum();
     let tfields = columns.len();
@@ -1002,7 +1571,8 @@ pub fn build_binary_table_cards(
 
     for (i, col) in columns.iter().enumerate() {
         let n = i + 1;
-        let tform = tform_string(col.repeat, &col.col_type);
+        let max_len = var_max.get(i).copied().flatten();
+        let tform = tform_string(col.repeat, &col.col_type, max_len);
         let tform_kw = alloc::format!("TFORM{}", n);
         cards.push(make_card(&tform_kw, Value::String(tform)));
 
@@ -1024,33 +1594,82 @@ pub fn build_binary_table_cards(
 
 /// Serialize all rows of a binary table into padded FITS data bytes.
 ///
-/// The returned buffer is padded to a multiple of 2880 bytes.
+/// Variable-length array columns are written as `P`/`Q` descriptors in the
+/// main table with their elements in a heap directly after it. The returned
+/// buffer is padded to a multiple of 2880 bytes.
 pub fn serialize_binary_table(
     columns: &[BinaryColumnDescriptor],
     col_data: &[BinaryColumnData],
     naxis2: usize,
 ) -> Result<Vec<u8>> {
+    serialize_binary_table_with_len(columns, col_data, naxis2).map(|s| s.bytes)
+}
+
+/// Serialize a binary table like [`serialize_binary_table`], also returning
+/// the unpadded length (`NAXIS1 * NAXIS2` plus the heap size).
+pub fn serialize_binary_table_with_len(
+    columns: &[BinaryColumnDescriptor],
+    col_data: &[BinaryColumnData],
+    naxis2: usize,
+) -> Result<Serialized> {
+    let (main, heap) = serialize_table_and_heap(columns, col_data, naxis2)?;
+    let mut bytes = main;
+    bytes.extend_from_slice(&heap);
+    let raw_len = bytes.len();
+    Ok(Serialized {
+        bytes: pad_to_block(bytes, DATA_PAD_BYTE),
+        raw_len,
+    })
+}
+
+/// Serialize the main table rows and the VLA heap, both unpadded.
+fn serialize_table_and_heap(
+    columns: &[BinaryColumnDescriptor],
+    col_data: &[BinaryColumnData],
+    naxis2: usize,
+) -> Result<(Vec<u8>, Vec<u8>)> {
     if columns.len() != col_data.len() {
         return Err(Error::InvalidValue);
     }
 
     let naxis1: usize = columns.iter().map(|c| c.byte_width).sum();
-    let raw_len = naxis1 * naxis2;
-    let padded_len = padded_byte_len(raw_len);
-    let mut buf = vec![0u8; padded_len];
+    let mut buf = vec![0u8; naxis1 * naxis2];
+    let mut heap = Vec::new();
 
     for row in 0..naxis2 {
         let mut col_offset = 0usize;
         for (col_idx, col) in columns.iter().enumerate() {
-            let cell_bytes =
-                serialize_binary_column_value(&col.col_type, col.repeat, &col_data[col_idx], row)?;
             let dest_start = row * naxis1 + col_offset;
-            buf[dest_start..dest_start + cell_bytes.len()].copy_from_slice(&cell_bytes);
+            match col.col_type {
+                BinaryColumnType::VarArrayP(elem) | BinaryColumnType::VarArrayQ(elem) => {
+                    let (count, bytes) = var_row_heap_bytes(elem, &col_data[col_idx], row)?;
+                    let dest = &mut buf[dest_start..];
+                    if matches!(col.col_type, BinaryColumnType::VarArrayP(_)) {
+                        let count = i32::try_from(count).map_err(|_| Error::InvalidValue)?;
+                        let offset = i32::try_from(heap.len()).map_err(|_| Error::InvalidValue)?;
+                        write_i32_be(dest, count);
+                        write_i32_be(&mut dest[4..], offset);
+                    } else {
+                        write_i64_be(dest, count as i64);
+                        write_i64_be(&mut dest[8..], heap.len() as i64);
+                    }
+                    heap.extend_from_slice(&bytes);
+                }
+                _ => {
+                    let cell_bytes = serialize_binary_column_value(
+                        &col.col_type,
+                        col.repeat,
+                        &col_data[col_idx],
+                        row,
+                    )?;
+                    buf[dest_start..dest_start + cell_bytes.len()].copy_from_slice(&cell_bytes);
+                }
+            }
             col_offset += col.byte_width;
         }
     }
 
-    Ok(buf)
+    Ok((buf, heap))
 }
 
 /// Build and serialize a complete binary table HDU (header + data).
@@ -1061,9 +1680,14 @@ pub fn serialize_binary_table_hdu(
     col_data: &[BinaryColumnData],
     naxis2: usize,
 ) -> Result<Vec<u8>> {
-    let cards = build_binary_table_cards(columns, naxis2, 0)?;
+    let (main, heap) = serialize_table_and_heap(columns, col_data, naxis2)?;
+    let var_max: Vec<Option<usize>> = col_data.iter().map(var_max_len).collect();
+    let cards = binary_table_cards(columns, naxis2, heap.len(), &var_max)?;
     let header_bytes = serialize_header(&cards)?;
-    let data_bytes = serialize_binary_table(columns, col_data, naxis2)?;
+
+    let mut data_bytes = main;
+    data_bytes.extend_from_slice(&heap);
+    let data_bytes = pad_to_block(data_bytes, DATA_PAD_BYTE);
 
     let mut result = Vec::with_capacity(header_bytes.len() + data_bytes.len());
     result.extend_from_slice(&header_bytes);
@@ -1071,6 +1695,155 @@ pub fn serialize_binary_table_hdu(
     Ok(result)
 }
 
+/// Build a complete FITS file holding a single binary table.
+///
+/// An empty primary HDU (`NAXIS = 0`, `EXTEND = T`) is written first, followed
+/// by the binary table extension, so the result can be written straight to
+/// disk or handed to [`crate::hdu::parse_fits`].
+pub fn build_fits_with_binary_table(
+    columns: &[BinaryColumnDescriptor],
+    col_data: &[BinaryColumnData],
+    naxis2: usize,
+) -> Result<Vec<u8>> {
+    let mut primary_cards = crate::primary::build_primary_header(8, &[])?;
+    primary_cards.push(Card {
+        keyword: make_keyword("EXTEND"),
+        value: Some(Value::Logical(true)),
+        comment: Some(String::from("FITS dataset may contain extensions")),
+    });
+    let primary_bytes = serialize_header(&primary_cards)?;
+    let table_bytes = serialize_binary_table_hdu(columns, col_data, naxis2)?;
+
+    let mut result = Vec::with_capacity(primary_bytes.len() + table_bytes.len());
+    result.extend_from_slice(&primary_bytes);
+    result.extend_from_slice(&table_bytes);
+    Ok(result)
+}
+
+/// Per-column keyword roots that carry a trailing column number.
+const COLUMN_KEYWORD_ROOTS: &[&str] = &[
+    "TTYPE", "TFORM", "TBCOL", "TUNIT", "TSCAL", "TZERO", "TNULL", "TDISP", "TDIM", "TDMIN",
+    "TDMAX", "TLMIN", "TLMAX", "TCTYP", "TCUNI", "TCRVL", "TCDLT", "TCRPX", "TCROT",
+];
+
+/// Split a column keyword such as `TFORM12` into its root and 1-based column number.
+fn split_column_keyword(keyword: &str) -> Option<(&'static str, usize)> {
+    COLUMN_KEYWORD_ROOTS.iter().find_map(|root| {
+        let digits = keyword.strip_prefix(root)?;
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse::<usize>().ok().map(|n| (*root, n))
+    })
+}
+
+/// Check that `order` is a permutation of `0..tfields`.
+fn validate_column_order(order: &[usize], tfields: usize) -> Result<()> {
+    if order.len() != tfields {
+        return Err(Error::InvalidValue);
+    }
+    let mut seen = vec![false; tfields];
+    for &idx in order {
+        if idx >= tfields || seen[idx] {
+            return Err(Error::InvalidValue);
+        }
+        seen[idx] = true;
+    }
+    Ok(())
+}
+
+/// Rename a table column by setting its `TTYPEn` keyword.
+///
+/// `col_index` is 0-based. An existing `TTYPEn` card is updated in place;
+/// otherwise a new one is inserted directly after `TFORMn`. Works for both
+/// binary and ASCII table headers.
+pub fn rename_column(cards: &mut Vec<Card>, col_index: usize, new_name: &str) -> Result<()> {
+    let tfields = card_int_value(cards, "TFIELDS").ok_or(Error::MissingKeyword("TFIELDS"))?;
+    if col_index as i64 >= tfields {
+        return Err(Error::InvalidValue);
+    }
+
+    let n = col_index + 1;
+    let ttype_kw = alloc::format!("TTYPE{}", n);
+    let new_card = make_card(&ttype_kw, Value::String(String::from(new_name)));
+
+    if let Some(card) = cards.iter_mut().find(|c| c.keyword_str() == ttype_kw) {
+        card.value = new_card.value;
+        return Ok(());
+    }
+
+    let tform_kw = alloc::format!("TFORM{}", n);
+    let tform_pos = cards
+        .iter()
+        .position(|c| c.keyword_str() == tform_kw)
+        .ok_or(Error::MissingKeyword("TFORMn"))?;
+    cards.insert(tform_pos + 1, new_card);
+    Ok(())
+}
+
+/// Renumber all per-column keywords (`TFORMn`, `TTYPEn`, `TBCOLn`, `TUNITn`,
+/// `TSCALn`, ...) so that the columns appear in a new order.
+///
+/// `order[i]` is the 0-based index of the existing column that becomes
+/// column `i`. Column cards keep the header slots they occupied and are
+/// regrouped by their new column number.
+///
+/// For ASCII tables this is sufficient, since `TBCOLn` travels with its
+/// column. Binary table rows are laid out in column order, so the data must
+/// also be re-serialized with the output of [`reorder_binary_table`].
+pub fn reorder_columns(cards: &mut [Card], order: &[usize]) -> Result<()> {
+    let tfields = card_int_value(cards, "TFIELDS").ok_or(Error::MissingKeyword("TFIELDS"))?;
+    let tfields = usize::try_from(tfields).map_err(|_| Error::InvalidValue)?;
+    validate_column_order(order, tfields)?;
+
+    // Maps an old 1-based column number to its new 1-based number.
+    let mut new_number = vec![0usize; tfields + 1];
+    for (new_idx, &old_idx) in order.iter().enumerate() {
+        new_number[old_idx + 1] = new_idx + 1;
+    }
+
+    let mut slots = Vec::new();
+    let mut renamed = Vec::new();
+    for (pos, card) in cards.iter().enumerate() {
+        let keyword = card.keyword_str();
+        if let Some((root, n)) = split_column_keyword(keyword) {
+            if n >= 1 && n <= tfields {
+                let mut card = card.clone();
+                let new_n = new_number[n];
+                card.keyword = make_keyword(&alloc::format!("{}{}", root, new_n));
+                slots.push(pos);
+                renamed.push((new_n, card));
+            }
+        }
+    }
+
+    renamed.sort_by_key(|(n, _)| *n);
+    for (pos, (_, card)) in slots.into_iter().zip(renamed) {
+        cards[pos] = card;
+    }
+    Ok(())
+}
+
+/// Reorder binary table column descriptors and their data together.
+///
+/// `order[i]` is the 0-based index of the existing column that becomes
+/// column `i`. The result can be passed straight to
+/// [`serialize_binary_table`] or [`serialize_binary_table_hdu`].
+pub fn reorder_binary_table(
+    columns: &[BinaryColumnDescriptor],
+    col_data: &[BinaryColumnData],
+    order: &[usize],
+) -> Result<(Vec<BinaryColumnDescriptor>, Vec<BinaryColumnData>)> {
+    if columns.len() != col_data.len() {
+        return Err(Error::InvalidValue);
+    }
+    validate_column_order(order, columns.len())?;
+
+    let new_columns = order.iter().map(|&i| columns[i].clone()).collect();
+    let new_data = order.iter().map(|&i| col_data[i].clone()).collect();
+    Ok((new_columns, new_data))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1182,6 +1955,53 @@ mod tests {
         assert_eq!(col_type, BinaryColumnType::Int);
     }
 
+    #[test]
+    fn parse_tform_signed_repeat() {
+        assert_eq!(
+            parse_tform_binary("+3J").unwrap(),
+            (3, BinaryColumnType::Int)
+        );
+        assert!(parse_tform_binary("-3J").is_err());
+        assert!(parse_tform_binary("+J").is_err());
+        assert_eq!(
+            parse_tform_binary("+2PE(10)").unwrap(),
+            (2, BinaryColumnType::VarArrayP('E'))
+        );
+    }
+
+    #[test]
+    fn parse_tform_trailing_junk() {
+        assert_eq!(
+            parse_tform_binary_lenient("1J ").unwrap(),
+            (1, BinaryColumnType::Int)
+        );
+        assert!(parse_tform_binary("1J.").is_err());
+        assert_eq!(
+            parse_tform_binary_lenient("1J.").unwrap(),
+            (1, BinaryColumnType::Int)
+        );
+        assert!(parse_tform_binary_lenient("1J..").is_err());
+        assert!(parse_tform_binary_lenient("-3J").is_err());
+        assert!(parse_tform_binary_lenient(".").is_err());
+    }
+
+    #[test]
+    fn parse_tform_substring_array() {
+        let (repeat, col_type) = parse_tform_binary("60A:SSTR20").unwrap();
+        assert_eq!(repeat, 60);
+        assert_eq!(col_type, BinaryColumnType::AsciiArray(20));
+        assert_eq!(compute_byte_width(repeat, &col_type), 60);
+        assert_eq!(tform_string(repeat, &col_type, None), "60A:SSTR20");
+    }
+
+    #[test]
+    fn parse_tform_substring_array_invalid() {
+        assert!(parse_tform_binary("60A:SSTR0").is_err());
+        assert!(parse_tform_binary("60A:SSTR").is_err());
+        assert!(parse_tform_binary("60A:FOO20").is_err());
+        assert!(parse_tform_binary("3J:SSTR1").is_err());
+    }
+
     // --- binary_type_byte_size ---
 
     #[test]
@@ -1318,6 +2138,20 @@ mod tests {
         assert!(columns[1].name.is_none());
     }
 
+    #[test]
+    fn column_display_names_resolve_missing_and_duplicates() {
+        let cards = make_bintable_header(
+            16,
+            1,
+            4,
+            &["1J", "1J", "1E", "1E"],
+            &[Some("ID"), None, Some("FLUX"), Some("FLUX")],
+        );
+        let columns = parse_binary_table_columns(&cards, 4).unwrap();
+        let names: Vec<String> = (0..4).map(|i| column_display_name(&columns, i)).collect();
+        assert_eq!(names, vec!["ID", "col2", "FLUX_1", "FLUX_2"]);
+    }
+
     // --- Read/write Int column ---
 
     #[test]
@@ -1465,6 +2299,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn read_logical_column_digit_bytes() {
+        let header = make_bintable_header(1, 4, 1, &["1L"], &[None]);
+        let raw_data = vec![b'1', b'0', b'1', b'1'];
+
+        let fits_data = build_bintable_hdu(&header, &raw_data);
+        let (full_fits, hdu) = parse_test_hdu(&fits_data);
+
+        let col = read_binary_column(&full_fits, &hdu, 0).unwrap();
+        assert_eq!(
+            col,
+            BinaryColumnData::Logical(vec![true, false, true, true])
+        );
+    }
+
+    #[test]
+    fn read_logical_column_undefined_is_false() {
+        let header = make_bintable_header(3, 1, 1, &["3L"], &[None]);
+        let raw_data = vec![b'T', 0x00, b'F'];
+
+        let fits_data = build_bintable_hdu(&header, &raw_data);
+        let (full_fits, hdu) = parse_test_hdu(&fits_data);
+
+        let col = read_binary_column(&full_fits, &hdu, 0).unwrap();
+        assert_eq!(col, BinaryColumnData::Logical(vec![true, false, false]));
+    }
+
     // --- Read/write Byte column ---
 
     #[test]
@@ -1512,6 +2373,46 @@ mod tests {
         }
     }
 
+    // --- Read/write substring array column ---
+
+    #[test]
+    fn read_substring_array_column() {
+        let naxis1 = 60;
+        let naxis2 = 2;
+        let header = make_bintable_header(naxis1, naxis2, 1, &["60A:SSTR20"], &[Some("NAMES")]);
+
+        let mut raw_data = vec![b' '; naxis1 * naxis2];
+        for (i, name) in ["alpha", "beta", "gamma", "delta", "", "zeta"]
+            .iter()
+            .enumerate()
+        {
+            raw_data[i * 20..i * 20 + name.len()].copy_from_slice(name.as_bytes());
+        }
+
+        let fits_data = build_bintable_hdu(&header, &raw_data);
+        let (full_fits, hdu) = parse_test_hdu(&fits_data);
+
+        let col = read_binary_column(&full_fits, &hdu, 0).unwrap();
+        let expected = BinaryColumnData::AsciiArray(vec![
+            vec![
+                String::from("alpha"),
+                String::from("beta"),
+                String::from("gamma"),
+            ],
+            vec![String::from("delta"), String::new(), String::from("zeta")],
+        ]);
+        assert_eq!(col, expected);
+
+        let cell =
+            serialize_binary_column_value(&BinaryColumnType::AsciiArray(20), 60, &expected, 0)
+                .unwrap();
+        assert_eq!(cell, raw_data[..60].to_vec());
+        assert!(matches!(
+            serialize_binary_column_value(&BinaryColumnType::AsciiArray(20), 60, &expected, 2),
+            Err(Error::InvalidValue)
+        ));
+    }
+
     // --- Read/write Bit column ---
 
     #[test]
@@ -1536,6 +2437,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn read_bitset_column_128x() {
+        let header = make_bintable_header(16, 2, 1, &["128X"], &[None]);
+        let mut raw_data = vec![0u8; 32];
+        raw_data[0] = 0x80; // bit 0
+        raw_data[15] = 0x01; // bit 127
+        raw_data[16 + 8] = 0x24; // bits 66 and 69
+
+        let fits_data = build_bintable_hdu(&header, &raw_data);
+        let (full_fits, hdu) = parse_test_hdu(&fits_data);
+
+        let rows = read_binary_column_bitset(&full_fits, &hdu, 0).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].len(), 128);
+        assert!(rows[0].get(0));
+        assert!(!rows[0].get(1));
+        assert!(rows[0].get(127));
+        assert!(!rows[0].get(128));
+        assert_eq!(rows[0].count_ones(), 2);
+
+        assert!(rows[1].get(66));
+        assert!(rows[1].get(69));
+        assert!(!rows[1].get(67));
+        assert_eq!(rows[1].count_ones(), 2);
+    }
+
+    #[test]
+    fn bit_row_count_ignores_padding() {
+        let row = BitRow::new(vec![0xFF, 0xFF], 10).unwrap();
+        assert_eq!(row.count_ones(), 10);
+        assert!(row.get(9));
+        assert!(!row.get(10));
+        assert!(matches!(
+            BitRow::new(vec![0xFF], 10),
+            Err(Error::InvalidValue)
+        ));
+        assert!(BitRow::new(vec![], 0).unwrap().is_empty());
+    }
+
     // --- Read/write ComplexFloat column ---
 
     #[test]
@@ -1629,6 +2569,213 @@ mod tests {
         }
     }
 
+    #[test]
+    fn read_raw_cells_of_byte_column() {
+        let naxis1 = 10;
+        let naxis2 = 3;
+        let header = make_bintable_header(
+            naxis1,
+            naxis2,
+            2,
+            &["1I", "8B"],
+            &[Some("ID"), Some("PACKED")],
+        );
+
+        let mut raw_data = vec![0u8; naxis1 * naxis2];
+        for row in 0..naxis2 {
+            for k in 0..8 {
+                raw_data[row * naxis1 + 2 + k] = (row * 10 + k) as u8;
+            }
+        }
+
+        let fits_data = build_bintable_hdu(&header, &raw_data);
+        let (full_fits, hdu) = parse_test_hdu(&fits_data);
+
+        let cells = read_binary_column_raw(&full_fits, &hdu, 1).unwrap();
+        assert_eq!(cells.len(), 3);
+        assert!(cells.iter().all(|c| c.len() == 8));
+        assert_eq!(cells[0], &[0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(cells[2], &[20, 21, 22, 23, 24, 25, 26, 27]);
+        assert_eq!(
+            read_binary_column_raw(&full_fits, &hdu, 0).unwrap()[1],
+            &[0, 0]
+        );
+        assert!(read_binary_column_raw(&full_fits, &hdu, 2).is_err());
+    }
+
+    #[test]
+    fn binary_columns_named_pairs_names_with_data() {
+        let naxis1 = 6;
+        let naxis2 = 2;
+        let header =
+            make_bintable_header(naxis1, naxis2, 2, &["1J", "1I"], &[Some("COUNTS"), None]);
+
+        let mut raw_data = vec![0u8; naxis1 * naxis2];
+        for row in 0..naxis2 {
+            write_i32_be(&mut raw_data[row * naxis1..], 100 + row as i32);
+            write_i16_be(&mut raw_data[row * naxis1 + 4..], -(row as i16));
+        }
+
+        let fits_data = build_bintable_hdu(&header, &raw_data);
+        let (full_fits, hdu) = parse_test_hdu(&fits_data);
+
+        assert_eq!(
+            binary_columns_named(&full_fits, &hdu).unwrap(),
+            vec![
+                (
+                    String::from("COUNTS"),
+                    BinaryColumnData::Int(vec![100, 101])
+                ),
+                (String::from("col2"), BinaryColumnData::Short(vec![0, -1])),
+            ]
+        );
+    }
+
+    #[test]
+    fn read_rows_with_trailing_filler() {
+        // 1J + 1E = 8 bytes of columns, but each row is 12 bytes wide.
+        let naxis1 = 12;
+        let naxis2 = 3;
+        let header =
+            make_bintable_header(naxis1, naxis2, 2, &["1J", "1E"], &[Some("ID"), Some("VAL")]);
+
+        let mut raw_data = vec![0xAAu8; naxis1 * naxis2];
+        for row in 0..naxis2 {
+            write_i32_be(&mut raw_data[row * naxis1..], row as i32 + 1);
+            write_f32_be(&mut raw_data[row * naxis1 + 4..], row as f32 * 0.5);
+        }
+
+        let fits_data = build_bintable_hdu(&header, &raw_data);
+        let (full_fits, hdu) = parse_test_hdu(&fits_data);
+
+        assert_eq!(
+            read_binary_column(&full_fits, &hdu, 0).unwrap(),
+            BinaryColumnData::Int(vec![1, 2, 3])
+        );
+        assert_eq!(
+            read_binary_column(&full_fits, &hdu, 1).unwrap(),
+            BinaryColumnData::Float(vec![0.0, 0.5, 1.0])
+        );
+        assert_eq!(
+            read_binary_row(&full_fits, &hdu, 2).unwrap(),
+            vec![
+                BinaryColumnData::Int(vec![3]),
+                BinaryColumnData::Float(vec![1.0])
+            ]
+        );
+        assert_eq!(
+            read_binary_columns(&full_fits, &hdu, &[1]).unwrap(),
+            vec![BinaryColumnData::Float(vec![0.0, 0.5, 1.0])]
+        );
+    }
+
+    #[test]
+    fn read_selected_columns() {
+        // 3 columns: 1J + 1E + 1D = 16 bytes per row, spanning two chunks.
+        let naxis1 = 16;
+        let naxis2 = COLUMN_CHUNK_ROWS + 44;
+        let header = make_bintable_header(
+            naxis1,
+            naxis2,
+            3,
+            &["1J", "1E", "1D"],
+            &[Some("ID"), Some("MAG"), Some("FLUX")],
+        );
+
+        let mut raw_data = vec![0u8; naxis1 * naxis2];
+        for row in 0..naxis2 {
+            let base = row * naxis1;
+            write_i32_be(&mut raw_data[base..], row as i32);
+            write_f32_be(&mut raw_data[base + 4..], -1.0);
+            write_f64_be(&mut raw_data[base + 8..], row as f64 * 0.5);
+        }
+
+        let fits_data = build_bintable_hdu(&header, &raw_data);
+        let (full_fits, hdu) = parse_test_hdu(&fits_data);
+
+        let selected = read_binary_columns(&full_fits, &hdu, &[0, 2]).unwrap();
+        assert_eq!(selected.len(), 2);
+        assert_eq!(
+            selected[0],
+            read_binary_column(&full_fits, &hdu, 0).unwrap()
+        );
+        assert_eq!(
+            selected[1],
+            read_binary_column(&full_fits, &hdu, 2).unwrap()
+        );
+        match &selected[1] {
+            BinaryColumnData::Double(vals) => {
+                assert_eq!(vals.len(), naxis2);
+                assert_eq!(vals[naxis2 - 1], (naxis2 - 1) as f64 * 0.5);
+            }
+            other => panic!("Expected Double, got {:?}", other),
+        }
+
+        assert!(read_binary_columns(&full_fits, &hdu, &[3]).is_err());
+    }
+
+    #[test]
+    fn read_column_cells_rejects_truncated_data() {
+        let columns = parse_binary_table_columns(
+            &make_bintable_header(12, 3, 2, &["1J", "1D"], &[None, None]),
+            2,
+        )
+        .unwrap();
+        let data = vec![0u8; 12 * 3];
+
+        assert!(read_column_cells(&data, 0, 12, 3, &columns[1], 4, false).is_ok());
+        for len in [35, 24, 1] {
+            assert!(matches!(
+                read_column_cells(&data[..len], 0, 12, 3, &columns[1], 4, false),
+                Err(Error::UnexpectedEof)
+            ));
+        }
+        assert!(matches!(
+            read_column_cells(&data, usize::MAX, 12, 3, &columns[0], 0, false),
+            Err(Error::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn read_little_endian_column_with_byteorder() {
+        use crate::hdu::{parse_fits_with, ParseOptions};
+
+        let mut header = make_bintable_header(8, 2, 2, &["1J", "1E"], &[None, None]);
+        let byteorder = Value::String(String::from("LITTLE"));
+        header.push(crate::header::hierarch_card("BYTEORDER", &byteorder).unwrap());
+        let mut raw_data = Vec::new();
+        for (j, e) in [(-70_000i32, 1.5f32), (123_456, -2.25)] {
+            raw_data.extend_from_slice(&j.to_le_bytes());
+            raw_data.extend_from_slice(&e.to_le_bytes());
+        }
+        let (full_fits, standard) = parse_test_hdu(&build_bintable_hdu(&header, &raw_data));
+        assert!(!standard.little_endian());
+        assert_eq!(
+            read_binary_column(&full_fits, &standard, 0).unwrap(),
+            BinaryColumnData::Int(vec![(-70_000i32).swap_bytes(), 123_456i32.swap_bytes()])
+        );
+
+        let options = ParseOptions {
+            honor_byteorder: true,
+            ..ParseOptions::default()
+        };
+        let (fits, _) = parse_fits_with(&full_fits, &options).unwrap();
+        let hdu = fits.get(1).unwrap();
+        assert!(hdu.little_endian());
+        assert_eq!(
+            read_binary_column(&full_fits, hdu, 0).unwrap(),
+            BinaryColumnData::Int(vec![-70_000, 123_456])
+        );
+        assert_eq!(
+            read_binary_column(&full_fits, hdu, 1).unwrap(),
+            BinaryColumnData::Float(vec![1.5, -2.25])
+        );
+        assert_eq!(
+            read_binary_column_range(&full_fits, hdu, 0, 1, 1).unwrap(),
+            BinaryColumnData::Int(vec![123_456])
+        );
+    }
+
     // --- Repeat count handling ---
 
     #[test]
@@ -2248,6 +3395,58 @@ mod tests {
         assert_eq!(col, BinaryColumnData::ComplexDouble(vec![(1.5, -2.5)]));
     }
 
+    #[test]
+    fn roundtrip_repeated_complex_columns() {
+        let columns = vec![
+            BinaryColumnDescriptor {
+                name: Some(String::from("VIS")),
+                repeat: 3,
+                col_type: BinaryColumnType::ComplexFloat,
+                byte_width: 24,
+                tdim: None,
+            },
+            BinaryColumnDescriptor {
+                name: Some(String::from("GAIN")),
+                repeat: 2,
+                col_type: BinaryColumnType::ComplexDouble,
+                byte_width: 32,
+                tdim: None,
+            },
+        ];
+        let vis: Vec<(f32, f32)> = (0..6).map(|i| (i as f32, -(i as f32) - 0.5)).collect();
+        let gain: Vec<(f64, f64)> = (0..4).map(|i| (i as f64 * 0.25, 10.0 + i as f64)).collect();
+        let original = vec![
+            BinaryColumnData::ComplexFloat(vis.clone()),
+            BinaryColumnData::ComplexDouble(gain.clone()),
+        ];
+
+        let cards = build_binary_table_cards(&columns, 2, 0).unwrap();
+        assert_eq!(
+            keyword_value(&cards, "TFORM1"),
+            Some(&Value::String(String::from("3C")))
+        );
+        assert_eq!(
+            keyword_value(&cards, "TFORM2"),
+            Some(&Value::String(String::from("2M")))
+        );
+
+        let fits = build_fits_with_binary_table(&columns, &original, 2).unwrap();
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = parsed.get(1).unwrap();
+        assert_eq!(
+            read_binary_column(&fits, hdu, 0).unwrap(),
+            BinaryColumnData::ComplexFloat(vis)
+        );
+        assert_eq!(
+            read_binary_column(&fits, hdu, 1).unwrap(),
+            BinaryColumnData::ComplexDouble(gain)
+        );
+        assert_eq!(
+            read_binary_row(&fits, hdu, 1).unwrap()[0],
+            BinaryColumnData::ComplexFloat(vec![(3.0, -3.5), (4.0, -4.5), (5.0, -5.5)])
+        );
+    }
+
     #[test]
     fn col_index_out_of_bounds() {
         let naxis1 = 4;
@@ -2473,6 +3672,43 @@ mod tests {
         let physical = read_binary_column_physical(&full_fits, &hdu, 0).unwrap();
         assert_eq!(physical, vec![100.0, 200.0]);
     }
+
+    #[test]
+    fn read_physical_complex_column_scales_both_parts() {
+        let naxis1 = 16;
+        let naxis2 = 2;
+        let mut header = make_bintable_header(naxis1, naxis2, 1, &["1M"], &[Some("VIS")]);
+        header.push(card_val("TSCAL1", Value::Float(2.0)));
+        header.push(card_val("TZERO1", Value::Float(1.0)));
+
+        let mut raw_data = vec![0u8; naxis1 * naxis2];
+        write_f64_be(&mut raw_data[0..], 1.0);
+        write_f64_be(&mut raw_data[8..], -2.0);
+        write_f64_be(&mut raw_data[16..], 3.0);
+        write_f64_be(&mut raw_data[24..], 0.5);
+
+        let fits_data = build_bintable_hdu(&header, &raw_data);
+        let (full_fits, hdu) = parse_test_hdu(&fits_data);
+
+        let physical = read_binary_column_physical(&full_fits, &hdu, 0).unwrap();
+        assert_eq!(physical, vec![3.0, -3.0, 7.0, 2.0]);
+    }
+
+    #[test]
+    fn read_physical_scaled_logical_column_rejected() {
+        let naxis1 = 1;
+        let naxis2 = 2;
+        let mut header = make_bintable_header(naxis1, naxis2, 1, &["1L"], &[Some("FLAG")]);
+        header.push(card_val("TSCAL1", Value::Float(2.0)));
+
+        let raw_data = vec![b'T', b'F'];
+        let fits_data = build_bintable_hdu(&header, &raw_data);
+        let (full_fits, hdu) = parse_test_hdu(&fits_data);
+
+        let result = read_binary_column_physical(&full_fits, &hdu, 0);
+        assert!(matches!(result, Err(Error::InvalidScaling(0))));
+    }
+
     // --- TFORM P/Q parsing ---
 
     #[test]
@@ -2517,12 +3753,105 @@ mod tests {
 
     #[test]
     fn tform_string_vararray_p() {
-        assert_eq!(tform_string(1, &BinaryColumnType::VarArrayP('J')), "1PJ");
+        assert_eq!(
+            tform_string(1, &BinaryColumnType::VarArrayP('J'), None),
+            "1PJ"
+        );
     }
 
     #[test]
     fn tform_string_vararray_q() {
-        assert_eq!(tform_string(1, &BinaryColumnType::VarArrayQ('E')), "1QE");
+        assert_eq!(
+            tform_string(1, &BinaryColumnType::VarArrayQ('E'), None),
+            "1QE"
+        );
+    }
+
+    #[test]
+    fn tform_string_vararray_with_max() {
+        let p = BinaryColumnType::VarArrayP('E');
+        let q = BinaryColumnType::VarArrayQ('D');
+        assert_eq!(tform_string(1, &p, Some(1000)), "1PE(1000)");
+        assert_eq!(tform_string(1, &q, Some(0)), "1QD(0)");
+        assert_eq!(tform_string(1, &q, None), "1QD");
+    }
+
+    #[test]
+    fn serialize_with_len_reports_unpadded_size() {
+        let columns = vec![
+            BinaryColumnDescriptor {
+                name: Some(String::from("ID")),
+                repeat: 1,
+                col_type: BinaryColumnType::Int,
+                byte_width: 4,
+                tdim: None,
+            },
+            BinaryColumnDescriptor {
+                name: Some(String::from("FLUX")),
+                repeat: 2,
+                col_type: BinaryColumnType::Double,
+                byte_width: 16,
+                tdim: None,
+            },
+        ];
+        let data = vec![
+            BinaryColumnData::Int(vec![1, 2, 3]),
+            BinaryColumnData::Double(vec![0.5; 6]),
+        ];
+
+        let out = serialize_binary_table_with_len(&columns, &data, 3).unwrap();
+        assert_eq!(out.raw_len, 20 * 3);
+        assert_eq!(out.padded_len(), crate::block::BLOCK_SIZE);
+        assert_eq!(
+            out.bytes,
+            serialize_binary_table(&columns, &data, 3).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_vla_column_records_max_length() {
+        let columns = vec![
+            BinaryColumnDescriptor {
+                name: Some(String::from("ID")),
+                repeat: 1,
+                col_type: BinaryColumnType::Int,
+                byte_width: 4,
+                tdim: None,
+            },
+            BinaryColumnDescriptor {
+                name: Some(String::from("SPEC")),
+                repeat: 1,
+                col_type: BinaryColumnType::VarArrayP('E'),
+                byte_width: 8,
+                tdim: None,
+            },
+        ];
+        let rows = vec![vec![1.0f32, 2.0], vec![], vec![3.0, 4.0, 5.0, 6.0, 7.0]];
+        let data = vec![
+            BinaryColumnData::Int(vec![10, 20, 30]),
+            BinaryColumnData::VarFloat(rows.clone()),
+        ];
+        let fits = build_fits_with_binary_table(&columns, &data, 3).unwrap();
+
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = parsed.get(1).unwrap();
+        match keyword_value(&hdu.cards, "TFORM2") {
+            Some(Value::String(s)) => assert_eq!(s.trim(), "1PE(5)"),
+            other => panic!("Expected TFORM2 string, got {:?}", other),
+        }
+        assert_eq!(
+            keyword_value(&hdu.cards, "PCOUNT"),
+            Some(&Value::Integer(28))
+        );
+
+        match read_binary_column_vla(&fits, hdu, 1).unwrap() {
+            BinaryColumnData::VarFloat(read) => assert_eq!(read, rows),
+            other => panic!("Expected VarFloat, got {:?}", other),
+        }
+        match read_binary_column(&fits, hdu, 0).unwrap() {
+            BinaryColumnData::Int(ids) => assert_eq!(ids, vec![10, 20, 30]),
+            other => panic!("Expected Int, got {:?}", other),
+        }
     }
 
     #[test]
@@ -2618,6 +3947,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn read_vla_with_explicit_theap() {
+        // A 4-byte gap separates the two-row table from the heap.
+        let heap = vec![0xEE, 0xEE, 0xEE, 0xEE, 10u8, 20, 30];
+        let fits = build_vla_fits(2, "1PB", &[(2, 0), (1, 2)], &heap);
+
+        let mut parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = &mut parsed.hdus[1];
+        hdu.cards.push(card_val("THEAP", Value::Integer(20)));
+        assert_eq!(
+            read_binary_column_vla(&fits, hdu, 0).unwrap(),
+            BinaryColumnData::VarByte(vec![vec![10, 20], vec![30]])
+        );
+
+        hdu.cards.last_mut().unwrap().value = Some(Value::Integer(12));
+        assert!(matches!(
+            read_binary_column_vla(&fits, hdu, 0),
+            Err(Error::InvalidHeader(_))
+        ));
+        hdu.cards.last_mut().unwrap().value = Some(Value::Integer(24));
+        assert!(matches!(
+            read_binary_column_vla(&fits, hdu, 0),
+            Err(Error::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn read_vla_corrupt_descriptor_errors() {
+        let heap = vec![0u8; 16];
+        for desc in [(-1, 0), (1, -1), (3, 0)] {
+            let fits = build_vla_fits(1, "1PD", &[desc], &heap);
+            let parsed = crate::hdu::parse_fits(&fits).unwrap();
+            let hdu = parsed.get(1).unwrap();
+            assert!(
+                matches!(
+                    read_binary_column_vla(&fits[..hdu.data_start + 24], hdu, 0),
+                    Err(Error::UnexpectedEof)
+                ),
+                "descriptor {desc:?}"
+            );
+        }
+    }
+
     #[test]
     fn read_vla_int_column() {
         // 2 rows: row 0 has 2 ints, row 1 has 1 int
@@ -2832,4 +4204,338 @@ mod tests {
         let read_back = read_binary_column(&full_fits, &hdu, 0).unwrap();
         assert_eq!(read_back, new_data);
     }
+
+    // --- Zero-column tables ---
+
+    #[test]
+    fn serialize_zero_column_table_is_empty() {
+        let data = serialize_binary_table(&[], &[], 5).unwrap();
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn build_cards_zero_columns() {
+        let cards = build_binary_table_cards(&[], 5, 0).unwrap();
+        assert_eq!(cards.len(), 8);
+        assert_eq!(card_int_value(&cards, "NAXIS1"), Some(0));
+        assert_eq!(card_int_value(&cards, "NAXIS2"), Some(5));
+        assert_eq!(card_int_value(&cards, "TFIELDS"), Some(0));
+    }
+
+    #[test]
+    fn zero_column_table_roundtrip() {
+        let hdu_bytes = serialize_binary_table_hdu(&[], &[], 5).unwrap();
+        assert_eq!(hdu_bytes.len(), BLOCK_SIZE);
+
+        let (full_fits, hdu) = parse_test_hdu(&hdu_bytes);
+        match &hdu.info {
+            HduInfo::BinaryTable {
+                naxis1,
+                naxis2,
+                tfields,
+                ..
+            } => {
+                assert_eq!(*naxis1, 0);
+                assert_eq!(*naxis2, 5);
+                assert_eq!(*tfields, 0);
+            }
+            other => panic!("Expected BinaryTable, got {:?}", other),
+        }
+        assert_eq!(hdu.data_len, 0);
+
+        let columns = parse_binary_table_columns(&hdu.cards, 0).unwrap();
+        assert!(columns.is_empty());
+        assert!(read_binary_column(&full_fits, &hdu, 0).is_err());
+        assert_eq!(read_binary_row(&full_fits, &hdu, 4).unwrap(), vec![]);
+        assert!(read_binary_row(&full_fits, &hdu, 5).is_err());
+    }
+
+    #[test]
+    fn zero_column_table_followed_by_another_hdu() {
+        let empty = serialize_binary_table_hdu(&[], &[], 0).unwrap();
+        let columns = vec![BinaryColumnDescriptor {
+            name: Some(String::from("X")),
+            repeat: 1,
+            col_type: BinaryColumnType::Int,
+            byte_width: 4,
+            tdim: None,
+        }];
+        let col_data = vec![BinaryColumnData::Int(vec![7, 8])];
+        let second = serialize_binary_table_hdu(&columns, &col_data, 2).unwrap();
+
+        let mut ext = empty.clone();
+        ext.extend_from_slice(&second);
+        let (full_fits, _) = parse_test_hdu(&ext);
+
+        let parsed = crate::hdu::parse_fits(&full_fits).unwrap();
+        assert_eq!(parsed.len(), 3);
+        let hdu = parsed.get(2).unwrap();
+        assert_eq!(
+            read_binary_column(&full_fits, hdu, 0).unwrap(),
+            BinaryColumnData::Int(vec![7, 8])
+        );
+    }
+
+    // --- Column rename/reorder ---
+
+    #[test]
+    fn rename_column_updates_existing_ttype() {
+        let mut cards = make_bintable_header(12, 1, 2, &["1J", "1D"], &[Some("X"), Some("Y")]);
+        rename_column(&mut cards, 1, "FLUX").unwrap();
+        assert_eq!(
+            card_string_value(&cards, "TTYPE2"),
+            Some(String::from("FLUX"))
+        );
+        assert_eq!(card_string_value(&cards, "TTYPE1"), Some(String::from("X")));
+    }
+
+    #[test]
+    fn rename_column_inserts_missing_ttype_after_tform() {
+        let mut cards = make_bintable_header(12, 1, 2, &["1J", "1D"], &[None, None]);
+        rename_column(&mut cards, 0, "ID").unwrap();
+        let pos = cards
+            .iter()
+            .position(|c| c.keyword_str() == "TTYPE1")
+            .unwrap();
+        assert_eq!(cards[pos - 1].keyword_str(), "TFORM1");
+        assert_eq!(
+            card_string_value(&cards, "TTYPE1"),
+            Some(String::from("ID"))
+        );
+    }
+
+    #[test]
+    fn rename_column_out_of_range() {
+        let mut cards = make_bintable_header(4, 1, 1, &["1J"], &[Some("X")]);
+        assert!(matches!(
+            rename_column(&mut cards, 1, "Y"),
+            Err(Error::InvalidValue)
+        ));
+    }
+
+    #[test]
+    fn reorder_columns_rejects_bad_permutation() {
+        let mut cards = make_bintable_header(12, 1, 2, &["1J", "1D"], &[Some("X"), Some("Y")]);
+        assert!(reorder_columns(&mut cards, &[0]).is_err());
+        assert!(reorder_columns(&mut cards, &[1, 1]).is_err());
+        assert!(reorder_columns(&mut cards, &[0, 2]).is_err());
+    }
+
+    #[test]
+    fn reorder_columns_renumbers_keywords() {
+        let mut cards = make_bintable_header(12, 1, 2, &["1J", "1D"], &[Some("X"), Some("Y")]);
+        cards.push(card_val("TUNIT2", Value::String(String::from("Jy"))));
+        reorder_columns(&mut cards, &[1, 0]).unwrap();
+
+        assert_eq!(
+            card_string_value(&cards, "TFORM1"),
+            Some(String::from("1D"))
+        );
+        assert_eq!(card_string_value(&cards, "TTYPE1"), Some(String::from("Y")));
+        assert_eq!(
+            card_string_value(&cards, "TUNIT1"),
+            Some(String::from("Jy"))
+        );
+        assert_eq!(
+            card_string_value(&cards, "TFORM2"),
+            Some(String::from("1J"))
+        );
+        assert_eq!(card_string_value(&cards, "TTYPE2"), Some(String::from("X")));
+        assert_eq!(card_string_value(&cards, "TUNIT2"), None);
+    }
+
+    #[test]
+    fn reorder_binary_table_swaps_columns_and_data() {
+        let columns = vec![
+            BinaryColumnDescriptor {
+                name: Some(String::from("ID")),
+                repeat: 1,
+                col_type: BinaryColumnType::Int,
+                byte_width: 4,
+                tdim: None,
+            },
+            BinaryColumnDescriptor {
+                name: Some(String::from("FLUX")),
+                repeat: 1,
+                col_type: BinaryColumnType::Double,
+                byte_width: 8,
+                tdim: None,
+            },
+        ];
+        let col_data = vec![
+            BinaryColumnData::Int(vec![1, 2, 3]),
+            BinaryColumnData::Double(vec![0.5, 1.5, 2.5]),
+        ];
+
+        let (new_columns, new_data) = reorder_binary_table(&columns, &col_data, &[1, 0]).unwrap();
+        assert_eq!(new_columns[0].name.as_deref(), Some("FLUX"));
+        assert_eq!(new_columns[1].name.as_deref(), Some("ID"));
+
+        let hdu_bytes = serialize_binary_table_hdu(&new_columns, &new_data, 3).unwrap();
+        let (full_fits, hdu) = parse_test_hdu(&hdu_bytes);
+
+        assert_eq!(
+            card_string_value(&hdu.cards, "TTYPE1"),
+            Some(String::from("FLUX"))
+        );
+        assert_eq!(
+            read_binary_column(&full_fits, &hdu, 0).unwrap(),
+            BinaryColumnData::Double(vec![0.5, 1.5, 2.5])
+        );
+        assert_eq!(
+            read_binary_column(&full_fits, &hdu, 1).unwrap(),
+            BinaryColumnData::Int(vec![1, 2, 3])
+        );
+    }
+
+    // --- build_fits_with_binary_table ---
+
+    #[test]
+    fn build_fits_with_binary_table_parses() {
+        let columns = vec![
+            BinaryColumnDescriptor {
+                name: Some(String::from("ID")),
+                repeat: 1,
+                col_type: BinaryColumnType::Int,
+                byte_width: 4,
+                tdim: None,
+            },
+            BinaryColumnDescriptor {
+                name: Some(String::from("NAME")),
+                repeat: 6,
+                col_type: BinaryColumnType::Ascii,
+                byte_width: 6,
+                tdim: None,
+            },
+        ];
+        let col_data = vec![
+            BinaryColumnData::Int(vec![10, 20]),
+            BinaryColumnData::Ascii(vec![String::from("alpha"), String::from("beta")]),
+        ];
+
+        let fits = build_fits_with_binary_table(&columns, &col_data, 2).unwrap();
+        assert_eq!(fits.len() % BLOCK_SIZE, 0);
+
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        assert_eq!(parsed.len(), 2);
+
+        let primary = parsed.primary();
+        assert_eq!(primary.data_len, 0);
+        assert!(primary
+            .cards
+            .iter()
+            .any(|c| c.keyword_str() == "EXTEND" && c.value == Some(Value::Logical(true))));
+
+        let hdu = parsed.get(1).unwrap();
+        assert_eq!(
+            read_binary_column(&fits, hdu, 0).unwrap(),
+            BinaryColumnData::Int(vec![10, 20])
+        );
+        assert_eq!(
+            read_binary_column(&fits, hdu, 1).unwrap(),
+            BinaryColumnData::Ascii(vec![String::from("alpha"), String::from("beta")])
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Source {
+        id: i32,
+        name: String,
+        flux: f32,
+    }
+
+    impl FromFitsRow for Source {
+        fn from_fits_row(row: &BinaryRow) -> Result<Self> {
+            let id = match row.column("ID")? {
+                BinaryColumnData::Int(v) => v[0],
+                _ => return Err(Error::InvalidValue),
+            };
+            let name = match row.column("NAME")? {
+                BinaryColumnData::Ascii(v) => v[0].clone(),
+                _ => return Err(Error::InvalidValue),
+            };
+            let flux = match row.column("FLUX")? {
+                BinaryColumnData::Float(v) => v[0],
+                _ => return Err(Error::InvalidValue),
+            };
+            Ok(Source { id, name, flux })
+        }
+    }
+
+    #[test]
+    fn read_row_into_struct() {
+        let column = |name: &str, repeat, col_type, byte_width| BinaryColumnDescriptor {
+            name: Some(String::from(name)),
+            repeat,
+            col_type,
+            byte_width,
+            tdim: None,
+        };
+        let columns = vec![
+            column("FLUX", 1, BinaryColumnType::Float, 4),
+            column("ID", 1, BinaryColumnType::Int, 4),
+            column("NAME", 6, BinaryColumnType::Ascii, 6),
+        ];
+        let col_data = vec![
+            BinaryColumnData::Float(vec![1.5, 2.25]),
+            BinaryColumnData::Int(vec![10, 20]),
+            BinaryColumnData::Ascii(vec![String::from("alpha"), String::from("beta")]),
+        ];
+        let fits = build_fits_with_binary_table(&columns, &col_data, 2).unwrap();
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = parsed.get(1).unwrap();
+
+        let source: Source = read_binary_row_as(&fits, hdu, 1).unwrap();
+        assert_eq!(
+            source,
+            Source {
+                id: 20,
+                name: String::from("beta"),
+                flux: 2.25,
+            }
+        );
+        assert!(read_binary_row_as::<Source>(&fits, hdu, 2).is_err());
+
+        struct Missing;
+        impl FromFitsRow for Missing {
+            fn from_fits_row(row: &BinaryRow) -> Result<Self> {
+                row.column("MAG").map(|_| Missing)
+            }
+        }
+        assert!(matches!(
+            read_binary_row_as::<Missing>(&fits, hdu, 0),
+            Err(Error::MissingKeyword("TTYPEn"))
+        ));
+    }
+
+    #[test]
+    fn tdim_string_array_with_nul_terminated_slots() {
+        let columns = vec![BinaryColumnDescriptor {
+            name: Some(String::from("IDS")),
+            repeat: 40,
+            col_type: BinaryColumnType::Ascii,
+            byte_width: 40,
+            tdim: Some(vec![20, 2]),
+        }];
+        let col_data = vec![BinaryColumnData::Ascii(vec![String::new()])];
+        let mut fits = build_fits_with_binary_table(&columns, &col_data, 1).unwrap();
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = parsed.get(1).unwrap();
+
+        let cell = &mut fits[hdu.data_start..hdu.data_start + 40];
+        cell.fill(b'?');
+        cell[..4].copy_from_slice(b"M31\0");
+        cell[20..28].copy_from_slice(b"NGC 224\0");
+
+        let cols = parse_binary_table_columns(&hdu.cards, 1).unwrap();
+        assert_eq!(cols[0].col_type, BinaryColumnType::Ascii);
+        assert!(matches!(
+            read_binary_column(&fits, hdu, 0).unwrap(),
+            BinaryColumnData::Ascii(_)
+        ));
+        assert_eq!(
+            read_binary_column_strings(&fits, hdu, 0).unwrap(),
+            vec![vec![String::from("M31"), String::from("NGC 224")]]
+        );
+    }
 }
@@ -32,6 +32,34 @@ pub const fn padded_byte_len(num_bytes: usize) -> usize {
     blocks_needed(num_bytes) * BLOCK_SIZE
 }
 
+/// Extend `buf` with `fill` bytes up to the next multiple of [`BLOCK_SIZE`].
+///
+/// Pass [`HEADER_PAD_BYTE`] for header units and [`DATA_PAD_BYTE`] for data
+/// units. An empty or already block-aligned buffer is returned unchanged.
+pub fn pad_to_block(mut buf: alloc::vec::Vec<u8>, fill: u8) -> alloc::vec::Vec<u8> {
+    buf.resize(padded_byte_len(buf.len()), fill);
+    buf
+}
+
+/// Block-padded data bytes together with the unpadded data length.
+///
+/// `raw_len` is the value that belongs in the header's data size bookkeeping
+/// (e.g. `NAXIS1 * NAXIS2 + PCOUNT` for a table).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Serialized {
+    /// The data, padded to a whole number of blocks.
+    pub bytes: alloc::vec::Vec<u8>,
+    /// Length of the data before block padding.
+    pub raw_len: usize,
+}
+
+impl Serialized {
+    /// Length of the padded data; always a multiple of [`BLOCK_SIZE`].
+    pub fn padded_len(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
 /// Copies `src` into the beginning of `dest` and fills the remaining bytes of
 /// `dest` with `pad_byte`.
 ///
@@ -125,6 +153,8 @@ pub fn write_block(dest: &mut [u8], block: &[u8; BLOCK_SIZE], block_index: usize
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
 
     // ---- blocks_needed ----
 
@@ -181,6 +211,21 @@ mod tests {
         assert_eq!(padded_byte_len(BLOCK_SIZE + 1), 2 * BLOCK_SIZE);
     }
 
+    #[test]
+    fn pad_to_block_fills_with_given_byte() {
+        let data = pad_to_block(vec![7u8; 12], DATA_PAD_BYTE);
+        assert_eq!(data.len(), BLOCK_SIZE);
+        assert_eq!(&data[..12], &[7u8; 12]);
+        assert!(data[12..].iter().all(|&b| b == 0));
+
+        let header = pad_to_block(vec![b'X'; 10], HEADER_PAD_BYTE);
+        assert_eq!(header.len(), BLOCK_SIZE);
+        assert!(header[10..].iter().all(|&b| b == b' '));
+
+        assert!(pad_to_block(Vec::new(), 0).is_empty());
+        assert_eq!(pad_to_block(vec![1u8; BLOCK_SIZE], 0).len(), BLOCK_SIZE);
+    }
+
     // ---- constants ----
 
     #[test]
@@ -7,9 +7,14 @@ use alloc::string::String;
 use alloc::string::ToString;
 use alloc::vec::Vec;
 
-use crate::block::{padded_byte_len, BLOCK_SIZE};
+use crate::block::{
+    pad_to_block, padded_byte_len, BLOCK_SIZE, CARD_SIZE, DATA_PAD_BYTE, HEADER_PAD_BYTE,
+};
+use crate::error::Result;
 use crate::hdu::Hdu;
-use crate::header::{serialize_header, Card};
+use crate::header::{
+    format_card, format_end_card, header_byte_len, keyword_value, serialize_header, Card,
+};
 use crate::value::Value;
 
 // ---------------------------------------------------------------------------
@@ -176,11 +181,45 @@ pub fn verify_datasum(fits_data: &[u8], hdu: &Hdu) -> bool {
 /// Verify the CHECKSUM keyword for an entire HDU.
 ///
 /// The total ones-complement checksum of a valid HDU should be -0
-/// (i.e. `0x00000000` or `0xFFFFFFFF`).
+/// (i.e. `0x00000000` or `0xFFFFFFFF`). Returns `true` if no CHECKSUM
+/// keyword is present; see [`verify_hdu_checksum`] to tell the cases apart.
 pub fn verify_checksum(fits_data: &[u8], hdu: &Hdu) -> bool {
+    verify_hdu_checksum(fits_data, hdu) != ChecksumStatus::ChecksumMismatch
+}
+
+/// Outcome of [`verify_hdu_checksum`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumStatus {
+    /// The HDU sums to -0, and DATASUM, if present, matches the data.
+    Ok,
+    /// There is no CHECKSUM keyword, so nothing was verified.
+    Missing,
+    /// The header and data together do not sum to -0.
+    ChecksumMismatch,
+    /// The HDU sums to -0 but the DATASUM value does not match the data.
+    DatasumMismatch,
+}
+
+/// Verify an HDU's CHECKSUM, and its DATASUM when present.
+///
+/// CHECKSUM covers the header and data, so it is checked on its own even
+/// when the writer omitted DATASUM; such an HDU reports
+/// [`ChecksumStatus::Ok`]. An HDU with DATASUM but no CHECKSUM reports
+/// [`ChecksumStatus::Missing`]; use [`verify_datasum`] for the data alone.
+pub fn verify_hdu_checksum(fits_data: &[u8], hdu: &Hdu) -> ChecksumStatus {
     if find_string_keyword(&hdu.cards, "CHECKSUM").is_none() {
-        return true;
+        return ChecksumStatus::Missing;
+    }
+    if !hdu_sums_to_negative_zero(fits_data, hdu) {
+        return ChecksumStatus::ChecksumMismatch;
     }
+    if !verify_datasum(fits_data, hdu) {
+        return ChecksumStatus::DatasumMismatch;
+    }
+    ChecksumStatus::Ok
+}
+
+fn hdu_sums_to_negative_zero(fits_data: &[u8], hdu: &Hdu) -> bool {
     let header_end = hdu.data_start;
     let data_padded = padded_byte_len(hdu.data_len);
     let hdu_end = header_end + data_padded;
@@ -233,10 +272,7 @@ pub fn stamp_checksum(cards: &[Card], data_bytes: &[u8]) -> Vec<Card> {
         .collect();
 
     // Compute data checksum.
-    let data_padded_len = padded_byte_len(data_bytes.len());
-    let mut data_padded = Vec::with_capacity(data_padded_len);
-    data_padded.extend_from_slice(data_bytes);
-    data_padded.resize(data_padded_len, 0u8);
+    let data_padded = pad_to_block(data_bytes.to_vec(), DATA_PAD_BYTE);
     let datasum = if data_bytes.is_empty() {
         0u32
     } else {
@@ -281,6 +317,45 @@ pub fn stamp_checksum(cards: &[Card], data_bytes: &[u8]) -> Vec<Card> {
     new_cards
 }
 
+/// Insert a DATASUM card into a serialized HDU without adding CHECKSUM.
+///
+/// `hdu_bytes` holds one HDU: its header blocks followed by its data. Any
+/// existing DATASUM card is replaced and any CHECKSUM card is dropped, since
+/// it would no longer be valid. The remaining header cards are kept byte for
+/// byte, DATASUM is placed just before END, and the header is re-padded. The
+/// data follows unchanged. DATASUM is the decimal value of the unsigned
+/// ones-complement sum over the zero-padded data blocks, as written by
+/// cfitsio.
+pub fn stamp_datasum(hdu_bytes: &[u8]) -> Result<Vec<u8>> {
+    let header_len = header_byte_len(hdu_bytes)?;
+    let (header, data) = hdu_bytes.split_at(header_len);
+
+    let datasum = if data.is_empty() {
+        0u32
+    } else {
+        checksum_blocks(&pad_to_block(data.to_vec(), DATA_PAD_BYTE))
+    };
+    let datasum_card = Card {
+        keyword: make_keyword(b"DATASUM"),
+        value: Some(Value::String(datasum.to_string())),
+        comment: Some(String::from("data unit checksum")),
+    };
+
+    let mut out = Vec::with_capacity(hdu_bytes.len() + BLOCK_SIZE);
+    for card in header.chunks_exact(CARD_SIZE) {
+        match &card[..8] {
+            b"END     " => break,
+            b"DATASUM " | b"CHECKSUM" => continue,
+            _ => out.extend_from_slice(card),
+        }
+    }
+    out.extend_from_slice(&format_card(&datasum_card));
+    out.extend_from_slice(&format_end_card());
+    let mut out = pad_to_block(out, HEADER_PAD_BYTE);
+    out.extend_from_slice(data);
+    Ok(out)
+}
+
 /// Ones-complement addition of two 32-bit values.
 fn ones_complement_add(a: u32, b: u32) -> u32 {
     let mut hi = (a >> 16) + (b >> 16);
@@ -308,16 +383,10 @@ fn make_keyword(name: &[u8]) -> [u8; 8] {
 }
 
 fn find_string_keyword(cards: &[Card], keyword: &str) -> Option<String> {
-    cards.iter().find_map(|c| {
-        if c.keyword_str() == keyword {
-            match &c.value {
-                Some(Value::String(s)) => Some(s.clone()),
-                _ => None,
-            }
-        } else {
-            None
-        }
-    })
+    match keyword_value(cards, keyword) {
+        Some(Value::String(s)) => Some(s.clone()),
+        _ => None,
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -327,6 +396,7 @@ fn find_string_keyword(cards: &[Card], keyword: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     // ---- Encoding/Decoding ----
 
@@ -603,4 +673,77 @@ mod tests {
         assert!(!verify_datasum(&fits_bytes, hdu), "DATASUM should fail");
         assert!(!verify_checksum(&fits_bytes, hdu), "CHECKSUM should fail");
     }
+
+    #[test]
+    fn stamp_datasum_only() {
+        use crate::hdu::parse_fits;
+        use crate::header::serialize_header;
+        use crate::primary::build_primary_header;
+
+        // Two words whose ones-complement sum wraps: 0xFFFFFFFF + 2 = 2.
+        let data = vec![0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x02];
+        let mut cards = build_primary_header(8, &[8]).unwrap();
+        cards.push(Card {
+            keyword: make_keyword(b"CHECKSUM"),
+            value: Some(Value::String(String::from("AAAAAAAAAAAAAAAA"))),
+            comment: None,
+        });
+        let mut hdu_bytes = serialize_header(&cards).unwrap();
+        hdu_bytes.extend_from_slice(&data);
+        hdu_bytes.resize(padded_byte_len(hdu_bytes.len()), 0u8);
+
+        let stamped = stamp_datasum(&hdu_bytes).unwrap();
+        assert_eq!(stamped.len(), hdu_bytes.len());
+
+        let fits = parse_fits(&stamped).unwrap();
+        let hdu = fits.primary();
+        assert_eq!(
+            find_string_keyword(&hdu.cards, "DATASUM").as_deref(),
+            Some("2")
+        );
+        assert!(find_string_keyword(&hdu.cards, "CHECKSUM").is_none());
+        assert!(verify_datasum(&stamped, hdu));
+        assert_eq!(&stamped[hdu.data_start..hdu.data_start + 8], &data[..]);
+    }
+
+    #[test]
+    fn checksum_without_datasum_verifies() {
+        use crate::hdu::parse_fits;
+        use crate::primary::build_primary_header;
+
+        let data = vec![0x5Au8; 64];
+        let mut cards = build_primary_header(8, &[64]).unwrap();
+        cards.push(Card {
+            keyword: make_keyword(b"CHECKSUM"),
+            value: Some(Value::String(String::from("0000000000000000"))),
+            comment: None,
+        });
+        let hdu_bytes = |cards: &[Card]| {
+            let mut bytes = serialize_header(cards).unwrap();
+            bytes.extend_from_slice(&data);
+            pad_to_block(bytes, DATA_PAD_BYTE)
+        };
+        let encoded = encode_checksum(checksum_blocks(&hdu_bytes(&cards)), true);
+        let last = cards.len() - 1;
+        cards[last].value = Some(Value::String(String::from_utf8(encoded.to_vec()).unwrap()));
+        let mut fits_bytes = hdu_bytes(&cards);
+
+        let hdu = parse_fits(&fits_bytes).unwrap().primary().clone();
+        assert!(find_string_keyword(&hdu.cards, "DATASUM").is_none());
+        assert_eq!(verify_hdu_checksum(&fits_bytes, &hdu), ChecksumStatus::Ok);
+        assert!(verify_checksum(&fits_bytes, &hdu));
+
+        fits_bytes[hdu.data_start] ^= 0x01;
+        assert_eq!(
+            verify_hdu_checksum(&fits_bytes, &hdu),
+            ChecksumStatus::ChecksumMismatch
+        );
+
+        let datasum_only = stamp_datasum(&fits_bytes).unwrap();
+        let hdu = parse_fits(&datasum_only).unwrap().primary().clone();
+        assert_eq!(
+            verify_hdu_checksum(&datasum_only, &hdu),
+            ChecksumStatus::Missing
+        );
+    }
 }
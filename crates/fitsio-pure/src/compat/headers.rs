@@ -26,14 +26,10 @@ fn find_card_value(file: &FitsFile, hdu: &FitsHdu, name: &str) -> Result<crate::
         hdu.hdu_index
     )))?;
 
-    for card in &core_hdu.cards {
-        if card.keyword_str() == name {
-            if let Some(ref v) = card.value {
-                return Ok(v.clone());
-            }
-        }
-    }
-    Err(Error::Message(format!("keyword '{name}' not found")))
+    crate::header::keyword_value(&core_hdu.cards, name)
+        .cloned()
+        .or_else(|| crate::header::hierarch_value(&core_hdu.cards, name))
+        .ok_or_else(|| Error::Message(format!("keyword '{name}' not found")))
 }
 
 impl ReadsKey for i64 {
@@ -98,6 +94,18 @@ fn write_key_to_file(
             hdu.hdu_index
         )))?;
 
+    // Names that do not fit an 8-byte keyword are written as HIERARCH cards.
+    if name.len() > 8 {
+        let new_card = crate::header::hierarch_card(name, &value)?;
+        core_hdu
+            .cards
+            .retain(|c| !(&c.keyword == b"HIERARCH" && hierarch_name(c) == Some(name)));
+        let end_idx = core_hdu.cards.iter().position(|c| c.is_end());
+        let idx = end_idx.unwrap_or(core_hdu.cards.len());
+        core_hdu.cards.insert(idx, new_card);
+        return rebuild_fits_data(file, &fits_data);
+    }
+
     let keyword = make_keyword(name);
     let mut found = false;
     for card in &mut core_hdu.cards {
@@ -125,12 +133,19 @@ fn write_key_to_file(
     rebuild_fits_data(file, &fits_data)
 }
 
+/// The keyword named by a `HIERARCH` card, e.g. `ESO DET CHIP TEMP`.
+fn hierarch_name(card: &crate::header::Card) -> Option<&str> {
+    let (name, _) = card.comment.as_deref()?.split_once('=')?;
+    Some(name.trim())
+}
+
 fn rebuild_fits_data(file: &mut FitsFile, fits_data: &crate::hdu::FitsData) -> Result<()> {
     let mut new_data = Vec::new();
 
     for (i, hdu) in fits_data.hdus.iter().enumerate() {
         let cards_without_end: Vec<_> = hdu.cards.iter().filter(|c| !c.is_end()).cloned().collect();
-        let header_bytes = crate::header::serialize_header(&cards_without_end)?;
+        let header_bytes =
+            crate::header::serialize_header_with_pad(&cards_without_end, hdu.header_pad_byte)?;
         new_data.extend_from_slice(&header_bytes);
 
         if hdu.data_len > 0 {
@@ -223,6 +238,22 @@ mod tests {
         assert_eq!(val, "NGC 1234");
     }
 
+    #[test]
+    fn read_write_long_key_as_hierarch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.fits");
+        let mut f = FitsFile::create(&path).open().unwrap();
+        let hdu = f.primary_hdu().unwrap();
+        f64::write_key(&mut f, &hdu, "ESO DET CHIP TEMP", &-120.5).unwrap();
+        f64::write_key(&mut f, &hdu, "ESO DET CHIP TEMP", &-119.0).unwrap();
+        let val = f64::read_key(&f, &hdu, "ESO DET CHIP TEMP").unwrap();
+        assert_eq!(val, -119.0);
+        assert!(i64::read_key(&f, &hdu, "ESO DET CH").is_err());
+
+        let too_long = "X".repeat(70);
+        assert!(i64::write_key(&mut f, &hdu, &too_long, &1).is_err());
+    }
+
     #[test]
     fn read_missing_key_returns_error() {
         let dir = tempfile::tempdir().unwrap();
@@ -127,6 +127,9 @@ fn extract_from_image_data<T: Clone>(
         crate::image::ImageData::I64(v) => convert_i64(v),
         crate::image::ImageData::F32(v) => convert_f32(v),
         crate::image::ImageData::F64(v) => convert_f64(v),
+        crate::image::ImageData::U32(v) => {
+            convert_i64(&v.iter().map(|&p| i64::from(p)).collect::<Vec<_>>())
+        }
     }
 }
 
@@ -64,6 +64,58 @@ pub fn read_f64_be(buf: &[u8]) -> f64 {
     ])
 }
 
+// ---------------------------------------------------------------------------
+// Checked reads: `None` when the slice is too short
+// ---------------------------------------------------------------------------
+
+/// Read a big-endian `i16`, or `None` if the slice holds fewer than 2 bytes.
+#[inline]
+pub fn try_read_i16_be(buf: &[u8]) -> Option<i16> {
+    buf.first_chunk().copied().map(i16::from_be_bytes)
+}
+
+/// Read a big-endian `u16`, or `None` if the slice holds fewer than 2 bytes.
+#[inline]
+pub fn try_read_u16_be(buf: &[u8]) -> Option<u16> {
+    buf.first_chunk().copied().map(u16::from_be_bytes)
+}
+
+/// Read a big-endian `i32`, or `None` if the slice holds fewer than 4 bytes.
+#[inline]
+pub fn try_read_i32_be(buf: &[u8]) -> Option<i32> {
+    buf.first_chunk().copied().map(i32::from_be_bytes)
+}
+
+/// Read a big-endian `u32`, or `None` if the slice holds fewer than 4 bytes.
+#[inline]
+pub fn try_read_u32_be(buf: &[u8]) -> Option<u32> {
+    buf.first_chunk().copied().map(u32::from_be_bytes)
+}
+
+/// Read a big-endian `i64`, or `None` if the slice holds fewer than 8 bytes.
+#[inline]
+pub fn try_read_i64_be(buf: &[u8]) -> Option<i64> {
+    buf.first_chunk().copied().map(i64::from_be_bytes)
+}
+
+/// Read a big-endian `u64`, or `None` if the slice holds fewer than 8 bytes.
+#[inline]
+pub fn try_read_u64_be(buf: &[u8]) -> Option<u64> {
+    buf.first_chunk().copied().map(u64::from_be_bytes)
+}
+
+/// Read a big-endian `f32`, or `None` if the slice holds fewer than 4 bytes.
+#[inline]
+pub fn try_read_f32_be(buf: &[u8]) -> Option<f32> {
+    buf.first_chunk().copied().map(f32::from_be_bytes)
+}
+
+/// Read a big-endian `f64`, or `None` if the slice holds fewer than 8 bytes.
+#[inline]
+pub fn try_read_f64_be(buf: &[u8]) -> Option<f64> {
+    buf.first_chunk().copied().map(f64::from_be_bytes)
+}
+
 // --- Single-value writes ---
 
 /// Write a `u8` into the first byte of the slice.
@@ -248,8 +300,9 @@ pub fn buf_f32_be_to_native(buf: &mut [u8]) {
         "buffer length must be a multiple of 4"
     );
     for chunk in buf.chunks_exact_mut(4) {
-        let val = f32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
-        let native = val.to_ne_bytes();
+        // Swap via the integer bit pattern so NaN payloads are preserved exactly.
+        let bits = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        let native = bits.to_ne_bytes();
         chunk.copy_from_slice(&native);
     }
 }
@@ -264,10 +317,11 @@ pub fn buf_f64_be_to_native(buf: &mut [u8]) {
         "buffer length must be a multiple of 8"
     );
     for chunk in buf.chunks_exact_mut(8) {
-        let val = f64::from_be_bytes([
+        // Swap via the integer bit pattern so NaN payloads are preserved exactly.
+        let bits = u64::from_be_bytes([
             chunk[0], chunk[1], chunk[2], chunk[3], chunk[4], chunk[5], chunk[6], chunk[7],
         ]);
-        let native = val.to_ne_bytes();
+        let native = bits.to_ne_bytes();
         chunk.copy_from_slice(&native);
     }
 }
@@ -335,8 +389,9 @@ pub fn buf_f32_native_to_be(buf: &mut [u8]) {
         "buffer length must be a multiple of 4"
     );
     for chunk in buf.chunks_exact_mut(4) {
-        let val = f32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
-        let be = val.to_be_bytes();
+        // Swap via the integer bit pattern so NaN payloads are preserved exactly.
+        let bits = u32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        let be = bits.to_be_bytes();
         chunk.copy_from_slice(&be);
     }
 }
@@ -351,10 +406,11 @@ pub fn buf_f64_native_to_be(buf: &mut [u8]) {
         "buffer length must be a multiple of 8"
     );
     for chunk in buf.chunks_exact_mut(8) {
-        let val = f64::from_ne_bytes([
+        // Swap via the integer bit pattern so NaN payloads are preserved exactly.
+        let bits = u64::from_ne_bytes([
             chunk[0], chunk[1], chunk[2], chunk[3], chunk[4], chunk[5], chunk[6], chunk[7],
         ]);
-        let be = val.to_be_bytes();
+        let be = bits.to_be_bytes();
         chunk.copy_from_slice(&be);
     }
 }
@@ -770,4 +826,34 @@ mod tests {
         assert_eq!(read_i16_be(&buf[4..]), 2_i16);
         assert_eq!(read_i32_be(&buf[0..]), 1_i32);
     }
+
+    #[test]
+    fn try_read_short_slices_return_none() {
+        assert_eq!(try_read_i16_be(&[0x01]), None);
+        assert_eq!(try_read_u16_be(&[]), None);
+        assert_eq!(try_read_i32_be(&[0, 0, 1]), None);
+        assert_eq!(try_read_u32_be(&[0xFF; 3]), None);
+        assert_eq!(try_read_i64_be(&[0; 7]), None);
+        assert_eq!(try_read_u64_be(&[0; 4]), None);
+        assert_eq!(try_read_f32_be(&[0x3F, 0x80]), None);
+        assert_eq!(try_read_f64_be(&[0; 7]), None);
+    }
+
+    #[test]
+    fn try_read_matches_unchecked_reads() {
+        let buf = [0x80, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        assert_eq!(try_read_i16_be(&buf), Some(read_i16_be(&buf)));
+        assert_eq!(try_read_u16_be(&buf), Some(read_u16_be(&buf)));
+        assert_eq!(try_read_i32_be(&buf), Some(read_i32_be(&buf)));
+        assert_eq!(try_read_u32_be(&buf), Some(read_u32_be(&buf)));
+        assert_eq!(try_read_i64_be(&buf), Some(read_i64_be(&buf)));
+        assert_eq!(try_read_u64_be(&buf), Some(read_u64_be(&buf)));
+        assert_eq!(try_read_f32_be(&[0x3F, 0x80, 0, 0]), Some(1.0));
+        assert_eq!(
+            try_read_f64_be(&[0x40, 0x09, 0x21, 0xFB, 0x54, 0x44, 0x2D, 0x18]),
+            Some(read_f64_be(&[
+                0x40, 0x09, 0x21, 0xFB, 0x54, 0x44, 0x2D, 0x18
+            ]))
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 /// All errors that can occur during FITS I/O operations.
 #[derive(Debug)]
 pub enum Error {
@@ -19,6 +21,24 @@ pub enum Error {
     UnsupportedCompression(&'static str),
     /// Error during tile decompression (Rice/GZIP).
     DecompressionError(&'static str),
+    /// TSCALn/TZEROn present on a column type that does not permit scaling
+    /// (`A`, `L` or `X`). Holds the 0-based column index.
+    InvalidScaling(usize),
+    /// The number of pixels supplied does not match the product of NAXISn.
+    DimensionMismatch {
+        /// Pixel count implied by the axis lengths.
+        expected: usize,
+        /// Pixel count actually supplied.
+        got: usize,
+    },
+    /// The binary table columns declared by TFORMn need more bytes per row
+    /// than NAXIS1 provides.
+    ColumnLayoutMismatch {
+        /// Row width declared by NAXIS1.
+        expected_naxis1: usize,
+        /// Sum of the column byte widths.
+        got: usize,
+    },
     /// An I/O error from the standard library.
     #[cfg(feature = "std")]
     Io(std::io::Error),
@@ -27,6 +47,104 @@ pub enum Error {
 /// Convenience alias used throughout the crate.
 pub type Result<T> = core::result::Result<T, Error>;
 
+/// A non-fatal problem tolerated while parsing in lenient mode.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseWarning {
+    /// A required keyword was absent and its default value was assumed.
+    DefaultedKeyword {
+        /// The missing keyword.
+        keyword: String,
+        /// The value substituted for it.
+        value: i64,
+    },
+    /// A numeric keyword was written as a quoted string and parsed as a number.
+    QuotedNumber {
+        /// The affected keyword.
+        keyword: String,
+        /// The number read from the string.
+        value: f64,
+    },
+    /// An integer keyword was written as an integral float (e.g. `-32.0`).
+    IntegralFloat {
+        /// The affected keyword.
+        keyword: String,
+        /// The integer value used.
+        value: i64,
+    },
+    /// A binary table TFORMn value had trailing punctuation and was
+    /// rewritten.
+    MalformedTform {
+        /// The affected keyword.
+        keyword: String,
+        /// The TFORM value used instead.
+        value: String,
+    },
+    /// The primary header has `SIMPLE = F`, declaring that the file does not
+    /// conform to the FITS standard.
+    NonConforming,
+    /// An HDU's data extends past the end of the input.
+    TruncatedData {
+        /// Index of the HDU (0 = primary).
+        hdu: usize,
+        /// Data length in bytes implied by the header.
+        expected: usize,
+        /// Data bytes actually present.
+        available: usize,
+    },
+    /// An END card was followed by more header cards and was skipped.
+    StrayEnd {
+        /// Index of the HDU (0 = primary).
+        hdu: usize,
+    },
+    /// Bytes that do not start an extension header were skipped after a
+    /// primary HDU with no data.
+    SkippedBytes {
+        /// Byte offset of the first skipped byte.
+        offset: usize,
+        /// Number of bytes skipped (whole 2880-byte blocks, or up to EOF).
+        len: usize,
+    },
+}
+
+impl core::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseWarning::DefaultedKeyword { keyword, value } => {
+                write!(f, "missing keyword {keyword}, assuming {value}")
+            }
+            ParseWarning::QuotedNumber { keyword, value } => {
+                write!(f, "keyword {keyword} is a quoted string, read as {value}")
+            }
+            ParseWarning::IntegralFloat { keyword, value } => {
+                write!(f, "keyword {keyword} is a float, read as {value}")
+            }
+            ParseWarning::MalformedTform { keyword, value } => {
+                write!(f, "keyword {keyword} is malformed, read as '{value}'")
+            }
+            ParseWarning::NonConforming => {
+                write!(f, "SIMPLE = F: file does not conform to the FITS standard")
+            }
+            ParseWarning::TruncatedData {
+                hdu,
+                expected,
+                available,
+            } => write!(
+                f,
+                "HDU {hdu} data truncated: expected {expected} bytes, found {available}"
+            ),
+            ParseWarning::StrayEnd { hdu } => {
+                write!(f, "HDU {hdu} header continues past a stray END card")
+            }
+            ParseWarning::SkippedBytes { offset, len } => {
+                write!(
+                    f,
+                    "skipped {len} bytes of unexpected data at offset {offset}"
+                )
+            }
+        }
+    }
+}
+
 impl core::fmt::Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
@@ -41,6 +159,19 @@ impl core::fmt::Display for Error {
                 write!(f, "unsupported compression algorithm: {ctx}")
             }
             Error::DecompressionError(ctx) => write!(f, "decompression error: {ctx}"),
+            Error::InvalidScaling(col) => {
+                write!(f, "TSCAL/TZERO not allowed on column index {col}")
+            }
+            Error::DimensionMismatch { expected, got } => {
+                write!(f, "image dimensions need {expected} pixels, got {got}")
+            }
+            Error::ColumnLayoutMismatch {
+                expected_naxis1,
+                got,
+            } => write!(
+                f,
+                "binary table columns need {got} bytes per row, NAXIS1 is {expected_naxis1}"
+            ),
             #[cfg(feature = "std")]
             Error::Io(e) => write!(f, "I/O error: {e}"),
         }
@@ -67,6 +198,7 @@ impl From<std::io::Error> for Error {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::string::ToString;
 
     #[test]
     fn display_invalid_header() {
@@ -110,6 +242,42 @@ mod tests {
         assert_eq!(e.to_string(), "missing required keyword: NAXIS");
     }
 
+    #[test]
+    fn display_invalid_scaling() {
+        let e = Error::InvalidScaling(2);
+        assert_eq!(e.to_string(), "TSCAL/TZERO not allowed on column index 2");
+    }
+
+    #[test]
+    fn display_dimension_mismatch() {
+        let e = Error::DimensionMismatch {
+            expected: 12,
+            got: 10,
+        };
+        assert_eq!(e.to_string(), "image dimensions need 12 pixels, got 10");
+    }
+
+    #[test]
+    fn display_column_layout_mismatch() {
+        let e = Error::ColumnLayoutMismatch {
+            expected_naxis1: 8,
+            got: 12,
+        };
+        assert_eq!(
+            e.to_string(),
+            "binary table columns need 12 bytes per row, NAXIS1 is 8"
+        );
+    }
+
+    #[test]
+    fn display_defaulted_keyword_warning() {
+        let w = ParseWarning::DefaultedKeyword {
+            keyword: String::from("GCOUNT"),
+            value: 1,
+        };
+        assert_eq!(w.to_string(), "missing keyword GCOUNT, assuming 1");
+    }
+
     #[cfg(feature = "std")]
     #[test]
     fn display_io_error() {
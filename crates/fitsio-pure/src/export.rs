@@ -0,0 +1,364 @@
+//! Delimited-text export of table HDUs.
+//!
+//! [`export_table_delimited`] writes a binary or ASCII table as CSV, TSV or
+//! any other single-character-delimited text, one line per row.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Write as _};
+
+use crate::bintable::{
+    column_display_name, column_offsets, extract_table_info, read_binary_column_vla,
+    read_column_cells, BinaryColumnData, BinaryColumnType,
+};
+use crate::error::{Error, Result};
+use crate::hdu::{Hdu, HduInfo};
+use crate::header::{io_error, keyword_value, Card};
+use crate::io::Write;
+use crate::table::{
+    ascii_table_dims, parse_ascii_table_columns, parse_column_values, AsciiColumnData,
+};
+use crate::value::Value;
+
+/// Write a table HDU as delimited text.
+///
+/// The first line holds the column names, resolved as by
+/// [`column_display_name`]; each following line is one table row. Cells are
+/// the stored values, without TSCAL/TZERO applied. Array cells list their
+/// elements separated by single spaces, logicals print as `T`/`F`, complex
+/// values as `(re,im)` and bit arrays as a string of `0`/`1`. Float columns
+/// with an `F`, `E` or `D` `TDISPn` use its number of decimals; other floats
+/// print in the shortest form that reads back exactly. A cell containing the
+/// delimiter, a double quote or a line break is quoted as in RFC 4180.
+///
+/// Rows are decoded and written one at a time. Variable-length array
+/// columns are the exception: they are read in full before the first row.
+/// Returns `Error::InvalidHeader` if `hdu` is not a table.
+pub fn export_table_delimited<W: Write>(
+    fits_data: &[u8],
+    hdu: &Hdu,
+    delim: char,
+    out: &mut W,
+) -> Result<()> {
+    match hdu.info {
+        HduInfo::BinaryTable { .. } => export_binary(fits_data, hdu, delim, out),
+        HduInfo::AsciiTable { .. } => export_ascii(fits_data, hdu, delim, out),
+        _ => Err(Error::InvalidHeader("not a table HDU")),
+    }
+}
+
+fn export_binary<W: Write>(fits_data: &[u8], hdu: &Hdu, delim: char, out: &mut W) -> Result<()> {
+    let (naxis1, naxis2, columns) = extract_table_info(fits_data, hdu)?;
+    let offsets = column_offsets(&columns);
+    let displays = float_displays(&hdu.cards, columns.len());
+    let vla_columns = columns
+        .iter()
+        .enumerate()
+        .map(|(i, col)| match col.col_type {
+            BinaryColumnType::VarArrayP(_) | BinaryColumnType::VarArrayQ(_) => {
+                read_binary_column_vla(fits_data, hdu, i).map(Some)
+            }
+            _ => Ok(None),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let names: Vec<String> = (0..columns.len())
+        .map(|i| column_display_name(&columns, i))
+        .collect();
+    write_line(out, names.iter().map(String::as_str), delim)?;
+
+    let mut cells = Vec::with_capacity(columns.len());
+    for row in 0..naxis2 {
+        cells.clear();
+        for (i, col) in columns.iter().enumerate() {
+            let mut field = String::new();
+            match &vla_columns[i] {
+                Some(data) => push_binary_cell(&mut field, data, row, col.repeat, displays[i]),
+                None => {
+                    let row_start = hdu.data_start + row * naxis1;
+                    let data = read_column_cells(
+                        fits_data,
+                        row_start,
+                        naxis1,
+                        1,
+                        col,
+                        offsets[i],
+                        hdu.little_endian,
+                    )?;
+                    push_binary_cell(&mut field, &data, 0, col.repeat, displays[i]);
+                }
+            }
+            cells.push(field);
+        }
+        write_line(out, cells.iter().map(String::as_str), delim)?;
+    }
+    Ok(())
+}
+
+fn export_ascii<W: Write>(fits_data: &[u8], hdu: &Hdu, delim: char, out: &mut W) -> Result<()> {
+    let (naxis1, naxis2, tfields) = ascii_table_dims(hdu)?;
+    let columns = parse_ascii_table_columns(&hdu.cards, tfields)?;
+    if hdu.data_start + naxis1 * naxis2 > fits_data.len() {
+        return Err(Error::UnexpectedEof);
+    }
+    let displays = float_displays(&hdu.cards, columns.len());
+
+    let names: Vec<Option<&str>> = columns.iter().map(|c| c.name.as_deref()).collect();
+    let names: Vec<String> = (0..columns.len())
+        .map(|i| crate::bintable::unique_column_name(&names, i))
+        .collect();
+    write_line(out, names.iter().map(String::as_str), delim)?;
+
+    let mut cells = Vec::with_capacity(columns.len());
+    for row in 0..naxis2 {
+        cells.clear();
+        let row_start = hdu.data_start + row * naxis1;
+        for (col, &display) in columns.iter().zip(&displays) {
+            let mut field = String::new();
+            match parse_column_values(fits_data, row_start, naxis1, 1, col, false)? {
+                AsciiColumnData::Character(v) => field.push_str(&v[0]),
+                AsciiColumnData::Integer(v) => push_display(&mut field, v[0]),
+                AsciiColumnData::Float(v) => push_float(&mut field, v[0], display),
+            }
+            cells.push(field);
+        }
+        write_line(out, cells.iter().map(String::as_str), delim)?;
+    }
+    Ok(())
+}
+
+/// How a `TDISPn` asks for floats to be shown.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FloatDisplay {
+    /// `Fw.d`: fixed point with `d` decimals.
+    Fixed(usize),
+    /// `Ew.d`, `ESw.d`, `ENw.d`, `Dw.d`: exponential with `d` decimals.
+    Exponential(usize),
+}
+
+fn float_displays(cards: &[Card], tfields: usize) -> Vec<Option<FloatDisplay>> {
+    (1..=tfields)
+        .map(
+            |i| match keyword_value(cards, &alloc::format!("TDISP{}", i)) {
+                Some(Value::String(s)) => parse_float_tdisp(s),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+/// Parse the float forms of a `TDISPn` value; other forms give `None`.
+fn parse_float_tdisp(tdisp: &str) -> Option<FloatDisplay> {
+    let tdisp = tdisp.trim();
+    let split = tdisp.find(|c: char| c.is_ascii_digit())?;
+    let (code, size) = tdisp.split_at(split);
+    let decimals = size.split_once('.')?.1;
+    let end = decimals
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(decimals.len());
+    let decimals = decimals[..end].parse().ok()?;
+    match code.to_ascii_uppercase().as_str() {
+        "F" => Some(FloatDisplay::Fixed(decimals)),
+        "E" | "ES" | "EN" | "D" => Some(FloatDisplay::Exponential(decimals)),
+        _ => None,
+    }
+}
+
+fn push_display(field: &mut String, value: impl Display) {
+    let _ = write!(field, "{}", value);
+}
+
+fn push_float<T: Into<f64> + Display + Copy>(
+    field: &mut String,
+    value: T,
+    display: Option<FloatDisplay>,
+) {
+    let v: f64 = value.into();
+    match display {
+        Some(FloatDisplay::Fixed(d)) if v.is_finite() => {
+            push_display(field, format_args!("{:.*}", d, v))
+        }
+        Some(FloatDisplay::Exponential(d)) if v.is_finite() => {
+            // Rust writes `1.5E3`; FITS tools expect a signed two-digit exponent.
+            let text = alloc::format!("{:.*E}", d, v);
+            let (mantissa, exp) = text.split_once('E').unwrap_or((&text, "0"));
+            let exp: i32 = exp.parse().unwrap_or(0);
+            push_display(field, format_args!("{}E{:+03}", mantissa, exp));
+        }
+        _ => push_display(field, value),
+    }
+}
+
+/// Append `items` to `field`, separated by single spaces.
+fn push_joined<T>(
+    field: &mut String,
+    items: impl IntoIterator<Item = T>,
+    mut push: impl FnMut(&mut String, T),
+) {
+    for (k, item) in items.into_iter().enumerate() {
+        if k > 0 {
+            field.push(' ');
+        }
+        push(field, item);
+    }
+}
+
+/// Format row `row` of `data`. Fixed-width columns read one row at a time
+/// pass `row = 0`; variable-length columns hold every row.
+fn push_binary_cell(
+    field: &mut String,
+    data: &BinaryColumnData,
+    row: usize,
+    repeat: usize,
+    display: Option<FloatDisplay>,
+) {
+    use BinaryColumnData as D;
+    let push_complex = |f: &mut String, re: f64, im: f64| {
+        f.push('(');
+        push_float(f, re, display);
+        f.push(',');
+        push_float(f, im, display);
+        f.push(')');
+    };
+    match data {
+        D::Logical(v) => push_joined(field, v, |f, &b| f.push(if b { 'T' } else { 'F' })),
+        D::Byte(v) => push_joined(field, v, push_display),
+        D::Short(v) => push_joined(field, v, push_display),
+        D::Int(v) => push_joined(field, v, push_display),
+        D::Long(v) => push_joined(field, v, push_display),
+        D::Float(v) => push_joined(field, v, |f, &v| push_float(f, v, display)),
+        D::Double(v) => push_joined(field, v, |f, &v| push_float(f, v, display)),
+        D::ComplexFloat(v) => push_joined(field, v, |f, &(re, im)| {
+            push_complex(f, re.into(), im.into())
+        }),
+        D::ComplexDouble(v) => push_joined(field, v, |f, &(re, im)| push_complex(f, re, im)),
+        D::Ascii(v) => field.push_str(v.get(row).map_or("", String::as_str)),
+        D::AsciiArray(v) => push_joined(field, v.get(row).into_iter().flatten(), |f, s| {
+            f.push_str(s)
+        }),
+        D::Bit(v) => {
+            let bytes = v.get(row).map_or(&[][..], Vec::as_slice);
+            for bit in 0..repeat.min(bytes.len() * 8) {
+                let set = bytes[bit / 8] & (0x80 >> (bit % 8)) != 0;
+                field.push(if set { '1' } else { '0' });
+            }
+        }
+        D::VarByte(v) => push_joined(field, v.get(row).into_iter().flatten(), push_display),
+        D::VarShort(v) => push_joined(field, v.get(row).into_iter().flatten(), push_display),
+        D::VarInt(v) => push_joined(field, v.get(row).into_iter().flatten(), push_display),
+        D::VarLong(v) => push_joined(field, v.get(row).into_iter().flatten(), push_display),
+        D::VarFloat(v) => push_joined(field, v.get(row).into_iter().flatten(), |f, &v| {
+            push_float(f, v, display)
+        }),
+        D::VarDouble(v) => push_joined(field, v.get(row).into_iter().flatten(), |f, &v| {
+            push_float(f, v, display)
+        }),
+    }
+}
+
+/// Write one line of cells, quoting those that need it.
+fn write_line<'a, W: Write>(
+    out: &mut W,
+    cells: impl Iterator<Item = &'a str>,
+    delim: char,
+) -> Result<()> {
+    let mut line = String::new();
+    for (k, cell) in cells.enumerate() {
+        if k > 0 {
+            line.push(delim);
+        }
+        if cell.contains([delim, '"', '\n', '\r']) {
+            line.push('"');
+            line.push_str(&cell.replace('"', "\"\""));
+            line.push('"');
+        } else {
+            line.push_str(cell);
+        }
+    }
+    line.push('\n');
+    out.write_all(line.as_bytes()).map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bintable::{build_fits_with_binary_table, BinaryColumnDescriptor};
+    use crate::hdu::parse_fits;
+    use crate::io::Cursor;
+    use alloc::vec;
+
+    fn column(
+        name: &str,
+        repeat: usize,
+        col_type: BinaryColumnType,
+        byte_width: usize,
+    ) -> BinaryColumnDescriptor {
+        BinaryColumnDescriptor {
+            name: Some(String::from(name)),
+            repeat,
+            col_type,
+            byte_width,
+            tdim: None,
+        }
+    }
+
+    fn export(fits: &[u8], hdu: &Hdu, delim: char) -> String {
+        let mut out = Cursor::new(Vec::new());
+        export_table_delimited(fits, hdu, delim, &mut out).unwrap();
+        String::from_utf8(out.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn export_mixed_table_as_csv() {
+        let columns = vec![
+            column("NAME", 8, BinaryColumnType::Ascii, 8),
+            column("ID", 1, BinaryColumnType::Int, 4),
+            column("FLUX", 1, BinaryColumnType::Float, 4),
+            column("GOOD", 1, BinaryColumnType::Logical, 1),
+            column("POS", 2, BinaryColumnType::Double, 16),
+            column("SPEC", 1, BinaryColumnType::VarArrayP('J'), 8),
+        ];
+        let data = vec![
+            BinaryColumnData::Ascii(vec![String::from("Vega"), String::from("a,b \"c\"")]),
+            BinaryColumnData::Int(vec![7, -2]),
+            BinaryColumnData::Float(vec![1.0 / 3.0, 12.5]),
+            BinaryColumnData::Logical(vec![true, false]),
+            BinaryColumnData::Double(vec![10.5, -1.25, 0.1, 2.0]),
+            BinaryColumnData::VarInt(vec![vec![1, 2, 3], vec![]]),
+        ];
+        let fits = build_fits_with_binary_table(&columns, &data, 2).unwrap();
+        let parsed = parse_fits(&fits).unwrap();
+        let mut hdu = parsed.get(1).unwrap().clone();
+        hdu.cards.push(Card {
+            keyword: *b"TDISP3  ",
+            value: Some(Value::String(String::from("F8.2"))),
+            comment: None,
+        });
+
+        assert_eq!(
+            export(&fits, &hdu, ','),
+            "NAME,ID,FLUX,GOOD,POS,SPEC\n\
+             Vega,7,0.33,T,10.5 -1.25,1 2 3\n\
+             \"a,b \"\"c\"\"\",-2,12.50,F,0.1 2,\n"
+        );
+        assert_eq!(
+            export(&fits, &hdu, '\t').lines().nth(2),
+            Some("\"a,b \"\"c\"\"\"\t-2\t12.50\tF\t0.1 2\t")
+        );
+    }
+
+    #[test]
+    fn parse_tdisp_float_forms() {
+        assert_eq!(parse_float_tdisp("F8.2"), Some(FloatDisplay::Fixed(2)));
+        assert_eq!(
+            parse_float_tdisp(" E15.7E3"),
+            Some(FloatDisplay::Exponential(7))
+        );
+        assert_eq!(parse_float_tdisp("I6"), None);
+        assert_eq!(parse_float_tdisp("G12.4"), None);
+
+        let mut field = String::new();
+        push_float(&mut field, 1234.56f64, Some(FloatDisplay::Exponential(3)));
+        assert_eq!(field, "1.235E+03");
+    }
+}
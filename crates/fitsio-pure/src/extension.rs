@@ -4,8 +4,9 @@ use alloc::vec::Vec;
 use crate::block::padded_byte_len;
 #[cfg(test)]
 use crate::block::BLOCK_SIZE;
-use crate::error::{Error, Result};
+use crate::error::{Error, ParseWarning, Result};
 use crate::header::{validate_required_keywords, Card, HduType};
+use crate::primary::VALID_BITPIX;
 use crate::value::Value;
 
 /// The type of FITS extension, determined by the XTENSION keyword value.
@@ -141,6 +142,9 @@ pub fn parse_extension_header(cards: &[Card]) -> Result<ExtensionHeader> {
 
     let bitpix_card = find_keyword(cards, &kw(b"BITPIX")).ok_or(Error::MissingKeyword("BITPIX"))?;
     let bitpix = extract_integer(bitpix_card, "BITPIX")?;
+    if !VALID_BITPIX.contains(&bitpix) {
+        return Err(Error::InvalidBitpix(bitpix));
+    }
 
     let naxis_card = find_keyword(cards, &kw(b"NAXIS")).ok_or(Error::MissingKeyword("NAXIS"))?;
     let naxis = extract_usize(naxis_card, "NAXIS")?;
@@ -173,6 +177,60 @@ pub fn parse_extension_header(cards: &[Card]) -> Result<ExtensionHeader> {
     })
 }
 
+/// Parse an extension header, tolerating a missing PCOUNT or GCOUNT.
+///
+/// Absent PCOUNT/GCOUNT cards are assumed to be 0 and 1 respectively and
+/// inserted after the NAXISn cards, each producing a
+/// [`ParseWarning::DefaultedKeyword`]. All other validation is as strict as
+/// [`parse_extension_header`].
+pub fn parse_extension_header_lenient(
+    cards: &[Card],
+) -> Result<(ExtensionHeader, Vec<ParseWarning>)> {
+    let mut warnings = Vec::new();
+    let mut cards = cards.to_vec();
+    insert_default_pcount_gcount(&mut cards, &mut warnings);
+    let header = parse_extension_header(&cards)?;
+    Ok((header, warnings))
+}
+
+/// Insert `PCOUNT = 0` and `GCOUNT = 1` cards when they are missing.
+///
+/// The new cards go after the last NAXISn card (or NAXIS), keeping the
+/// mandatory keyword order. A warning is pushed for each inserted card.
+pub(crate) fn insert_default_pcount_gcount(
+    cards: &mut Vec<Card>,
+    warnings: &mut Vec<ParseWarning>,
+) {
+    let mut insert_at = cards
+        .iter()
+        .rposition(|c| {
+            c.keyword_str()
+                .strip_prefix("NAXIS")
+                .is_some_and(|n| n.bytes().all(|b| b.is_ascii_digit()))
+        })
+        .map_or(cards.len(), |i| i + 1);
+
+    for (name, default) in [("PCOUNT", 0), ("GCOUNT", 1)] {
+        if let Some(i) = cards.iter().position(|c| c.keyword_str() == name) {
+            insert_at = insert_at.max(i + 1);
+            continue;
+        }
+        cards.insert(
+            insert_at,
+            Card {
+                keyword: kw(name.as_bytes()),
+                value: Some(Value::Integer(default)),
+                comment: None,
+            },
+        );
+        insert_at += 1;
+        warnings.push(ParseWarning::DefaultedKeyword {
+            keyword: String::from(name),
+            value: default,
+        });
+    }
+}
+
 /// Build a sequence of cards for an extension header.
 ///
 /// Creates the mandatory keywords in the order required by the FITS standard:
@@ -236,6 +294,7 @@ pub fn build_extension_header(
 mod tests {
     use super::*;
     use alloc::string::String;
+    use alloc::vec;
 
     fn card(keyword: &[u8], value: Option<Value>) -> Card {
         Card {
@@ -319,6 +378,16 @@ mod tests {
         assert_eq!(hdr.gcount, 1);
     }
 
+    #[test]
+    fn parse_extension_rejects_illegal_bitpix() {
+        let mut cards = make_image_cards();
+        cards[1].value = Some(Value::Integer(24));
+        assert!(matches!(
+            parse_extension_header(&cards),
+            Err(Error::InvalidBitpix(24))
+        ));
+    }
+
     #[test]
     fn data_byte_count_image() {
         let cards = make_image_cards();
@@ -591,4 +660,50 @@ mod tests {
         assert_eq!(cards[5].keyword_str(), "PCOUNT");
         assert_eq!(cards[6].keyword_str(), "GCOUNT");
     }
+
+    #[test]
+    fn lenient_defaults_missing_gcount() {
+        let mut cards = make_image_cards();
+        cards.retain(|c| c.keyword_str() != "GCOUNT");
+        cards.push(card(b"EXTNAME", Some(Value::String(String::from("SCI")))));
+
+        assert!(matches!(
+            parse_extension_header(&cards),
+            Err(Error::MissingKeyword("GCOUNT"))
+        ));
+
+        let (hdr, warnings) = parse_extension_header_lenient(&cards).unwrap();
+        assert_eq!(hdr.pcount, 0);
+        assert_eq!(hdr.gcount, 1);
+        assert_eq!(hdr.data_byte_count(), 4 * 512 * 256);
+        assert_eq!(
+            warnings,
+            vec![ParseWarning::DefaultedKeyword {
+                keyword: String::from("GCOUNT"),
+                value: 1
+            }]
+        );
+        assert_eq!(hdr.cards[6].keyword_str(), "GCOUNT");
+        assert_eq!(hdr.cards[7].keyword_str(), "EXTNAME");
+    }
+
+    #[test]
+    fn lenient_defaults_missing_pcount_and_gcount() {
+        let mut cards = make_image_cards();
+        cards.retain(|c| !matches!(c.keyword_str(), "PCOUNT" | "GCOUNT"));
+
+        let (hdr, warnings) = parse_extension_header_lenient(&cards).unwrap();
+        assert_eq!((hdr.pcount, hdr.gcount), (0, 1));
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(hdr.cards[5].keyword_str(), "PCOUNT");
+        assert_eq!(hdr.cards[6].keyword_str(), "GCOUNT");
+    }
+
+    #[test]
+    fn lenient_without_missing_keywords_has_no_warnings() {
+        let cards = make_image_cards();
+        let (hdr, warnings) = parse_extension_header_lenient(&cards).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(hdr, parse_extension_header(&cards).unwrap());
+    }
 }
@@ -1,13 +1,21 @@
 use alloc::string::String;
+use alloc::vec;
 use alloc::vec::Vec;
 
-use crate::block::{padded_byte_len, BLOCK_SIZE};
-use crate::error::{Error, Result};
-use crate::header::{header_byte_len, parse_header_blocks, Card};
-use crate::value::Value;
+use crate::bintable::{check_column_layout, parse_binary_table_columns, repair_tform};
+use crate::block::{padded_byte_len, BLOCK_SIZE, CARD_SIZE};
+use crate::error::{Error, ParseWarning, Result};
+use crate::extension::insert_default_pcount_gcount;
+use crate::header::{
+    detect_header_pad_byte, find_keyword_in_header, header_byte_len, keyword_value, parse_card,
+    parse_header_blocks, parse_header_blocks_inner, serialize_header_with_pad, Card, TextEncoding,
+};
+use crate::primary::VALID_BITPIX;
+use crate::value::{parse_float_str, Value};
 
 /// Describes the kind and shape of data in a single HDU.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HduInfo {
     /// Primary HDU containing image data.
     Primary {
@@ -55,6 +63,10 @@ pub enum HduInfo {
         gcount: usize,
     },
     /// Tile-compressed image stored as a binary table (ZIMAGE=T).
+    ///
+    /// `ZIMAGE = T` alone selects this variant; `ZTENSION` is not
+    /// consulted. The tile columns remain readable through the
+    /// [`bintable`](crate::bintable) functions.
     CompressedImage {
         /// Original image BITPIX before compression.
         zbitpix: i64,
@@ -80,7 +92,11 @@ pub enum HduInfo {
 }
 
 /// A single Header Data Unit parsed from a FITS byte stream.
+///
+/// Fields may be added in minor releases; build one outside the parser with
+/// [`Hdu::new`].
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct Hdu {
     /// Parsed metadata describing the HDU type and shape.
     pub info: HduInfo,
@@ -92,6 +108,72 @@ pub struct Hdu {
     pub data_len: usize,
     /// All header cards parsed from this HDU.
     pub cards: Vec<Card>,
+    /// See [`Hdu::header_pad_byte`].
+    pub(crate) header_pad_byte: u8,
+    /// See [`Hdu::little_endian`].
+    pub(crate) little_endian: bool,
+}
+
+impl Hdu {
+    /// Describe an HDU whose header starts at `header_start` and whose data
+    /// segment of `data_len` bytes starts at `data_start`. The header is
+    /// taken to be space-padded and the data big-endian.
+    pub fn new(
+        info: HduInfo,
+        header_start: usize,
+        data_start: usize,
+        data_len: usize,
+        cards: Vec<Card>,
+    ) -> Self {
+        Hdu {
+            info,
+            header_start,
+            data_start,
+            data_len,
+            cards,
+            header_pad_byte: b' ',
+            little_endian: false,
+        }
+    }
+
+    /// Byte padding the header after END: ASCII space as the standard
+    /// requires, or 0x00 for files written by some older tools.
+    pub fn header_pad_byte(&self) -> u8 {
+        self.header_pad_byte
+    }
+
+    /// Whether the data segment is stored little-endian. Only set when
+    /// parsing with [`ParseOptions::honor_byteorder`] and the header carries
+    /// the non-standard `HIERARCH BYTEORDER = 'LITTLE'` (or
+    /// `BYTEORDR = 'LITTLE'`); FITS data is otherwise always big-endian.
+    pub fn little_endian(&self) -> bool {
+        self.little_endian
+    }
+
+    /// Returns the EXTNAME of this HDU, if present.
+    pub fn extname(&self) -> Option<String> {
+        card_string_value(&self.cards, "EXTNAME")
+    }
+
+    /// Returns the EXTVER of this HDU, defaulting to 1 when absent.
+    pub fn extver(&self) -> i64 {
+        card_integer_value(&self.cards, "EXTVER").unwrap_or(1)
+    }
+
+    /// Returns the EXTLEVEL (hierarchical nesting level) of this HDU,
+    /// defaulting to 1 when absent.
+    pub fn extlevel(&self) -> i64 {
+        card_integer_value(&self.cards, "EXTLEVEL").unwrap_or(1)
+    }
+
+    /// Returns the number of 2880-byte blocks taken by the header and by
+    /// the padded data segment.
+    pub fn block_span(&self) -> (usize, usize) {
+        (
+            (self.data_start - self.header_start) / BLOCK_SIZE,
+            padded_byte_len(self.data_len) / BLOCK_SIZE,
+        )
+    }
 }
 
 /// A collection of HDUs parsed from a complete FITS file.
@@ -101,6 +183,27 @@ pub struct FitsData {
     pub hdus: Vec<Hdu>,
 }
 
+/// One line of a file's table of contents, as returned by
+/// [`FitsData::toc`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HduEntry {
+    /// Position of the HDU in the file, 0 for the primary.
+    pub index: usize,
+    /// HDU type: `"PRIMARY"`, `"IMAGE"`, `"TABLE"`, `"BINTABLE"`, `"GROUPS"`
+    /// or `"COMPRESSED_IMAGE"`.
+    pub kind: &'static str,
+    /// EXTNAME, if present.
+    pub name: Option<String>,
+    /// EXTVER, defaulting to 1.
+    pub ver: i64,
+    /// Axis lengths (NAXIS1 first). Tables report `[NAXIS1, NAXIS2]`,
+    /// compressed images their uncompressed `ZNAXISn`.
+    pub shape: Vec<usize>,
+    /// Row count for ASCII and binary tables, `None` otherwise.
+    pub rows: Option<usize>,
+}
+
 impl FitsData {
     /// Returns the primary (first) HDU.
     pub fn primary(&self) -> &Hdu {
@@ -135,45 +238,184 @@ impl FitsData {
     pub fn iter(&self) -> impl Iterator<Item = &Hdu> {
         self.hdus.iter()
     }
-}
 
-fn card_integer_value(cards: &[Card], keyword: &str) -> Option<i64> {
-    cards.iter().find_map(|c| {
-        if c.keyword_str() == keyword {
-            match &c.value {
-                Some(Value::Integer(n)) => Some(*n),
-                _ => None,
+    /// Returns the number of 2880-byte blocks spanned by all HDUs, i.e. the
+    /// length of the file once every HDU is padded.
+    pub fn total_blocks(&self) -> usize {
+        self.hdus
+            .iter()
+            .map(|hdu| {
+                let (header, data) = hdu.block_span();
+                header + data
+            })
+            .sum()
+    }
+
+    /// Lists every HDU's type, name and shape without reading any data.
+    pub fn toc(&self) -> Vec<HduEntry> {
+        self.hdus
+            .iter()
+            .enumerate()
+            .map(|(index, hdu)| {
+                let (kind, shape, rows) = match &hdu.info {
+                    HduInfo::Primary { naxes, .. } => ("PRIMARY", naxes.clone(), None),
+                    HduInfo::Image { naxes, .. } => ("IMAGE", naxes.clone(), None),
+                    HduInfo::AsciiTable { naxis1, naxis2, .. } => {
+                        ("TABLE", vec![*naxis1, *naxis2], Some(*naxis2))
+                    }
+                    HduInfo::BinaryTable { naxis1, naxis2, .. } => {
+                        ("BINTABLE", vec![*naxis1, *naxis2], Some(*naxis2))
+                    }
+                    HduInfo::RandomGroups { naxes, .. } => ("GROUPS", naxes.clone(), None),
+                    HduInfo::CompressedImage { znaxes, .. } => {
+                        ("COMPRESSED_IMAGE", znaxes.clone(), None)
+                    }
+                };
+                HduEntry {
+                    index,
+                    kind,
+                    name: hdu.extname(),
+                    ver: hdu.extver(),
+                    shape,
+                    rows,
+                }
+            })
+            .collect()
+    }
+
+    /// Read the raw pixel data of the primary HDU.
+    ///
+    /// `fits_data` must be the byte stream this `FitsData` was parsed from.
+    /// Returns an error if the primary HDU carries no image data.
+    pub fn read_primary_image(&self, fits_data: &[u8]) -> Result<crate::image::ImageData> {
+        let primary = self.primary_with_image()?;
+        crate::image::read_image_data(fits_data, primary)
+    }
+
+    /// Read the primary HDU pixels with BSCALE/BZERO applied and BLANK
+    /// pixels set to NaN.
+    ///
+    /// `fits_data` must be the byte stream this `FitsData` was parsed from.
+    /// Returns an error if the primary HDU carries no image data.
+    pub fn read_primary_physical(&self, fits_data: &[u8]) -> Result<Vec<f64>> {
+        let primary = self.primary_with_image()?;
+        crate::image::read_image_physical(fits_data, primary)
+    }
+
+    /// Iterates over every HDU that holds image pixels, decoding each one.
+    ///
+    /// Covers the primary array, IMAGE extensions and tile-compressed images
+    /// (as found in `.fits.fz` files), which are decompressed transparently
+    /// by [`read_image_data`](crate::image::read_image_data). Yields each
+    /// HDU's index with its pixels. HDUs without pixels (e.g. a primary with
+    /// `NAXIS = 0`) and tables are skipped.
+    ///
+    /// `fits_data` must be the byte stream this `FitsData` was parsed from.
+    pub fn images<'a>(
+        &'a self,
+        fits_data: &'a [u8],
+    ) -> impl Iterator<Item = (usize, Result<crate::image::ImageData>)> + 'a {
+        self.hdus
+            .iter()
+            .enumerate()
+            .filter(|(_, hdu)| match &hdu.info {
+                HduInfo::Primary { naxes, .. } | HduInfo::Image { naxes, .. } => {
+                    !naxes.is_empty() && hdu.data_len > 0
+                }
+                HduInfo::CompressedImage { .. } => true,
+                _ => false,
+            })
+            .map(move |(i, hdu)| (i, crate::image::read_image_data(fits_data, hdu)))
+    }
+
+    /// Returns the primary HDU if it holds random groups (`GROUPS = T`).
+    ///
+    /// Random groups are never treated as an image: [`images`](Self::images),
+    /// [`read_primary_image`](Self::read_primary_image) and
+    /// [`image_dimensions`](crate::image::image_dimensions) all skip or
+    /// reject them.
+    pub fn random_groups(&self) -> Option<&Hdu> {
+        let primary = self.hdus.first()?;
+        matches!(primary.info, HduInfo::RandomGroups { .. }).then_some(primary)
+    }
+
+    fn primary_with_image(&self) -> Result<&Hdu> {
+        let primary = self.primary();
+        match &primary.info {
+            HduInfo::Primary { naxes, .. } if !naxes.is_empty() && primary.data_len > 0 => {
+                Ok(primary)
             }
-        } else {
-            None
+            _ => Err(Error::InvalidHeader("primary HDU has no image data")),
         }
-    })
+    }
 }
 
-fn card_string_value(cards: &[Card], keyword: &str) -> Option<String> {
-    cards.iter().find_map(|c| {
-        if c.keyword_str() == keyword {
-            match &c.value {
-                Some(Value::String(s)) => Some(s.trim().into()),
-                _ => None,
+/// An HDU located in a borrowed FITS byte stream whose cards are parsed on
+/// demand, as returned by [`parse_fits_borrowed`].
+#[derive(Debug, Clone, Copy)]
+pub struct BorrowedHdu<'a> {
+    /// Raw header bytes, up to and including the block holding END.
+    header: &'a [u8],
+    /// Byte offset where the header begins in the FITS stream.
+    pub header_start: usize,
+    /// Byte offset where the data segment begins.
+    pub data_start: usize,
+    /// Length of the data segment in bytes (unpadded).
+    pub data_len: usize,
+}
+
+impl<'a> BorrowedHdu<'a> {
+    /// Parses the header cards one at a time, up to and including END.
+    ///
+    /// Each call re-reads the header bytes; nothing is cached. CONTINUE
+    /// cards are yielded as they appear rather than merged.
+    pub fn cards_iter(&self) -> impl Iterator<Item = Result<Card>> + 'a {
+        let mut done = false;
+        self.header.chunks_exact(CARD_SIZE).map_while(move |chunk| {
+            if done {
+                return None;
             }
-        } else {
-            None
+            let card_bytes: &[u8; CARD_SIZE] = chunk.try_into().ok()?;
+            done = &card_bytes[..8] == b"END     ";
+            Some(parse_card(card_bytes))
+        })
+    }
+
+    /// Looks up the value of the first card carrying `keyword`.
+    ///
+    /// Only cards with a matching keyword are parsed.
+    pub fn value(&self, keyword: &str) -> Result<Option<Value>> {
+        find_keyword_in_header(self.header, keyword)
+    }
+
+    /// Returns the EXTNAME of this HDU, if present.
+    pub fn extname(&self) -> Option<String> {
+        match self.value("EXTNAME") {
+            Ok(Some(Value::String(s))) => Some(s.trim().into()),
+            _ => None,
         }
-    })
+    }
+}
+
+fn card_integer_value(cards: &[Card], keyword: &str) -> Option<i64> {
+    match keyword_value(cards, keyword) {
+        Some(Value::Integer(n)) => Some(*n),
+        _ => None,
+    }
+}
+
+fn card_string_value(cards: &[Card], keyword: &str) -> Option<String> {
+    match keyword_value(cards, keyword) {
+        Some(Value::String(s)) => Some(s.trim().into()),
+        _ => None,
+    }
 }
 
 fn card_logical_value(cards: &[Card], keyword: &str) -> Option<bool> {
-    cards.iter().find_map(|c| {
-        if c.keyword_str() == keyword {
-            match &c.value {
-                Some(Value::Logical(b)) => Some(*b),
-                _ => None,
-            }
-        } else {
-            None
-        }
-    })
+    match keyword_value(cards, keyword) {
+        Some(Value::Logical(b)) => Some(*b),
+        _ => None,
+    }
 }
 
 fn is_primary_hdu(cards: &[Card]) -> bool {
@@ -184,8 +426,21 @@ fn is_primary_hdu(cards: &[Card]) -> bool {
 }
 
 fn compute_data_byte_len(cards: &[Card], is_primary: bool) -> Result<usize> {
-    let bitpix = card_integer_value(cards, "BITPIX").ok_or(Error::MissingKeyword("BITPIX"))?;
-    let naxis = card_integer_value(cards, "NAXIS").ok_or(Error::MissingKeyword("NAXIS"))?;
+    let groups = card_logical_value(cards, "GROUPS") == Some(true);
+    data_byte_len_from(|kw| card_integer_value(cards, kw), groups, is_primary)
+}
+
+/// Compute the data size from integer keyword values supplied by `int`.
+fn data_byte_len_from(
+    int: impl Fn(&str) -> Option<i64>,
+    groups: bool,
+    is_primary: bool,
+) -> Result<usize> {
+    let bitpix = int("BITPIX").ok_or(Error::MissingKeyword("BITPIX"))?;
+    if !VALID_BITPIX.contains(&bitpix) {
+        return Err(Error::InvalidBitpix(bitpix));
+    }
+    let naxis = int("NAXIS").ok_or(Error::MissingKeyword("NAXIS"))?;
     let naxis = naxis as usize;
 
     if naxis == 0 {
@@ -197,16 +452,14 @@ fn compute_data_byte_len(cards: &[Card], is_primary: bool) -> Result<usize> {
     let mut dims = Vec::with_capacity(naxis);
     for i in 1..=naxis {
         let kw = alloc::format!("NAXIS{}", i);
-        let dim = card_integer_value(cards, &kw).ok_or(Error::MissingKeyword("NAXISn"))? as usize;
+        let dim = int(&kw).ok_or(Error::MissingKeyword("NAXISn"))? as usize;
         dims.push(dim);
     }
 
     // Random groups: primary HDU with NAXIS1=0 and GROUPS=T
-    if is_primary && dims[0] == 0 && card_logical_value(cards, "GROUPS") == Some(true) {
-        let pcount =
-            card_integer_value(cards, "PCOUNT").ok_or(Error::MissingKeyword("PCOUNT"))? as usize;
-        let gcount =
-            card_integer_value(cards, "GCOUNT").ok_or(Error::MissingKeyword("GCOUNT"))? as usize;
+    if is_primary && dims[0] == 0 && groups {
+        let pcount = int("PCOUNT").ok_or(Error::MissingKeyword("PCOUNT"))? as usize;
+        let gcount = int("GCOUNT").ok_or(Error::MissingKeyword("GCOUNT"))? as usize;
 
         // Product of NAXIS2 * NAXIS3 * ... * NAXISm
         let mut product: usize = 1;
@@ -236,13 +489,13 @@ fn compute_data_byte_len(cards: &[Card], is_primary: bool) -> Result<usize> {
     let pcount = if is_primary {
         0
     } else {
-        card_integer_value(cards, "PCOUNT").unwrap_or(0) as usize
+        int("PCOUNT").unwrap_or(0) as usize
     };
 
     let gcount = if is_primary {
         1
     } else {
-        let g = card_integer_value(cards, "GCOUNT").unwrap_or(1) as usize;
+        let g = int("GCOUNT").unwrap_or(1) as usize;
         if g == 0 {
             1
         } else {
@@ -400,8 +653,230 @@ fn parse_hdu_info(cards: &[Card], is_primary: bool) -> Result<HduInfo> {
     }
 }
 
+/// Replace quoted `BSCALE`/`BZERO` values such as `'1.0'` with the number
+/// they contain, warning for each card converted.
+fn coerce_quoted_scaling(cards: &mut [Card], warnings: &mut Vec<ParseWarning>) {
+    for keyword in ["BSCALE", "BZERO"] {
+        for card in cards.iter_mut().filter(|c| c.keyword_str() == keyword) {
+            let parsed = match &card.value {
+                Some(Value::String(s)) => parse_float_str(s.trim()),
+                _ => None,
+            };
+            if let Some(value) = parsed {
+                card.value = Some(Value::Float(value));
+                warnings.push(ParseWarning::QuotedNumber {
+                    keyword: String::from(keyword),
+                    value,
+                });
+            }
+        }
+    }
+}
+
+/// Replace integral float values of `BITPIX`, `NAXIS` and `NAXISn` (e.g.
+/// `-32.0`) with integers, warning for each card converted.
+fn coerce_integral_axis_floats(cards: &mut [Card], warnings: &mut Vec<ParseWarning>) {
+    for card in cards.iter_mut() {
+        let keyword = card.keyword_str();
+        let is_axis_keyword = keyword == "BITPIX"
+            || keyword
+                .strip_prefix("NAXIS")
+                .is_some_and(|n| n.bytes().all(|b| b.is_ascii_digit()));
+        if !is_axis_keyword {
+            continue;
+        }
+        if let Some(Value::Float(f)) = card.value {
+            if f.is_finite() && libm::trunc(f) == f && f.abs() < i64::MAX as f64 {
+                let value = f as i64;
+                warnings.push(ParseWarning::IntegralFloat {
+                    keyword: String::from(keyword),
+                    value,
+                });
+                card.value = Some(Value::Integer(value));
+            }
+        }
+    }
+}
+
+/// Rewrite binary table TFORMn values that only parse after
+/// [`repair_tform`], warning for each card changed.
+fn repair_binary_tforms(cards: &mut [Card], warnings: &mut Vec<ParseWarning>) {
+    for card in cards.iter_mut() {
+        let keyword = card.keyword_str();
+        let is_tform = keyword
+            .strip_prefix("TFORM")
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()));
+        let Some(Value::String(text)) = &card.value else {
+            continue;
+        };
+        if !is_tform || repair_tform(text) == text.trim() {
+            continue;
+        }
+        let value = String::from(repair_tform(text));
+        warnings.push(ParseWarning::MalformedTform {
+            keyword: String::from(keyword),
+            value: value.clone(),
+        });
+        card.value = Some(Value::String(value));
+    }
+}
+
+/// Options controlling how [`parse_fits_with`] handles non-conforming input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Tolerate common header defects: `=` without a following space,
+    /// missing `PCOUNT`/`GCOUNT` in extensions (defaults are inserted),
+    /// `BSCALE`/`BZERO` written as quoted strings, integral floats such
+    /// as `BITPIX = -32.0`, a primary header with `SIMPLE = F`, stray
+    /// blocks between a data-less primary and the first extension, binary
+    /// table columns wider than NAXIS1, and TFORMn values with a `+` sign
+    /// or trailing punctuation (rewritten in the HDU's cards).
+    pub lenient: bool,
+    /// Encoding used to decode comment and commentary text.
+    pub text_encoding: TextEncoding,
+    /// Return the warnings raised while parsing. When `false` the warning
+    /// list is always empty.
+    pub collect_warnings: bool,
+    /// Require every HDU's data bytes to be present. When `false`, an HDU
+    /// whose data runs past the end of the input is kept and parsing stops
+    /// after it.
+    pub validate_data_length: bool,
+    /// Treat an END card as stray when the block after the header holds
+    /// further keyword cards rather than data, and keep reading the header
+    /// up to the next END. Stray END cards and blank cards are dropped.
+    pub continue_past_end: bool,
+    /// Honor the non-standard `BYTEORDER = 'LITTLE'` keyword written by some
+    /// tools, as a `HIERARCH` card or spelled `BYTEORDR`, marking the HDU as
+    /// [`Hdu::little_endian`] so image and binary table readers swap its data.
+    /// Variable-length arrays and tile-compressed images of such HDUs are
+    /// rejected rather than misread.
+    pub honor_byteorder: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            lenient: false,
+            text_encoding: TextEncoding::Utf8,
+            collect_warnings: true,
+            validate_data_length: true,
+            continue_past_end: false,
+            honor_byteorder: false,
+        }
+    }
+}
+
 /// Parse a complete FITS byte stream into a [`FitsData`] containing all HDUs.
 pub fn parse_fits(data: &[u8]) -> Result<FitsData> {
+    parse_fits_with(data, &ParseOptions::default()).map(|(fits, _)| fits)
+}
+
+/// Check whether `data` plausibly starts a FITS file.
+///
+/// The first card must be `SIMPLE  =` with the logical value `T`. A
+/// gzip-wrapped buffer (magic `1f 8b`) is accepted when the first card of
+/// its decompressed payload passes the same check; only that card is
+/// inflated. No other part of the header is inspected.
+pub fn is_fits(data: &[u8]) -> bool {
+    if data.starts_with(&[0x1f, 0x8b]) {
+        let Ok(payload) = crate::tiled::strip_gzip_header(data) else {
+            return false;
+        };
+        let head = match miniz_oxide::inflate::decompress_to_vec_with_limit(payload, CARD_SIZE) {
+            Ok(out) => out,
+            Err(err) => err.output,
+        };
+        return starts_with_simple_true(&head);
+    }
+    starts_with_simple_true(data)
+}
+
+/// Whether the header declares little-endian data with `BYTEORDER`.
+///
+/// The name is nine characters long, so it is only found as a `HIERARCH`
+/// card or in the eight-character spelling `BYTEORDR`.
+fn declares_little_endian(cards: &[Card]) -> bool {
+    let value = match crate::header::hierarch_value(cards, "BYTEORDER") {
+        Some(Value::String(s)) => Some(s),
+        _ => card_string_value(cards, "BYTEORDR"),
+    };
+    value.is_some_and(|s| s.trim().eq_ignore_ascii_case("LITTLE"))
+}
+
+/// Whether `card` is a `HIERARCH BYTEORDER` or `BYTEORDR` card.
+fn is_byteorder_card(card: &Card) -> bool {
+    card.keyword_str() == "BYTEORDR"
+        || (&card.keyword == b"HIERARCH"
+            && card
+                .comment
+                .as_deref()
+                .and_then(|text| text.split_once('='))
+                .is_some_and(|(name, _)| name.trim() == "BYTEORDER"))
+}
+
+/// Whether `data` opens with a keyword card carrying a value, other than
+/// the SIMPLE or XTENSION card that starts a new HDU.
+fn starts_with_value_card(data: &[u8]) -> bool {
+    let Some(first) = data.first_chunk::<CARD_SIZE>() else {
+        return false;
+    };
+    !first.starts_with(b"SIMPLE  ")
+        && !first.starts_with(b"XTENSION")
+        && matches!(parse_card(first), Ok(Card { value: Some(_), .. }))
+}
+
+/// Copy a header's cards up to its last END, leaving out earlier END cards
+/// and blank cards, and re-pad the result to whole blocks.
+fn join_past_stray_ends(header: &[u8]) -> Vec<u8> {
+    let is_end = |card: &[u8]| card.starts_with(b"END     ");
+    let cards: Vec<&[u8]> = header.chunks_exact(CARD_SIZE).collect();
+    let last_end = cards.iter().rposition(|c| is_end(c)).unwrap_or(cards.len());
+    let mut out = Vec::with_capacity(header.len());
+    for card in &cards[..last_end] {
+        if !is_end(card) && !card.iter().all(|&b| b == b' ' || b == 0) {
+            out.extend_from_slice(card);
+        }
+    }
+    out.extend_from_slice(&crate::header::format_end_card());
+    crate::block::pad_to_block(out, crate::block::HEADER_PAD_BYTE)
+}
+
+fn starts_with_xtension(data: &[u8]) -> bool {
+    let Some(first) = data.first_chunk::<CARD_SIZE>() else {
+        return false;
+    };
+    first.starts_with(b"XTENSION=")
+        && matches!(
+            parse_card(first),
+            Ok(Card {
+                value: Some(Value::String(_)),
+                ..
+            })
+        )
+}
+
+fn starts_with_simple_true(data: &[u8]) -> bool {
+    let Some(first) = data.first_chunk::<CARD_SIZE>() else {
+        return false;
+    };
+    first.starts_with(b"SIMPLE  =")
+        && matches!(
+            parse_card(first),
+            Ok(Card {
+                value: Some(Value::Logical(true)),
+                ..
+            })
+        )
+}
+
+/// Parse a complete FITS byte stream using the given [`ParseOptions`].
+///
+/// Returns the parsed HDUs together with any warnings raised for defects
+/// that were tolerated.
+pub fn parse_fits_with(
+    data: &[u8],
+    options: &ParseOptions,
+) -> Result<(FitsData, Vec<ParseWarning>)> {
     if data.is_empty() {
         return Err(Error::UnexpectedEof);
     }
@@ -410,6 +885,7 @@ pub fn parse_fits(data: &[u8]) -> Result<FitsData> {
     }
 
     let mut hdus = Vec::new();
+    let mut warnings = Vec::new();
     let mut offset: usize = 0;
 
     while offset < data.len() {
@@ -418,28 +894,75 @@ pub fn parse_fits(data: &[u8]) -> Result<FitsData> {
             break;
         }
 
-        let header_len = match header_byte_len(remaining) {
+        let mut header_len = match header_byte_len(remaining) {
             Ok(len) => len,
             Err(_) if !hdus.is_empty() => break,
             Err(e) => return Err(e),
         };
+        let mut stray_end = false;
+        if options.continue_past_end {
+            while starts_with_value_card(&remaining[header_len..]) {
+                match header_byte_len(&remaining[header_len..]) {
+                    Ok(more) => header_len += more,
+                    Err(_) => break,
+                }
+                stray_end = true;
+            }
+        }
         let header_data = &remaining[..header_len];
-        let cards = match parse_header_blocks(header_data) {
-            Ok(cards) => cards,
-            Err(_) if !hdus.is_empty() => break,
-            Err(e) => return Err(e),
+        let joined;
+        let card_data = if stray_end {
+            warnings.push(ParseWarning::StrayEnd { hdu: hdus.len() });
+            joined = join_past_stray_ends(header_data);
+            &joined[..]
+        } else {
+            header_data
         };
+        let mut cards =
+            match parse_header_blocks_inner(card_data, options.lenient, options.text_encoding) {
+                Ok(cards) => cards,
+                Err(_) if !hdus.is_empty() => break,
+                Err(e) => return Err(e),
+            };
 
         let is_primary = hdus.is_empty() && is_primary_hdu(&cards);
         if hdus.is_empty() && !is_primary {
             return Err(Error::InvalidHeader("first HDU must be primary"));
         }
+        if is_primary && card_logical_value(&cards, "SIMPLE") != Some(true) {
+            if !options.lenient {
+                return Err(Error::InvalidHeader("SIMPLE must be T"));
+            }
+            warnings.push(ParseWarning::NonConforming);
+        }
+        if options.lenient {
+            coerce_integral_axis_floats(&mut cards, &mut warnings);
+            if !is_primary {
+                insert_default_pcount_gcount(&mut cards, &mut warnings);
+            }
+            coerce_quoted_scaling(&mut cards, &mut warnings);
+        }
 
         let info = match parse_hdu_info(&cards, is_primary) {
             Ok(info) => info,
             Err(_) if !hdus.is_empty() => break,
             Err(e) => return Err(e),
         };
+        if options.lenient && matches!(info, HduInfo::BinaryTable { .. }) {
+            repair_binary_tforms(&mut cards, &mut warnings);
+        }
+        if !options.lenient {
+            if let HduInfo::BinaryTable {
+                naxis1, tfields, ..
+            } = &info
+            {
+                // Columns whose TFORMs cannot be parsed are reported when
+                // the table is read; here only their combined width matters.
+                if let Ok(columns) = parse_binary_table_columns(&cards, *tfields) {
+                    check_column_layout(&columns, *naxis1)?;
+                }
+            }
+        }
         let data_len = match compute_data_byte_len(&cards, is_primary) {
             Ok(len) => len,
             Err(_) if !hdus.is_empty() => break,
@@ -451,26 +974,350 @@ pub fn parse_fits(data: &[u8]) -> Result<FitsData> {
         // the trailing block padding to be missing.  Many real-world
         // files (HiPS tiles from Aladin/Hipsgen) omit trailing padding.
         if data_len > 0 && data_start + data_len > data.len() {
-            return Err(Error::UnexpectedEof);
+            if options.validate_data_length {
+                return Err(Error::UnexpectedEof);
+            }
+            warnings.push(ParseWarning::TruncatedData {
+                hdu: hdus.len(),
+                expected: data_len,
+                available: data.len().saturating_sub(data_start),
+            });
         }
 
+        let little_endian = options.honor_byteorder && declares_little_endian(&cards);
         hdus.push(Hdu {
             info,
             header_start: offset,
             data_start,
             data_len,
             cards,
+            header_pad_byte: detect_header_pad_byte(header_data),
+            little_endian,
         });
 
         let padded_data = padded_byte_len(data_len);
         offset = data_start + padded_data;
+
+        // Some writers leave stray bytes after a primary that declares no
+        // data. Skip whole blocks until one opens an extension header.
+        if options.lenient && is_primary && data_len == 0 {
+            let skip_start = offset;
+            while offset < data.len() && !starts_with_xtension(&data[offset..]) {
+                offset = (offset + BLOCK_SIZE).min(data.len());
+            }
+            if offset > skip_start {
+                warnings.push(ParseWarning::SkippedBytes {
+                    offset: skip_start,
+                    len: offset - skip_start,
+                });
+            }
+        }
     }
 
     if hdus.is_empty() {
         return Err(Error::InvalidHeader("no valid HDUs found"));
     }
 
-    Ok(FitsData { hdus })
+    if !options.collect_warnings {
+        warnings.clear();
+    }
+    Ok((FitsData { hdus }, warnings))
+}
+
+/// Parse as many HDU headers as are fully contained in `prefix`.
+///
+/// Intended for reading the start of a remote file (e.g. via an HTTP range
+/// request) before fetching data. Data offsets and lengths are computed from
+/// the headers but are not checked against `prefix.len()`.
+///
+/// The returned flag is `true` when parsing stopped because the prefix ended
+/// inside a header or before the start of the next one, meaning more bytes
+/// are needed to continue. A prefix that ends exactly on an HDU boundary is
+/// treated as complete.
+pub fn parse_headers_only(prefix: &[u8]) -> Result<(Vec<Hdu>, bool)> {
+    let mut hdus = Vec::new();
+    let mut offset: usize = 0;
+
+    loop {
+        if offset >= prefix.len() {
+            return Ok((hdus, offset > prefix.len()));
+        }
+        let remaining = &prefix[offset..];
+
+        let header_len = match header_byte_len(remaining) {
+            Ok(len) => len,
+            Err(Error::UnexpectedEof) => return Ok((hdus, true)),
+            Err(_) if !hdus.is_empty() => break,
+            Err(e) => return Err(e),
+        };
+        let header_data = &remaining[..header_len];
+        let cards = match parse_header_blocks(header_data) {
+            Ok(cards) => cards,
+            Err(_) if !hdus.is_empty() => break,
+            Err(e) => return Err(e),
+        };
+
+        let is_primary = hdus.is_empty() && is_primary_hdu(&cards);
+        if hdus.is_empty() && !is_primary {
+            return Err(Error::InvalidHeader("first HDU must be primary"));
+        }
+
+        let info = match parse_hdu_info(&cards, is_primary) {
+            Ok(info) => info,
+            Err(_) if !hdus.is_empty() => break,
+            Err(e) => return Err(e),
+        };
+        let data_len = match compute_data_byte_len(&cards, is_primary) {
+            Ok(len) => len,
+            Err(_) if !hdus.is_empty() => break,
+            Err(e) => return Err(e),
+        };
+        let data_start = offset + header_len;
+
+        hdus.push(Hdu {
+            info,
+            header_start: offset,
+            data_start,
+            data_len,
+            cards,
+            header_pad_byte: detect_header_pad_byte(header_data),
+            little_endian: false,
+        });
+
+        offset = data_start + padded_byte_len(data_len);
+    }
+
+    Ok((hdus, false))
+}
+
+/// Locate the HDUs of a FITS byte stream without collecting their cards.
+///
+/// Only the keywords that determine each data segment's size are parsed;
+/// use [`BorrowedHdu::cards_iter`] to read the rest of a header on demand.
+/// This suits read-only metadata scans over large multi-HDU files, where
+/// [`parse_fits`] would build a `Vec<Card>` for every header up front.
+///
+/// As with [`parse_fits`], the first HDU must be primary and its data must
+/// be present; a malformed later HDU ends the scan.
+pub fn parse_fits_borrowed(data: &[u8]) -> Result<Vec<BorrowedHdu<'_>>> {
+    let mut hdus = Vec::new();
+    let mut offset: usize = 0;
+
+    while data.len().saturating_sub(offset) >= BLOCK_SIZE {
+        let remaining = &data[offset..];
+        let header_len = match header_byte_len(remaining) {
+            Ok(len) => len,
+            Err(_) if !hdus.is_empty() => break,
+            Err(e) => return Err(e),
+        };
+        let header = &remaining[..header_len];
+
+        let is_primary = hdus.is_empty();
+        if is_primary && !header.starts_with(b"SIMPLE  =") {
+            return Err(Error::InvalidHeader("first HDU must be primary"));
+        }
+        let int = |kw: &str| match find_keyword_in_header(header, kw) {
+            Ok(Some(Value::Integer(n))) => Some(n),
+            _ => None,
+        };
+        let groups = matches!(
+            find_keyword_in_header(header, "GROUPS"),
+            Ok(Some(Value::Logical(true)))
+        );
+        let data_len = match data_byte_len_from(int, groups, is_primary) {
+            Ok(len) => len,
+            Err(_) if !hdus.is_empty() => break,
+            Err(e) => return Err(e),
+        };
+        let data_start = offset + header_len;
+        if data_len > 0 && data_start + data_len > data.len() {
+            return Err(Error::UnexpectedEof);
+        }
+
+        hdus.push(BorrowedHdu {
+            header,
+            header_start: offset,
+            data_start,
+            data_len,
+        });
+        offset = data_start + padded_byte_len(data_len);
+    }
+
+    if hdus.is_empty() {
+        return Err(Error::UnexpectedEof);
+    }
+    Ok(hdus)
+}
+
+/// Merge two FITS files: every HDU of `primary_file` followed by the
+/// extension HDUs of `extensions_from`.
+///
+/// Extension headers and data are copied byte for byte. The primary header
+/// of `primary_file` is kept verbatim when it already has `EXTEND = T`, and
+/// is otherwise re-serialized with `EXTEND = T` set (inserted after the
+/// `NAXISn` cards if absent). The primary HDU of `extensions_from` is
+/// dropped; it must have no data, since primary pixels cannot be carried
+/// over as an extension, and `Error::InvalidHeader` is returned otherwise.
+pub fn concat_fits(primary_file: &[u8], extensions_from: &[u8]) -> Result<Vec<u8>> {
+    let first = parse_fits(primary_file)?;
+    let second = parse_fits(extensions_from)?;
+
+    if second.hdus[0].data_len > 0 {
+        return Err(Error::InvalidHeader(
+            "second file's primary HDU has data and cannot become an extension",
+        ));
+    }
+
+    let mut out = Vec::with_capacity(primary_file.len() + extensions_from.len());
+    let primary = &first.hdus[0];
+    if keyword_value(&primary.cards, "EXTEND") == Some(&Value::Logical(true)) {
+        out.extend_from_slice(&primary_file[primary.header_start..primary.data_start]);
+    } else {
+        let mut cards = primary.cards.clone();
+        cards.retain(|c| !c.is_end());
+        set_extend_true(&mut cards);
+        out.extend_from_slice(&serialize_header_with_pad(&cards, primary.header_pad_byte)?);
+    }
+    push_hdu_data(&mut out, primary_file, primary);
+
+    for hdu in &first.hdus[1..] {
+        out.extend_from_slice(&primary_file[hdu.header_start..hdu.data_start]);
+        push_hdu_data(&mut out, primary_file, hdu);
+    }
+    for hdu in &second.hdus[1..] {
+        out.extend_from_slice(&extensions_from[hdu.header_start..hdu.data_start]);
+        push_hdu_data(&mut out, extensions_from, hdu);
+    }
+
+    Ok(out)
+}
+
+/// Set `EXTEND = T`, adding the card after the last `NAXISn` if missing.
+fn set_extend_true(cards: &mut Vec<Card>) {
+    if let Some(card) = cards.iter_mut().find(|c| c.keyword_str() == "EXTEND") {
+        card.value = Some(Value::Logical(true));
+        return;
+    }
+    let after_naxis = cards
+        .iter()
+        .rposition(|c| c.keyword_str().starts_with("NAXIS"))
+        .map_or(cards.len(), |i| i + 1);
+    let mut keyword = [b' '; 8];
+    keyword[..6].copy_from_slice(b"EXTEND");
+    cards.insert(
+        after_naxis,
+        Card {
+            keyword,
+            value: Some(Value::Logical(true)),
+            comment: None,
+        },
+    );
+}
+
+/// Rebuild a FITS file with the pixels of one image HDU replaced.
+///
+/// `hdu_index` selects a primary or IMAGE HDU (0 is the primary). Its
+/// header is kept with BITPIX set from the variant of `new_data`. With
+/// `keep_shape` the NAXISn keywords are left alone and `new_data` must hold
+/// exactly as many pixels as they describe, else
+/// `Error::DimensionMismatch`; without it NAXIS and NAXISn are rewritten
+/// from `new_data`'s shape. A header that needs no change is copied byte
+/// for byte. All other keywords, BSCALE/BZERO included, are kept as they
+/// are, and every other HDU is copied unchanged after the new data. The
+/// new pixels are always written big-endian, so a `BYTEORDER = 'LITTLE'`
+/// marker (see [`ParseOptions::honor_byteorder`]) is dropped.
+///
+/// [`ImageData::U32`](crate::image::ImageData::U32) needs a matching BZERO
+/// and is rejected with `Error::InvalidValue`, as is an out-of-range index.
+pub fn replace_hdu_data(
+    fits_data: &[u8],
+    hdu_index: usize,
+    new_data: &crate::image::ShapedImage,
+    keep_shape: bool,
+) -> Result<Vec<u8>> {
+    let fits = parse_fits(fits_data)?;
+    let target = fits.get(hdu_index).ok_or(Error::InvalidValue)?;
+    let (old_bitpix, old_naxes) = match &target.info {
+        HduInfo::Primary { bitpix, naxes } | HduInfo::Image { bitpix, naxes } => (*bitpix, naxes),
+        _ => return Err(Error::InvalidHeader("not an image HDU")),
+    };
+    if matches!(**new_data, crate::image::ImageData::U32(_)) {
+        return Err(Error::InvalidValue);
+    }
+    let bitpix = new_data.bitpix();
+    let reshape = !keep_shape && new_data.naxes() != old_naxes.as_slice();
+    if keep_shape {
+        let expected = crate::image::checked_pixel_count(old_naxes)?;
+        let got = crate::image::checked_pixel_count(new_data.naxes())?;
+        if expected != got {
+            return Err(Error::DimensionMismatch { expected, got });
+        }
+    }
+
+    let mut out = Vec::with_capacity(fits_data.len());
+    for (i, hdu) in fits.hdus.iter().enumerate() {
+        if i != hdu_index {
+            out.extend_from_slice(&fits_data[hdu.header_start..hdu.data_start]);
+            push_hdu_data(&mut out, fits_data, hdu);
+            continue;
+        }
+        let little_endian = declares_little_endian(&hdu.cards);
+        if bitpix == old_bitpix && !reshape && !little_endian {
+            out.extend_from_slice(&fits_data[hdu.header_start..hdu.data_start]);
+        } else {
+            let mut cards =
+                image_cards_with_layout(&hdu.cards, bitpix, reshape.then(|| new_data.naxes()));
+            if little_endian {
+                cards.retain(|c| !is_byteorder_card(c));
+            }
+            out.extend_from_slice(&serialize_header_with_pad(&cards, hdu.header_pad_byte)?);
+        }
+        out.extend_from_slice(&crate::image::serialize_image(new_data));
+    }
+    Ok(out)
+}
+
+/// `cards` without END, with BITPIX set to `bitpix` and, when `naxes` is
+/// given, NAXIS and NAXISn replaced to describe it.
+fn image_cards_with_layout(cards: &[Card], bitpix: i64, naxes: Option<&[usize]>) -> Vec<Card> {
+    let keyword_card = |name: &str, value: i64| {
+        let mut keyword = [b' '; 8];
+        keyword[..name.len()].copy_from_slice(name.as_bytes());
+        Card {
+            keyword,
+            value: Some(Value::Integer(value)),
+            comment: None,
+        }
+    };
+    let is_axis_card = |card: &Card| {
+        card.keyword_str()
+            .strip_prefix("NAXIS")
+            .is_some_and(|n| n.bytes().all(|b| b.is_ascii_digit()))
+    };
+
+    let mut out = Vec::with_capacity(cards.len());
+    for card in cards.iter().filter(|c| !c.is_end()) {
+        if card.keyword_str() == "BITPIX" {
+            out.push(keyword_card("BITPIX", bitpix));
+            if let Some(naxes) = naxes {
+                out.push(keyword_card("NAXIS", naxes.len() as i64));
+                for (i, &n) in naxes.iter().enumerate() {
+                    out.push(keyword_card(&alloc::format!("NAXIS{}", i + 1), n as i64));
+                }
+            }
+        } else if naxes.is_none() || !is_axis_card(card) {
+            out.push(card.clone());
+        }
+    }
+    out
+}
+
+/// Append an HDU's data segment, zero-filling any padding the source lacks.
+fn push_hdu_data(out: &mut Vec<u8>, data: &[u8], hdu: &Hdu) {
+    let padded = padded_byte_len(hdu.data_len);
+    let end = (hdu.data_start + padded).min(data.len());
+    out.extend_from_slice(&data[hdu.data_start..end]);
+    out.resize(out.len() + padded - (end - hdu.data_start), 0);
 }
 
 #[cfg(test)]
@@ -590,6 +1437,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_rejects_illegal_bitpix() {
+        let data = build_fits_bytes(&primary_header_image(7, &[2, 2]), 4);
+        assert!(matches!(parse_fits(&data), Err(Error::InvalidBitpix(7))));
+        assert!(matches!(
+            parse_headers_only(&data),
+            Err(Error::InvalidBitpix(7))
+        ));
+    }
+
     #[test]
     fn parse_primary_with_image_data() {
         let dims = [100, 200];
@@ -672,6 +1529,192 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_primary_image_followed_by_bintable() {
+        let mut primary_cards = primary_header_image(16, &[10, 10]);
+        primary_cards.push(card("EXTEND", Value::Logical(true)));
+        let mut data = serialize_header(&primary_cards).unwrap();
+        for i in 0..100i16 {
+            data.extend_from_slice(&(i * 3 - 50).to_be_bytes());
+        }
+        data.resize(padded_byte_len(data.len()), 0);
+        let table_start = data.len();
+        data.extend(build_fits_bytes(
+            &bintable_extension_header(4, 5, 0, 0, Some("EVENTS")),
+            20,
+        ));
+
+        let fits = parse_fits(&data).unwrap();
+        assert_eq!(fits.len(), 2);
+        let primary = fits.primary();
+        assert_eq!(primary.data_len, 200);
+        assert_eq!(primary.data_start, BLOCK_SIZE);
+
+        let table = fits.get(1).unwrap();
+        assert_eq!(table.header_start, table_start);
+        assert_eq!(table.header_start, 2 * BLOCK_SIZE);
+        assert_eq!(table.extname().as_deref(), Some("EVENTS"));
+        assert!(matches!(
+            table.info,
+            HduInfo::BinaryTable {
+                naxis1: 4,
+                naxis2: 5,
+                ..
+            }
+        ));
+
+        match crate::image::read_image_data(&data, primary).unwrap() {
+            crate::image::ImageData::I16(pixels) => {
+                assert_eq!(pixels.len(), 100);
+                assert_eq!(pixels[0], -50);
+                assert_eq!(pixels[99], 247);
+            }
+            other => panic!("Expected I16, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn toc_lists_every_hdu() {
+        let mut primary_cards = primary_header_naxis0();
+        primary_cards.push(card("EXTEND", Value::Logical(true)));
+        let mut sci2 = image_extension_header(-32, &[8, 4], Some("SCI"));
+        sci2.push(card("EXTVER", Value::Integer(2)));
+        let mut data = build_fits_bytes(&primary_cards, 0);
+        data.extend(build_fits_bytes(
+            &image_extension_header(-32, &[8, 4], Some("SCI")),
+            8 * 4 * 4,
+        ));
+        data.extend(build_fits_bytes(&sci2, 8 * 4 * 4));
+        data.extend(build_fits_bytes(
+            &bintable_extension_header(12, 3, 0, 0, Some("EVENTS")),
+            36,
+        ));
+
+        let toc = parse_fits(&data).unwrap().toc();
+        assert_eq!(toc.len(), 4);
+        assert_eq!(
+            toc[0],
+            HduEntry {
+                index: 0,
+                kind: "PRIMARY",
+                name: None,
+                ver: 1,
+                shape: vec![],
+                rows: None,
+            }
+        );
+        assert_eq!(
+            (toc[1].kind, toc[1].name.as_deref(), toc[1].ver),
+            ("IMAGE", Some("SCI"), 1)
+        );
+        assert_eq!(toc[1].shape, [8, 4]);
+        assert_eq!((toc[2].index, toc[2].ver), (2, 2));
+        assert_eq!(
+            toc[3],
+            HduEntry {
+                index: 3,
+                kind: "BINTABLE",
+                name: Some(String::from("EVENTS")),
+                ver: 1,
+                shape: vec![12, 3],
+                rows: Some(3),
+            }
+        );
+    }
+
+    #[test]
+    fn block_span_counts_header_and_data_blocks() {
+        let mut data = build_fits_bytes(&primary_header_image(8, &[100]), 100);
+        let mut ext_cards = image_extension_header(16, &[40, 40], None);
+        for i in 0..36 {
+            ext_cards.push(card(&alloc::format!("KEY{i}"), Value::Integer(i)));
+        }
+        data.extend(build_fits_bytes(&ext_cards, 40 * 40 * 2));
+
+        let fits = parse_fits(&data).unwrap();
+        assert_eq!(fits.primary().block_span(), (1, 1));
+        assert_eq!(fits.get(1).unwrap().block_span(), (2, 2));
+        assert_eq!(fits.total_blocks(), 6);
+        assert_eq!(fits.total_blocks() * BLOCK_SIZE, data.len());
+    }
+
+    fn bintable_with_tforms(naxis1: usize, tforms: &[&str]) -> Vec<u8> {
+        let mut primary_cards = primary_header_naxis0();
+        primary_cards.push(card("EXTEND", Value::Logical(true)));
+        let mut cards = bintable_extension_header(naxis1, 2, 0, tforms.len(), None);
+        for (i, tform) in tforms.iter().enumerate() {
+            let kw = alloc::format!("TFORM{}", i + 1);
+            cards.push(card(&kw, Value::String(String::from(*tform))));
+        }
+        let mut data = build_fits_bytes(&primary_cards, 0);
+        data.extend(build_fits_bytes(&cards, naxis1 * 2));
+        data
+    }
+
+    #[test]
+    fn bintable_columns_matching_naxis1() {
+        let data = bintable_with_tforms(14, &["1J", "1D", "2A"]);
+        assert_eq!(parse_fits(&data).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn bintable_trailing_filler_is_allowed() {
+        let data = bintable_with_tforms(16, &["1J", "1D"]);
+        assert_eq!(parse_fits(&data).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn bintable_columns_overflowing_naxis1() {
+        let data = bintable_with_tforms(10, &["1J", "1D"]);
+        assert!(matches!(
+            parse_fits(&data),
+            Err(Error::ColumnLayoutMismatch {
+                expected_naxis1: 10,
+                got: 12,
+            })
+        ));
+
+        let options = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        assert_eq!(parse_fits_with(&data, &options).unwrap().0.len(), 2);
+    }
+
+    #[test]
+    fn lenient_repairs_malformed_tforms() {
+        let data = bintable_with_tforms(12, &["+1J", "1D"]);
+        let fits = parse_fits(&data).unwrap();
+        let table = fits.get(1).unwrap();
+        assert_eq!(
+            crate::bintable::read_binary_column(&data, table, 0).unwrap(),
+            crate::bintable::BinaryColumnData::Int(vec![0, 0])
+        );
+
+        let data = bintable_with_tforms(12, &["+1J", "1D."]);
+        let fits = parse_fits(&data).unwrap();
+        let table = fits.get(1).unwrap();
+        assert!(crate::bintable::read_binary_column(&data, table, 0).is_err());
+
+        let options = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        let (fits, warnings) = parse_fits_with(&data, &options).unwrap();
+        assert_eq!(
+            warnings,
+            vec![ParseWarning::MalformedTform {
+                keyword: String::from("TFORM2"),
+                value: String::from("1D"),
+            }]
+        );
+        let table = fits.get(1).unwrap();
+        assert_eq!(
+            crate::bintable::read_binary_column(&data, table, 1).unwrap(),
+            crate::bintable::BinaryColumnData::Double(vec![0.0, 0.0])
+        );
+    }
+
     #[test]
     fn find_by_name_lookup() {
         let primary_cards = primary_header_naxis0();
@@ -717,6 +1760,48 @@ mod tests {
         assert!(fits.find_by_name("MISSING").is_none());
     }
 
+    #[test]
+    fn extname_extver_extlevel_accessors() {
+        let mut sci1 = image_extension_header(16, &[4], Some("SCI"));
+        sci1.push(card("EXTVER", Value::Integer(1)));
+        let mut dq1 = image_extension_header(16, &[4], Some("DQ"));
+        dq1.push(card("EXTVER", Value::Integer(1)));
+        dq1.push(card("EXTLEVEL", Value::Integer(2)));
+        let mut sci2 = image_extension_header(16, &[4], Some("SCI"));
+        sci2.push(card("EXTVER", Value::Integer(2)));
+        let mut dq2 = image_extension_header(16, &[4], Some("DQ"));
+        dq2.push(card("EXTVER", Value::Integer(2)));
+        dq2.push(card("EXTLEVEL", Value::Integer(2)));
+
+        let mut data = build_fits_bytes(&primary_header_naxis0(), 0);
+        for cards in [&sci1, &dq1, &sci2, &dq2] {
+            data.extend_from_slice(&build_fits_bytes(cards, 8));
+        }
+
+        let fits = parse_fits(&data).unwrap();
+        assert_eq!(fits.len(), 5);
+
+        let primary = fits.primary();
+        assert_eq!(primary.extname(), None);
+        assert_eq!(primary.extver(), 1);
+        assert_eq!(primary.extlevel(), 1);
+
+        let summary: Vec<(Option<String>, i64, i64)> = fits
+            .iter()
+            .skip(1)
+            .map(|h| (h.extname(), h.extver(), h.extlevel()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some(String::from("SCI")), 1, 1),
+                (Some(String::from("DQ")), 1, 2),
+                (Some(String::from("SCI")), 2, 1),
+                (Some(String::from("DQ")), 2, 2),
+            ]
+        );
+    }
+
     #[test]
     fn correct_byte_offsets() {
         let primary_cards = primary_header_image(8, &[100]);
@@ -952,6 +2037,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn zimage_bintable_is_compressed_image() {
+        let mut cards = bintable_extension_header(8, 2, 0, 1, Some("COMPRESSED_IMAGE"));
+        cards.push(card(
+            "TTYPE1",
+            Value::String(String::from("COMPRESSED_DATA")),
+        ));
+        cards.push(card("TFORM1", Value::String(String::from("1PB"))));
+        cards.push(card("ZIMAGE", Value::Logical(true)));
+        cards.push(card("ZBITPIX", Value::Integer(16)));
+        cards.push(card("ZNAXIS", Value::Integer(2)));
+        cards.push(card("ZNAXIS1", Value::Integer(3)));
+        cards.push(card("ZNAXIS2", Value::Integer(2)));
+        cards.push(card("ZCMPTYPE", Value::String(String::from("RICE_1"))));
+        let without_ztension = cards.clone();
+        cards.push(card("ZTENSION", Value::String(String::from("IMAGE"))));
+
+        for header in [&without_ztension, &cards] {
+            let mut data = build_fits_bytes(&primary_header_naxis0(), 0);
+            data.extend(build_fits_bytes(header, 16));
+            let fits = parse_fits(&data).unwrap();
+            let hdu = fits.get(1).unwrap();
+            match &hdu.info {
+                HduInfo::CompressedImage {
+                    zbitpix,
+                    znaxes,
+                    ztile,
+                    naxis2,
+                    tfields,
+                    ..
+                } => {
+                    assert_eq!(*zbitpix, 16);
+                    assert_eq!(znaxes, &[3, 2]);
+                    assert_eq!(ztile, &[3, 1]);
+                    assert_eq!((*naxis2, *tfields), (2, 1));
+                }
+                other => panic!("Expected CompressedImage, got {:?}", other),
+            }
+            assert_eq!(
+                crate::bintable::read_binary_column_vla(&data, hdu, 0).unwrap(),
+                crate::bintable::BinaryColumnData::VarByte(vec![vec![], vec![]])
+            );
+        }
+    }
+
+    #[test]
+    fn random_groups_are_not_images() {
+        let cards = random_groups_header(-32, &[0, 3, 4, 1, 1, 1], 6, 2);
+        let data = build_fits_bytes(&cards, 144);
+        let fits = parse_fits(&data).unwrap();
+
+        assert!(fits.random_groups().is_some());
+        assert_eq!(fits.images(&data).count(), 0);
+        assert!(fits.read_primary_image(&data).is_err());
+        assert!(crate::image::image_dimensions(fits.primary()).is_err());
+
+        let image = build_fits_bytes(&primary_header_image(8, &[2, 2]), 4);
+        assert!(parse_fits(&image).unwrap().random_groups().is_none());
+    }
+
     #[test]
     fn random_groups_data_length() {
         let cards = random_groups_header(-32, &[0, 3, 4, 1, 1, 1], 6, 2);
@@ -959,6 +2104,7 @@ mod tests {
         assert_eq!(len, 144);
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn parse_random_groups_real_file() {
         let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
@@ -1009,4 +2155,608 @@ mod tests {
             other => panic!("Expected Primary, got {:?}", other),
         }
     }
+
+    #[test]
+    fn parse_headers_only_truncated_prefix() {
+        let mut data = build_fits_bytes(&primary_header_image(16, &[100, 100]), 20000);
+        let ext_start = data.len();
+        data.extend_from_slice(&build_fits_bytes(
+            &image_extension_header(-32, &[10], Some("SCI")),
+            40,
+        ));
+
+        // Only the primary header block is available.
+        let (hdus, needs_more) = parse_headers_only(&data[..BLOCK_SIZE]).unwrap();
+        assert!(needs_more);
+        assert_eq!(hdus.len(), 1);
+        assert_eq!(hdus[0].data_start, BLOCK_SIZE);
+        assert_eq!(hdus[0].data_len, 20000);
+
+        // Prefix cut in the middle of the extension header.
+        let (hdus, needs_more) = parse_headers_only(&data[..ext_start + 100]).unwrap();
+        assert!(needs_more);
+        assert_eq!(hdus.len(), 1);
+
+        // Extension header available, its data is not.
+        let (hdus, needs_more) = parse_headers_only(&data[..ext_start + BLOCK_SIZE]).unwrap();
+        assert!(needs_more);
+        assert_eq!(hdus.len(), 2);
+        assert_eq!(hdus[1].extname().as_deref(), Some("SCI"));
+        assert_eq!(hdus[1].header_start, ext_start);
+        assert_eq!(hdus[1].data_len, 40);
+
+        // The whole file needs nothing more.
+        let (hdus, needs_more) = parse_headers_only(&data).unwrap();
+        assert!(!needs_more);
+        assert_eq!(hdus.len(), 2);
+    }
+
+    #[test]
+    fn parse_headers_only_partial_primary() {
+        let data = build_fits_bytes(&primary_header_naxis0(), 0);
+        let (hdus, needs_more) = parse_headers_only(&data[..1000]).unwrap();
+        assert!(hdus.is_empty());
+        assert!(needs_more);
+    }
+
+    #[test]
+    fn read_primary_image_and_physical() {
+        let mut cards = primary_header_image(16, &[3]);
+        cards.push(card("BZERO", Value::Integer(32768)));
+        let mut data = build_fits_bytes(&cards, 6);
+        let start = BLOCK_SIZE;
+        data[start..start + 6].copy_from_slice(&[0x80, 0x00, 0x00, 0x00, 0x7F, 0xFF]);
+
+        let fits = parse_fits(&data).unwrap();
+        let image = fits.read_primary_image(&data).unwrap();
+        assert_eq!(
+            image,
+            crate::image::ImageData::I16(vec![i16::MIN, 0, i16::MAX])
+        );
+        let physical = fits.read_primary_physical(&data).unwrap();
+        assert_eq!(physical, vec![0.0, 32768.0, 65535.0]);
+    }
+
+    #[test]
+    fn read_primary_image_without_data_errors() {
+        let data = build_fits_bytes(&primary_header_naxis0(), 0);
+        let fits = parse_fits(&data).unwrap();
+        assert!(matches!(
+            fits.read_primary_image(&data),
+            Err(Error::InvalidHeader(_))
+        ));
+        assert!(fits.read_primary_physical(&data).is_err());
+    }
+
+    // -- parse_fits_with --
+
+    /// Overwrite the card at `index` (0-based, from `header_start`) with `text`.
+    fn put_card(data: &mut [u8], header_start: usize, index: usize, text: &[u8]) {
+        let start = header_start + index * 80;
+        data[start..start + 80].fill(b' ');
+        data[start..start + text.len()].copy_from_slice(text);
+    }
+
+    /// Primary NAXIS=0 plus an image extension with PCOUNT/GCOUNT blanked out.
+    fn fits_missing_pcount_gcount() -> (Vec<u8>, usize) {
+        let mut data = build_fits_bytes(&primary_header_naxis0(), 0);
+        let ext_start = data.len();
+        data.extend_from_slice(&build_fits_bytes(
+            &image_extension_header(8, &[4], Some("SCI")),
+            4,
+        ));
+        // Cards: XTENSION, BITPIX, NAXIS, NAXIS1, PCOUNT, GCOUNT, EXTNAME.
+        put_card(&mut data, ext_start, 4, b"");
+        put_card(&mut data, ext_start, 5, b"FOO     =42");
+        (data, ext_start)
+    }
+
+    #[test]
+    fn parse_fits_with_lenient_flag() {
+        let (data, _) = fits_missing_pcount_gcount();
+
+        let (strict, warnings) = parse_fits_with(&data, &ParseOptions::default()).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(keyword_value(&strict.hdus[1].cards, "FOO"), None);
+
+        let options = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        let (fits, warnings) = parse_fits_with(&data, &options).unwrap();
+        let cards = &fits.hdus[1].cards;
+        assert_eq!(keyword_value(cards, "FOO"), Some(&Value::Integer(42)));
+        assert_eq!(keyword_value(cards, "PCOUNT"), Some(&Value::Integer(0)));
+        assert_eq!(keyword_value(cards, "GCOUNT"), Some(&Value::Integer(1)));
+        assert_eq!(warnings.len(), 2);
+        assert!(matches!(
+            &warnings[0],
+            ParseWarning::DefaultedKeyword { keyword, .. } if keyword == "PCOUNT"
+        ));
+    }
+
+    #[test]
+    fn parse_fits_with_collect_warnings_flag() {
+        let (data, _) = fits_missing_pcount_gcount();
+        let options = ParseOptions {
+            lenient: true,
+            collect_warnings: false,
+            ..ParseOptions::default()
+        };
+        let (fits, warnings) = parse_fits_with(&data, &options).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(
+            keyword_value(&fits.hdus[1].cards, "PCOUNT"),
+            Some(&Value::Integer(0))
+        );
+    }
+
+    #[test]
+    fn parse_fits_with_text_encoding_flag() {
+        let mut cards = primary_header_naxis0();
+        cards.push(card("OBJECT", Value::String(String::from("M31"))));
+        let mut data = build_fits_bytes(&cards, 0);
+        put_card(&mut data, 0, 3, b"COMMENT rotated 90\xb0");
+
+        assert!(matches!(
+            parse_fits(&data),
+            Err(Error::InvalidHeader("non-UTF8 card data"))
+        ));
+
+        let options = ParseOptions {
+            text_encoding: TextEncoding::Latin1,
+            ..ParseOptions::default()
+        };
+        let (fits, _) = parse_fits_with(&data, &options).unwrap();
+        let comment = fits.primary().cards[3].comment.as_deref();
+        assert_eq!(comment, Some("rotated 90\u{b0}"));
+    }
+
+    #[test]
+    fn lenient_parses_quoted_bscale_bzero() {
+        let mut cards = primary_header_image(16, &[2]);
+        cards.push(card("BSCALE", Value::String(String::from("2.0"))));
+        cards.push(card("BZERO", Value::String(String::from("10"))));
+        let mut data = build_fits_bytes(&cards, 4);
+        data[BLOCK_SIZE..BLOCK_SIZE + 4].copy_from_slice(&[0, 1, 0, 5]);
+
+        let strict = parse_fits(&data).unwrap();
+        assert_eq!(strict.read_primary_physical(&data).unwrap(), vec![1.0, 5.0]);
+
+        let options = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        let (fits, warnings) = parse_fits_with(&data, &options).unwrap();
+        assert_eq!(fits.read_primary_physical(&data).unwrap(), vec![12.0, 20.0]);
+        assert_eq!(
+            warnings,
+            vec![
+                ParseWarning::QuotedNumber {
+                    keyword: String::from("BSCALE"),
+                    value: 2.0
+                },
+                ParseWarning::QuotedNumber {
+                    keyword: String::from("BZERO"),
+                    value: 10.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lenient_accepts_integral_float_bitpix() {
+        let cards = vec![
+            card("SIMPLE", Value::Logical(true)),
+            card("BITPIX", Value::Float(-32.0)),
+            card("NAXIS", Value::Integer(1)),
+            card("NAXIS1", Value::Float(2.0)),
+        ];
+        let mut data = build_fits_bytes(&cards, 8);
+        data[BLOCK_SIZE..BLOCK_SIZE + 4].copy_from_slice(&1.5f32.to_be_bytes());
+        data[BLOCK_SIZE + 4..BLOCK_SIZE + 8].copy_from_slice(&(-2.0f32).to_be_bytes());
+
+        assert!(matches!(
+            parse_fits(&data),
+            Err(Error::MissingKeyword("BITPIX"))
+        ));
+
+        let options = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        let (fits, warnings) = parse_fits_with(&data, &options).unwrap();
+        assert_eq!(
+            fits.read_primary_image(&data).unwrap(),
+            crate::image::ImageData::F32(vec![1.5, -2.0])
+        );
+        assert_eq!(
+            warnings,
+            vec![
+                ParseWarning::IntegralFloat {
+                    keyword: String::from("BITPIX"),
+                    value: -32
+                },
+                ParseWarning::IntegralFloat {
+                    keyword: String::from("NAXIS1"),
+                    value: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lenient_accepts_simple_false() {
+        let mut data = build_fits_bytes(&primary_header_image(8, &[2, 2]), 4);
+        assert_eq!(data[29], b'T');
+        data[29] = b'F';
+        data[BLOCK_SIZE..BLOCK_SIZE + 4].copy_from_slice(&[1, 2, 3, 4]);
+
+        assert!(matches!(parse_fits(&data), Err(Error::InvalidHeader(_))));
+
+        let options = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        let (fits, warnings) = parse_fits_with(&data, &options).unwrap();
+        assert_eq!(fits.primary().cards[0].value, Some(Value::Logical(false)));
+        assert_eq!(
+            fits.read_primary_image(&data).unwrap(),
+            crate::image::ImageData::U8(vec![1, 2, 3, 4])
+        );
+        assert_eq!(warnings, vec![ParseWarning::NonConforming]);
+    }
+
+    #[test]
+    fn is_fits_sniffs_plain_and_gzip_buffers() {
+        let data = build_fits_bytes(&primary_header_image(8, &[2, 2]), 4);
+        assert!(is_fits(&data));
+        assert!(is_fits(&data[..CARD_SIZE]));
+        assert!(!is_fits(&data[..CARD_SIZE - 1]));
+
+        let mut not_simple = data.clone();
+        not_simple[29] = b'F';
+        assert!(!is_fits(&not_simple));
+
+        let noise: Vec<u8> = (0..BLOCK_SIZE as u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 24) as u8)
+            .collect();
+        assert!(!is_fits(&noise));
+
+        let mut gz = vec![0x1f, 0x8b, 0x08, 0, 0, 0, 0, 0, 0, 0xff];
+        gz.extend_from_slice(&miniz_oxide::deflate::compress_to_vec(&data, 6));
+        gz.extend_from_slice(&[0, 0, 0, 0]);
+        gz.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        assert!(is_fits(&gz));
+        assert!(!is_fits(&gz[..12]));
+    }
+
+    #[test]
+    fn borrowed_hdus_parse_cards_on_demand() {
+        let mut data = build_fits_bytes(&primary_header_image(8, &[2, 2]), 4);
+        let mut sci = image_extension_header(16, &[10, 10], Some("SCI"));
+        sci.push(card("OBSERVER", Value::String(String::from("Hubble"))));
+        let sci_start = data.len();
+        data.extend(build_fits_bytes(&sci, 200));
+        data.extend(build_fits_bytes(
+            &bintable_extension_header(8, 3, 0, 0, Some("EVENTS")),
+            24,
+        ));
+
+        // Corrupt a card that plays no part in locating the data.
+        let observer = sci_start + 8 * CARD_SIZE;
+        data[observer..observer + 8].copy_from_slice(b"obs?rver");
+
+        let hdus = parse_fits_borrowed(&data).unwrap();
+        assert_eq!(hdus.len(), 3);
+        assert_eq!(hdus[1].header_start, sci_start);
+        assert_eq!(hdus[1].data_len, 200);
+        assert_eq!(hdus[2].data_start, sci_start + 3 * BLOCK_SIZE);
+        assert_eq!(hdus[2].data_len, 24);
+        assert_eq!(hdus[1].extname().as_deref(), Some("SCI"));
+        assert_eq!(hdus[2].value("NAXIS2").unwrap(), Some(Value::Integer(3)));
+
+        // Cards are only parsed once they are iterated.
+        assert!(hdus[1].cards_iter().any(|c| c.is_err()));
+        let fits = parse_fits(&data[..sci_start]).unwrap();
+        let cards: Vec<Card> = hdus[0].cards_iter().collect::<Result<_>>().unwrap();
+        assert_eq!(cards, fits.primary().cards);
+    }
+
+    #[test]
+    fn null_header_padding_is_detected_and_reproduced() {
+        let cards = primary_header_image(8, &[2, 2]);
+        let mut data = build_fits_bytes(&cards, 4);
+        assert_eq!(parse_fits(&data).unwrap().primary().header_pad_byte(), b' ');
+
+        let pad_start = (cards.len() + 1) * CARD_SIZE;
+        data[pad_start..BLOCK_SIZE].fill(0);
+        let fits = parse_fits(&data).unwrap();
+        let primary = fits.primary();
+        assert_eq!(primary.header_pad_byte(), 0);
+
+        let without_end: Vec<Card> = primary
+            .cards
+            .iter()
+            .filter(|c| !c.is_end())
+            .cloned()
+            .collect();
+        let header =
+            crate::header::serialize_header_with_pad(&without_end, primary.header_pad_byte)
+                .unwrap();
+        assert_eq!(header, &data[..BLOCK_SIZE]);
+    }
+
+    #[test]
+    fn parse_fits_with_validate_data_length_flag() {
+        let data = build_fits_bytes(&primary_header_image(16, &[100, 100]), 20000);
+        let truncated = &data[..BLOCK_SIZE * 2];
+        assert!(matches!(parse_fits(truncated), Err(Error::UnexpectedEof)));
+
+        let options = ParseOptions {
+            validate_data_length: false,
+            ..ParseOptions::default()
+        };
+        let (fits, warnings) = parse_fits_with(truncated, &options).unwrap();
+        assert_eq!(fits.len(), 1);
+        assert_eq!(fits.primary().data_len, 20000);
+        assert_eq!(
+            warnings,
+            vec![ParseWarning::TruncatedData {
+                hdu: 0,
+                expected: 20000,
+                available: BLOCK_SIZE,
+            }]
+        );
+    }
+
+    #[test]
+    fn lenient_skips_stray_bytes_after_dataless_primary() {
+        let mut primary_cards = primary_header_naxis0();
+        primary_cards.push(card("EXTEND", Value::Logical(true)));
+        let mut data = build_fits_bytes(&primary_cards, 0);
+        data.extend(core::iter::repeat_n(b'x', 2 * BLOCK_SIZE));
+        data.extend(build_fits_bytes(
+            &image_extension_header(8, &[4], Some("SCI")),
+            4,
+        ));
+
+        // Strict parsing stops at the junk and loses the extension.
+        assert_eq!(parse_fits(&data).unwrap().len(), 1);
+
+        let options = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        let (fits, warnings) = parse_fits_with(&data, &options).unwrap();
+        assert_eq!(fits.len(), 2);
+        assert_eq!(fits.get(1).unwrap().extname().as_deref(), Some("SCI"));
+        assert_eq!(fits.get(1).unwrap().header_start, 3 * BLOCK_SIZE);
+        assert_eq!(
+            warnings,
+            vec![ParseWarning::SkippedBytes {
+                offset: BLOCK_SIZE,
+                len: 2 * BLOCK_SIZE,
+            }]
+        );
+    }
+
+    #[test]
+    fn replace_hdu_data_rewrites_pixels_and_layout() {
+        use crate::image::{read_image_data, ImageData};
+
+        let mut primary_cards = primary_header_naxis0();
+        primary_cards.push(card("EXTEND", Value::Logical(true)));
+        let mut ext_cards = image_extension_header(16, &[4, 2], Some("SCI"));
+        ext_cards.push(card("BZERO", Value::Float(0.0)));
+        let mut data = build_fits_bytes(&primary_cards, 0);
+        data.extend(build_fits_bytes(&ext_cards, 16));
+        data.extend(build_fits_bytes(
+            &bintable_extension_header(4, 1, 0, 0, Some("EVENTS")),
+            4,
+        ));
+
+        // Same type and shape: header bytes survive untouched.
+        let pixels = ImageData::I16((0..8).collect())
+            .with_shape(&[4, 2])
+            .unwrap();
+        let out = replace_hdu_data(&data, 1, &pixels, true).unwrap();
+        assert_eq!(out[..2 * BLOCK_SIZE], data[..2 * BLOCK_SIZE]);
+        let fits = parse_fits(&out).unwrap();
+        assert_eq!(read_image_data(&out, &fits.hdus[1]).unwrap(), *pixels);
+        assert_eq!(fits.hdus[2].extname().as_deref(), Some("EVENTS"));
+
+        // New type and shape: BITPIX and NAXISn follow the data.
+        let floats = ImageData::F32(vec![0.5; 12])
+            .with_shape(&[3, 2, 2])
+            .unwrap();
+        assert!(matches!(
+            replace_hdu_data(&data, 1, &floats, true),
+            Err(Error::DimensionMismatch {
+                expected: 8,
+                got: 12
+            })
+        ));
+        let out = replace_hdu_data(&data, 1, &floats, false).unwrap();
+        let fits = parse_fits(&out).unwrap();
+        let sci = &fits.hdus[1];
+        assert_eq!(
+            sci.info,
+            HduInfo::Image {
+                bitpix: -32,
+                naxes: vec![3, 2, 2],
+            }
+        );
+        assert_eq!(sci.extname().as_deref(), Some("SCI"));
+        assert!(keyword_value(&sci.cards, "BZERO").is_some());
+        assert_eq!(read_image_data(&out, sci).unwrap(), *floats);
+        assert_eq!(fits.hdus[2].extname().as_deref(), Some("EVENTS"));
+
+        assert!(replace_hdu_data(&data, 2, &pixels, false).is_err());
+        assert!(matches!(
+            replace_hdu_data(&data, 3, &pixels, false),
+            Err(Error::InvalidValue)
+        ));
+    }
+
+    #[test]
+    fn replace_hdu_data_drops_little_endian_marker() {
+        use crate::image::{read_image_data, ImageData};
+
+        let mut cards = primary_header_image(16, &[2]);
+        let byteorder = Value::String(String::from("LITTLE"));
+        cards.push(crate::header::hierarch_card("BYTEORDER", &byteorder).unwrap());
+        cards.push(card("BYTEORDR", byteorder));
+        let mut data = build_fits_bytes(&cards, 4);
+        data[BLOCK_SIZE..BLOCK_SIZE + 4].copy_from_slice(&[1, 0, 2, 0]);
+        let options = ParseOptions {
+            honor_byteorder: true,
+            ..ParseOptions::default()
+        };
+
+        // Copied verbatim, the marker still describes the data.
+        let merged = concat_fits(&data, &build_fits_bytes(&primary_header_naxis0(), 0)).unwrap();
+        let (fits, _) = parse_fits_with(&merged, &options).unwrap();
+        assert!(fits.primary().little_endian());
+        assert_eq!(
+            read_image_data(&merged, fits.primary()).unwrap(),
+            ImageData::I16(vec![1, 2])
+        );
+
+        let pixels = ImageData::I16(vec![3, -4]).with_shape(&[2]).unwrap();
+        let out = replace_hdu_data(&data, 0, &pixels, true).unwrap();
+        let (fits, _) = parse_fits_with(&out, &options).unwrap();
+        let primary = fits.primary();
+        assert!(!primary.little_endian());
+        assert!(!primary.cards.iter().any(is_byteorder_card));
+        assert_eq!(read_image_data(&out, primary).unwrap(), *pixels);
+    }
+
+    #[test]
+    fn continue_past_stray_end() {
+        use crate::header::{format_card, format_end_card};
+
+        let block = |cards: &[Card]| {
+            let mut bytes: Vec<u8> = cards.iter().flat_map(format_card).collect();
+            bytes.extend_from_slice(&format_end_card());
+            bytes.resize(BLOCK_SIZE, b' ');
+            bytes
+        };
+        let mut data = block(&[
+            card("SIMPLE", Value::Logical(true)),
+            card("BITPIX", Value::Integer(8)),
+            card("NAXIS", Value::Integer(1)),
+        ]);
+        data.extend(block(&[
+            card("NAXIS1", Value::Integer(4)),
+            card("OBJECT", Value::String(String::from("M31"))),
+        ]));
+        data.extend_from_slice(&[1, 2, 3, 4]);
+        data.resize(3 * BLOCK_SIZE, 0);
+
+        assert!(parse_fits(&data).is_err());
+
+        let options = ParseOptions {
+            continue_past_end: true,
+            ..ParseOptions::default()
+        };
+        let (fits, warnings) = parse_fits_with(&data, &options).unwrap();
+        assert_eq!(warnings, vec![ParseWarning::StrayEnd { hdu: 0 }]);
+        let primary = fits.primary();
+        assert_eq!(
+            primary.info,
+            HduInfo::Primary {
+                bitpix: 8,
+                naxes: vec![4],
+            }
+        );
+        assert_eq!(
+            keyword_value(&primary.cards, "OBJECT"),
+            Some(&Value::String(String::from("M31")))
+        );
+        assert_eq!(primary.data_start, 2 * BLOCK_SIZE);
+        assert_eq!(
+            crate::image::read_image_data(&data, primary).unwrap(),
+            crate::image::ImageData::U8(vec![1, 2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn hdu_new_describes_standard_layout() {
+        let cards = primary_header_image(8, &[2, 2]);
+        let data = build_fits_bytes(&cards, 4);
+        let hdu = Hdu::new(
+            HduInfo::Primary {
+                bitpix: 8,
+                naxes: vec![2, 2],
+            },
+            0,
+            BLOCK_SIZE,
+            4,
+            cards,
+        );
+        assert_eq!(hdu.header_pad_byte(), b' ');
+        assert!(!hdu.little_endian());
+        assert_eq!(
+            crate::image::read_image_data(&data, &hdu).unwrap(),
+            crate::image::ImageData::U8(vec![0; 4])
+        );
+    }
+
+    #[test]
+    fn concat_fits_appends_extensions_of_second_file() {
+        let mut first = build_fits_bytes(&primary_header_naxis0(), 0);
+        first.extend(build_fits_bytes(
+            &image_extension_header(16, &[4, 4], Some("SCI")),
+            32,
+        ));
+        first[2 * BLOCK_SIZE] = 0x7f;
+        let mut second = build_fits_bytes(&primary_header_naxis0(), 0);
+        let cal = build_fits_bytes(&bintable_extension_header(8, 3, 0, 0, Some("CAL")), 24);
+        second.extend_from_slice(&cal);
+
+        let merged = concat_fits(&first, &second).unwrap();
+        let fits = parse_fits(&merged).unwrap();
+        assert_eq!(fits.len(), 3);
+        assert_eq!(
+            keyword_value(&fits.primary().cards, "EXTEND"),
+            Some(&Value::Logical(true))
+        );
+        assert_eq!(fits.get(1).unwrap().extname().as_deref(), Some("SCI"));
+        assert_eq!(fits.get(2).unwrap().extname().as_deref(), Some("CAL"));
+        assert_eq!(merged[fits.get(1).unwrap().data_start], 0x7f);
+        assert_eq!(&merged[fits.get(2).unwrap().header_start..], &cal[..]);
+
+        let with_pixels = build_fits_bytes(&primary_header_image(8, &[2, 2]), 4);
+        assert!(matches!(
+            concat_fits(&first, &with_pixels),
+            Err(Error::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn concat_fits_adds_extend_at_block_boundary() {
+        // With 34 cards the header and END fill one block once EXTEND is
+        // added; with 35 it only spills into a second block after EXTEND.
+        for (ncards, header_blocks) in [(34, 1), (35, 2)] {
+            let mut cards = primary_header_image(8, &[2, 2]);
+            for i in cards.len()..ncards {
+                let kw = alloc::format!("KEY{}", i);
+                cards.push(card(&kw, Value::Integer(i as i64)));
+            }
+            let mut first = build_fits_bytes(&cards, 4);
+            first[BLOCK_SIZE..BLOCK_SIZE + 4].copy_from_slice(&[1, 2, 3, 4]);
+            let second = build_fits_bytes(&primary_header_naxis0(), 0);
+
+            let merged = concat_fits(&first, &second).unwrap();
+            assert_eq!(merged.len(), (header_blocks + 1) * BLOCK_SIZE);
+            let fits = parse_fits(&merged).unwrap();
+            let primary = fits.primary();
+            assert_eq!(primary.data_start, header_blocks * BLOCK_SIZE);
+            assert_eq!(
+                crate::image::read_image_data(&merged, primary).unwrap(),
+                crate::image::ImageData::U8(vec![1, 2, 3, 4])
+            );
+        }
+    }
 }
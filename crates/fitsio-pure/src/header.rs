@@ -1,5 +1,6 @@
 //! FITS header card parsing, writing, and validation.
 
+use alloc::borrow::Cow;
 use alloc::string::String;
 use alloc::string::ToString;
 use alloc::vec;
@@ -8,14 +9,19 @@ use core::str;
 
 use crate::block::{BLOCK_SIZE, CARDS_PER_BLOCK, CARD_SIZE, HEADER_PAD_BYTE};
 use crate::error::{Error, Result};
-use crate::value::{format_value, parse_value, Value};
+use crate::io::Read;
+use crate::value::{format_value, parse_value_raw, Value};
 
 // ── Types ──
 
 /// A parsed FITS header card (one 80-byte keyword record).
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Card {
     /// The 8-byte keyword name, ASCII, left-justified, space-padded.
+    ///
+    /// With the `serde` feature this is (de)serialized as a trimmed string.
+    #[cfg_attr(feature = "serde", serde(with = "keyword_serde"))]
     pub keyword: [u8; 8],
     /// The parsed value, if this card has a value indicator (`= ` in bytes 8..10).
     pub value: Option<Value>,
@@ -53,6 +59,31 @@ impl Card {
     }
 }
 
+/// Serialize the padded keyword array as a trimmed string and back.
+#[cfg(feature = "serde")]
+mod keyword_serde {
+    use alloc::string::String;
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(keyword: &[u8; 8], serializer: S) -> Result<S::Ok, S::Error> {
+        let name = core::str::from_utf8(keyword).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(name.trim_end())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 8], D::Error> {
+        let name = String::deserialize(deserializer)?;
+        if name.len() > 8 || !name.is_ascii() {
+            return Err(D::Error::custom(
+                "keyword must be at most 8 ASCII characters",
+            ));
+        }
+        let mut keyword = [b' '; 8];
+        keyword[..name.len()].copy_from_slice(name.as_bytes());
+        Ok(keyword)
+    }
+}
+
 /// The type of HDU, which determines required keywords per the FITS standard.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HduType {
@@ -76,8 +107,53 @@ fn is_commentary_keyword(keyword: &[u8; 8]) -> bool {
     COMMENTARY_KEYWORDS.contains(&keyword)
 }
 
+/// Character encoding used to decode comment and commentary text in cards.
+///
+/// The FITS standard restricts header text to printable ASCII. Files written
+/// by some tools contain Latin-1 bytes (e.g. `°` or `Å`) that are not valid
+/// UTF-8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextEncoding {
+    /// Decode text as UTF-8; invalid sequences in commentary cards are errors.
+    #[default]
+    Utf8,
+    /// Decode each byte as the Unicode code point of the same value (ISO 8859-1).
+    Latin1,
+}
+
+impl TextEncoding {
+    fn decode<'a>(self, bytes: &'a [u8]) -> Result<Cow<'a, str>> {
+        match self {
+            TextEncoding::Utf8 => str::from_utf8(bytes)
+                .map(Cow::Borrowed)
+                .map_err(|_| Error::InvalidHeader("non-UTF8 card data")),
+            TextEncoding::Latin1 => match str::from_utf8(bytes) {
+                Ok(text) if text.is_ascii() => Ok(Cow::Borrowed(text)),
+                _ => Ok(Cow::Owned(bytes.iter().map(|&b| b as char).collect())),
+            },
+        }
+    }
+}
+
 /// Parse a single 80-byte FITS header card.
 pub fn parse_card(card_bytes: &[u8; CARD_SIZE]) -> Result<Card> {
+    parse_card_inner(card_bytes, false, TextEncoding::Utf8)
+}
+
+/// Parse a single 80-byte FITS header card, tolerating common non-conformities.
+///
+/// Unlike [`parse_card`], a value indicator of `=` in byte 8 is accepted even
+/// when byte 9 is not a space (e.g. `FOO     =42`); the value field then
+/// starts at byte 9.
+pub fn parse_card_lenient(card_bytes: &[u8; CARD_SIZE]) -> Result<Card> {
+    parse_card_inner(card_bytes, true, TextEncoding::Utf8)
+}
+
+fn parse_card_inner(
+    card_bytes: &[u8; CARD_SIZE],
+    lenient: bool,
+    encoding: TextEncoding,
+) -> Result<Card> {
     let mut keyword = [b' '; 8];
     keyword.copy_from_slice(&card_bytes[..8]);
 
@@ -98,9 +174,8 @@ pub fn parse_card(card_bytes: &[u8; CARD_SIZE]) -> Result<Card> {
 
     if is_commentary_keyword(&keyword) {
         let text_bytes = &card_bytes[8..CARD_SIZE];
-        let text = str::from_utf8(text_bytes)
-            .map_err(|_| Error::InvalidHeader("non-UTF8 card data"))?
-            .trim_end();
+        let text = encoding.decode(text_bytes)?;
+        let text = text.trim_end();
         let comment = if text.is_empty() {
             None
         } else {
@@ -113,18 +188,30 @@ pub fn parse_card(card_bytes: &[u8; CARD_SIZE]) -> Result<Card> {
         });
     }
 
-    if card_bytes[8] == b'=' && card_bytes[9] == b' ' {
-        let value_field = &card_bytes[10..CARD_SIZE];
-        match parse_value(value_field) {
-            Some((val, comment)) => Ok(Card {
-                keyword,
-                value: Some(val),
-                comment: comment.map(String::from),
-            }),
+    let value_start = match (card_bytes[8], card_bytes[9]) {
+        (b'=', b' ') => Some(10),
+        (b'=', _) if lenient => Some(9),
+        _ => None,
+    };
+
+    if let Some(value_start) = value_start {
+        let value_field = &card_bytes[value_start..CARD_SIZE];
+        match parse_value_raw(value_field) {
+            Some((val, comment)) => {
+                // Comments that fail to decode are dropped rather than rejected.
+                let comment = comment
+                    .and_then(|c| encoding.decode(c).ok())
+                    .map(|c| String::from(c.trim_end()))
+                    .filter(|c| !c.is_empty());
+                Ok(Card {
+                    keyword,
+                    value: Some(val),
+                    comment,
+                })
+            }
             None => {
-                let field_str = str::from_utf8(value_field)
-                    .map_err(|_| Error::InvalidHeader("non-UTF8 card data"))?;
-                let comment = extract_comment_from_empty_value(field_str);
+                let field_str = encoding.decode(value_field)?;
+                let comment = extract_comment_from_empty_value(&field_str);
                 Ok(Card {
                     keyword,
                     value: None,
@@ -134,9 +221,8 @@ pub fn parse_card(card_bytes: &[u8; CARD_SIZE]) -> Result<Card> {
         }
     } else {
         let text_bytes = &card_bytes[8..CARD_SIZE];
-        let text = str::from_utf8(text_bytes)
-            .map_err(|_| Error::InvalidHeader("non-UTF8 card data"))?
-            .trim_end();
+        let text = encoding.decode(text_bytes)?;
+        let text = text.trim_end();
         let comment = if text.is_empty() {
             None
         } else {
@@ -182,40 +268,97 @@ const KW_CONTINUE: [u8; 8] = *b"CONTINUE";
 /// is followed by one or more `CONTINUE` keyword cards, the values are
 /// merged into a single `Value::String`.
 pub fn parse_header_blocks(data: &[u8]) -> Result<Vec<Card>> {
+    parse_header_blocks_inner(data, false, TextEncoding::Utf8)
+}
+
+/// Parse header blocks like [`parse_header_blocks`], using
+/// [`parse_card_lenient`] for each card.
+pub fn parse_header_blocks_lenient(data: &[u8]) -> Result<Vec<Card>> {
+    parse_header_blocks_inner(data, true, TextEncoding::Utf8)
+}
+
+pub(crate) fn parse_header_blocks_inner(
+    data: &[u8],
+    lenient: bool,
+    encoding: TextEncoding,
+) -> Result<Vec<Card>> {
     if data.len() < BLOCK_SIZE {
         return Err(Error::UnexpectedEof);
     }
 
     let mut cards = Vec::new();
-    let num_blocks = data.len() / BLOCK_SIZE;
-
-    for block_idx in 0..num_blocks {
-        let block_start = block_idx * BLOCK_SIZE;
-        for card_idx in 0..CARDS_PER_BLOCK {
-            let card_start = block_start + card_idx * CARD_SIZE;
-            let card_bytes: &[u8; CARD_SIZE] = data[card_start..card_start + CARD_SIZE]
-                .try_into()
-                .map_err(|_| Error::InvalidHeader("non-UTF8 card data"))?;
+    for block in data.chunks_exact(BLOCK_SIZE) {
+        if parse_block_cards(block, lenient, encoding, &mut cards)? {
+            merge_continue_cards(&mut cards);
+            return Ok(cards);
+        }
+    }
 
-            let card = parse_card(card_bytes)?;
-            let is_end = card.is_end();
-            cards.push(card);
+    Err(Error::UnexpectedEof)
+}
 
-            if is_end {
-                merge_continue_cards(&mut cards);
-                return Ok(cards);
-            }
+/// Parse header blocks from a reader, one 2880-byte block at a time.
+///
+/// Reads exactly as many blocks as the header occupies, so the reader is
+/// left positioned at the start of the HDU's data. Cards are parsed and
+/// CONTINUE strings merged as in [`parse_header_blocks`]. Returns
+/// [`Error::UnexpectedEof`] if the stream ends before the END card.
+pub fn parse_header_blocks_streaming<R: Read>(reader: &mut R) -> Result<Vec<Card>> {
+    let mut cards = Vec::new();
+    let mut block = [0u8; BLOCK_SIZE];
+    loop {
+        reader.read_exact(&mut block).map_err(io_error)?;
+        if parse_block_cards(&block, false, TextEncoding::Utf8, &mut cards)? {
+            merge_continue_cards(&mut cards);
+            return Ok(cards);
         }
     }
+}
 
-    Err(Error::UnexpectedEof)
+#[cfg(feature = "std")]
+pub(crate) fn io_error(e: std::io::Error) -> Error {
+    match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => Error::UnexpectedEof,
+        _ => Error::Io(e),
+    }
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn io_error(_: crate::io::IoError) -> Error {
+    Error::UnexpectedEof
+}
+
+/// Parse the cards of one header block onto `cards`, stopping after END.
+///
+/// Returns `true` if the END card was found.
+fn parse_block_cards(
+    block: &[u8],
+    lenient: bool,
+    encoding: TextEncoding,
+    cards: &mut Vec<Card>,
+) -> Result<bool> {
+    for chunk in block.chunks_exact(CARD_SIZE).take(CARDS_PER_BLOCK) {
+        let card_bytes: &[u8; CARD_SIZE] = chunk
+            .try_into()
+            .map_err(|_| Error::InvalidHeader("non-UTF8 card data"))?;
+
+        let card = parse_card_inner(card_bytes, lenient, encoding)?;
+        let is_end = card.is_end();
+        cards.push(card);
+
+        if is_end {
+            return Ok(true);
+        }
+    }
+    Ok(false)
 }
 
 /// Merge CONTINUE cards into the preceding card's string value.
 ///
 /// A string ending with `&` followed by one or more CONTINUE cards forms
 /// a single long string. The `&` markers and CONTINUE cards are removed;
-/// the preceding card's value receives the concatenated string.
+/// the preceding card's value receives the concatenated string and its
+/// comment the concatenated `/` comments of all the merged cards.
 fn merge_continue_cards(cards: &mut Vec<Card>) {
     let mut i = 0;
     while i < cards.len() {
@@ -239,11 +382,13 @@ fn merge_continue_cards(cards: &mut Vec<Card>) {
         };
 
         // Absorb subsequent CONTINUE cards.
+        let mut comment = cards[i].comment.take();
         let mut j = i + 1;
         while j < cards.len() && cards[j].keyword == KW_CONTINUE {
             // CONTINUE cards store their string in the comment field
             // (no `= ` indicator), or sometimes use a value field.
-            let cont_str = extract_continue_string(&cards[j]);
+            let (cont_str, cont_comment) = extract_continue_string(&cards[j]);
+            push_comment_piece(&mut comment, cont_comment);
             let ends_with_amp = cont_str.ends_with('&');
             if ends_with_amp {
                 combined.push_str(&cont_str[..cont_str.len() - 1]);
@@ -256,41 +401,49 @@ fn merge_continue_cards(cards: &mut Vec<Card>) {
             }
         }
 
-        // Update the base card with the merged string.
+        // Update the base card with the merged string and comment.
+        if let Some(c) = comment.as_mut() {
+            if c.ends_with('&') {
+                c.pop();
+                c.truncate(c.trim_end().len());
+            }
+        }
         cards[i].value = Some(Value::String(combined));
+        cards[i].comment = comment;
         // Remove the consumed CONTINUE cards.
         cards.drain(i + 1..j);
         i += 1;
     }
 }
 
-/// Extract the string payload from a CONTINUE card.
+/// Extract the string payload and comment from a CONTINUE card.
 ///
 /// CONTINUE cards may carry their value in two forms:
 /// 1. As a quoted string in bytes 8..80 (no `= ` indicator but starts with
-///    optional spaces then `'`).
+///    optional spaces then `'`), optionally followed by `/ comment`.
 /// 2. As a regular value field if the card was parsed with a value.
-fn extract_continue_string(card: &Card) -> String {
+fn extract_continue_string(card: &Card) -> (String, Option<String>) {
     // If the parser found a value (some files use `= ` on CONTINUE), use it.
     if let Some(Value::String(s)) = &card.value {
-        return s.clone();
+        return (s.clone(), card.comment.clone());
     }
     // Otherwise the string lives in the comment field (raw text after keyword).
     // Try to parse it as a quoted string.
     if let Some(text) = &card.comment {
         let trimmed = text.trim_start();
         if trimmed.starts_with('\'') {
-            if let Some(s) = parse_continue_quoted(trimmed) {
-                return s;
+            if let Some(parsed) = parse_continue_quoted(trimmed) {
+                return parsed;
             }
         }
-        return trimmed.to_string();
+        return (trimmed.to_string(), None);
     }
-    String::new()
+    (String::new(), None)
 }
 
-/// Parse a quoted string from CONTINUE card text (bytes 8..80 as a &str).
-fn parse_continue_quoted(text: &str) -> Option<String> {
+/// Parse a quoted string and any trailing `/ comment` from CONTINUE card
+/// text (bytes 8..80 as a &str).
+fn parse_continue_quoted(text: &str) -> Option<(String, Option<String>)> {
     let bytes = text.as_bytes();
     if bytes.is_empty() || bytes[0] != b'\'' {
         return None;
@@ -307,6 +460,7 @@ fn parse_continue_quoted(text: &str) -> Option<String> {
                 value.push('\'');
                 i += 2;
             } else {
+                i += 1;
                 break;
             }
         } else {
@@ -314,7 +468,32 @@ fn parse_continue_quoted(text: &str) -> Option<String> {
             i += 1;
         }
     }
-    Some(value.trim_end().to_string())
+    let comment = text
+        .get(i..)
+        .and_then(|rest| rest.trim_start().strip_prefix('/'))
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(String::from);
+    Some((value.trim_end().to_string(), comment))
+}
+
+/// Append one piece of a continued comment to `merged`.
+///
+/// A piece ending in `&` continues directly into the next one; otherwise
+/// pieces are separated by a space.
+fn push_comment_piece(merged: &mut Option<String>, piece: Option<String>) {
+    let Some(piece) = piece else { return };
+    match merged {
+        Some(m) => {
+            if m.ends_with('&') {
+                m.pop();
+            } else {
+                m.push(' ');
+            }
+            m.push_str(&piece);
+        }
+        None => *merged = Some(piece),
+    }
 }
 
 /// Return the number of bytes consumed by the header (always a multiple of BLOCK_SIZE).
@@ -342,9 +521,41 @@ pub fn header_byte_len(data: &[u8]) -> Result<usize> {
     Err(Error::UnexpectedEof)
 }
 
+/// Look up `keyword` in the first header of `prefix` without parsing the
+/// rest of the file.
+///
+/// Cards are scanned in order up to END and only cards carrying `keyword`
+/// are parsed; the value of the first one that has a value is returned.
+/// `prefix` need not be block-aligned. Returns `Ok(None)` if END is reached
+/// without a match and [`Error::UnexpectedEof`] if `prefix` ends first.
+/// Long strings continued on CONTINUE cards are not joined.
+pub fn find_keyword_in_header(prefix: &[u8], keyword: &str) -> Result<Option<Value>> {
+    if keyword.len() > 8 {
+        return Err(Error::InvalidKeyword);
+    }
+    let mut target = [b' '; 8];
+    target[..keyword.len()].copy_from_slice(keyword.as_bytes());
+
+    for chunk in prefix.chunks_exact(CARD_SIZE) {
+        let card_bytes: &[u8; CARD_SIZE] = chunk.try_into().map_err(|_| Error::UnexpectedEof)?;
+        if &card_bytes[..8] == b"END     " {
+            return Ok(None);
+        }
+        if card_bytes[..8] == target {
+            if let Some(value) = parse_card(card_bytes)?.value {
+                return Ok(Some(value));
+            }
+        }
+    }
+    Err(Error::UnexpectedEof)
+}
+
 // ── Writing ──
 
 /// Serialize a [`Card`] into an 80-byte FITS card image.
+///
+/// Values are laid out by [`format_value`]: integers and logicals end in
+/// column 30 (byte 29), as the fixed format requires.
 pub fn format_card(card: &Card) -> [u8; CARD_SIZE] {
     let mut buf = [b' '; CARD_SIZE];
 
@@ -397,7 +608,11 @@ fn insert_comment(field: &mut [u8; 70], comment: &str) {
             }
         }
     } else {
-        20
+        // Long floats run past the fixed-format column 30.
+        field
+            .iter()
+            .rposition(|&b| b != b' ')
+            .map_or(20, |i| (i + 1).max(20))
     };
 
     let sep_start = content_end + 1;
@@ -459,15 +674,75 @@ fn detect_hdu_type(cards: &[Card]) -> Option<HduType> {
 /// Appends the END card and pads the final block with blank cards.
 /// The returned length is always a multiple of [`BLOCK_SIZE`].
 pub fn serialize_header(cards: &[Card]) -> Result<Vec<u8>> {
+    serialize_header_with_pad(cards, HEADER_PAD_BYTE)
+}
+
+/// Serialize header cards like [`serialize_header`], filling the space after
+/// END with `pad_byte` instead of ASCII spaces.
+///
+/// The standard requires spaces; pass [`Hdu::header_pad_byte`] to reproduce
+/// the null padding written by some older tools when rewriting a file
+/// verbatim.
+///
+/// [`Hdu::header_pad_byte`]: crate::hdu::Hdu::header_pad_byte
+pub fn serialize_header_with_pad(cards: &[Card], pad_byte: u8) -> Result<Vec<u8>> {
     if let Some(hdu_type) = detect_hdu_type(cards) {
         validate_required_keywords(hdu_type, cards)?;
     }
 
-    let total_cards = cards.len() + 1; // +1 for END
-    let total_blocks = total_cards.div_ceil(CARDS_PER_BLOCK);
-    let total_bytes = total_blocks * BLOCK_SIZE;
+    let mut buf = serialize_header_raw(cards);
+    let pad_start = (cards.len() + 1) * CARD_SIZE;
+    buf[pad_start..].fill(pad_byte);
+    Ok(buf)
+}
+
+/// Detect the byte used to pad a header after its END card.
+///
+/// `header` holds the header blocks up to and including the one with END.
+/// Returns 0x00 when everything after END is null and [`HEADER_PAD_BYTE`]
+/// otherwise, including when END is the last card of its block.
+pub fn detect_header_pad_byte(header: &[u8]) -> u8 {
+    let end = header
+        .chunks_exact(CARD_SIZE)
+        .position(|c| &c[..8] == b"END     ");
+    match end.map(|i| &header[(i + 1) * CARD_SIZE..]) {
+        Some(pad) if !pad.is_empty() && pad.iter().all(|&b| b == 0) => 0,
+        _ => HEADER_PAD_BYTE,
+    }
+}
 
-    let mut buf = vec![HEADER_PAD_BYTE; total_bytes];
+/// Serialize a header padded to at least `min_blocks` 2880-byte blocks.
+///
+/// Trailing blank cards (blank keyword, no comment) are dropped, then blank
+/// space is inserted before the END card so that END lands in the last
+/// reserved block. Readers locate the data after the block holding END, so
+/// this reserves room for later in-place header edits without moving the
+/// data. Headers that need more than `min_blocks` blocks are not truncated.
+pub fn pad_header_to_blocks(cards: &[Card], min_blocks: usize) -> Result<Vec<u8>> {
+    let keep = cards
+        .iter()
+        .rposition(|c| !(c.is_blank() && c.value.is_none() && c.comment.is_none()))
+        .map_or(0, |i| i + 1);
+    let mut buf = serialize_header(&cards[..keep])?;
+    let min_len = min_blocks
+        .checked_mul(BLOCK_SIZE)
+        .ok_or(Error::InvalidValue)?;
+    if buf.len() < min_len {
+        let end_offset = keep * CARD_SIZE;
+        buf[end_offset..end_offset + CARD_SIZE].fill(HEADER_PAD_BYTE);
+        buf.resize(min_len, HEADER_PAD_BYTE);
+        buf[min_len - CARD_SIZE..].copy_from_slice(&format_end_card());
+    }
+    Ok(buf)
+}
+
+/// Serialize header cards without validating mandatory keywords.
+///
+/// Lays out `cards` in order, appends the END card, and pads to a whole
+/// number of blocks. Useful for templates and deliberately partial headers;
+/// use [`serialize_header`] for anything meant to be a valid FITS header.
+pub fn serialize_header_raw(cards: &[Card]) -> Vec<u8> {
+    let mut buf = vec![HEADER_PAD_BYTE; header_serialized_len(cards)];
 
     for (i, card) in cards.iter().enumerate() {
         let offset = i * CARD_SIZE;
@@ -479,7 +754,171 @@ pub fn serialize_header(cards: &[Card]) -> Result<Vec<u8>> {
     let end_card = format_end_card();
     buf[end_offset..end_offset + CARD_SIZE].copy_from_slice(&end_card);
 
-    Ok(buf)
+    buf
+}
+
+/// Return the number of bytes `serialize_header` would produce for `cards`.
+///
+/// This is the card count plus the END card, rounded up to whole 2880-byte
+/// blocks. No validation or allocation is performed.
+pub fn header_serialized_len(cards: &[Card]) -> usize {
+    let total_cards = cards.len() + 1; // +1 for END
+    total_cards.div_ceil(CARDS_PER_BLOCK) * BLOCK_SIZE
+}
+
+// ── Lookup ──
+
+/// Returns `true` if any card in `cards` carries `keyword`.
+///
+/// Only borrows the card list; no allocation is performed.
+pub fn has_keyword(cards: &[Card], keyword: &str) -> bool {
+    cards.iter().any(|c| c.keyword_str() == keyword)
+}
+
+/// Return a borrowed reference to the value of the first `keyword` card
+/// that has one.
+///
+/// Cards without a value indicator (e.g. COMMENT) are skipped.
+pub fn keyword_value<'a>(cards: &'a [Card], keyword: &str) -> Option<&'a Value> {
+    cards
+        .iter()
+        .filter(|c| c.keyword_str() == keyword)
+        .find_map(|c| c.value.as_ref())
+}
+
+/// Build a card for a keyword longer than 8 characters using the ESO
+/// `HIERARCH` convention, e.g. `HIERARCH ESO DET CHIP TEMP = -120.5`.
+///
+/// The result has keyword `HIERARCH` and carries the full `keyword = value`
+/// text in its comment, which is also how [`parse_card`] reads such cards
+/// back; look them up with [`hierarch_value`]. Returns
+/// [`Error::InvalidKeyword`] if `keyword` is empty, contains `=` or is not
+/// printable ASCII, and [`Error::InvalidHeader`] if the text does not fit
+/// in one 80-byte card.
+pub fn hierarch_card(keyword: &str, value: &Value) -> Result<Card> {
+    let keyword = keyword.trim();
+    if keyword.is_empty()
+        || !keyword
+            .bytes()
+            .all(|b| (b' '..=b'~').contains(&b) && b != b'=')
+    {
+        return Err(Error::InvalidKeyword);
+    }
+    let field = format_value(value);
+    let value_text = str::from_utf8(&field).map_err(|_| Error::InvalidValue)?;
+    let text = alloc::format!(" {} = {}", keyword, value_text.trim());
+    if text.len() > CARD_SIZE - 8 {
+        return Err(Error::InvalidHeader("HIERARCH card longer than 80 bytes"));
+    }
+    Ok(Card {
+        keyword: *b"HIERARCH",
+        value: None,
+        comment: Some(text),
+    })
+}
+
+/// Return the value of the first `HIERARCH` card for `keyword`.
+///
+/// `keyword` is the full name after `HIERARCH`, e.g. `ESO DET CHIP TEMP`;
+/// runs of spaces must match exactly.
+pub fn hierarch_value(cards: &[Card], keyword: &str) -> Option<Value> {
+    let keyword = keyword.trim();
+    cards
+        .iter()
+        .filter(|c| &c.keyword == b"HIERARCH")
+        .filter_map(|c| c.comment.as_deref()?.split_once('='))
+        .find(|(name, _)| name.trim() == keyword)
+        .and_then(|(_, rest)| parse_value_raw(rest.as_bytes()))
+        .map(|(value, _)| value)
+}
+
+/// Collect the doubly-indexed keywords `{prefix}{i}_{j}`, such as `PV2_1`
+/// (prefix `PV`) or the SIP coefficient `A_2_0` (prefix `A_`).
+///
+/// Returns `((i, j), value)` sorted by index. Both indices must be plain
+/// decimal digits, so alternate-WCS forms like `PV2_1A` are not matched.
+/// When an index pair repeats, the first card with a value wins.
+pub fn collect_indexed_pairs(cards: &[Card], prefix: &str) -> Vec<((usize, usize), Value)> {
+    let index = |s: &str| {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        s.parse::<usize>().ok()
+    };
+    let mut pairs: Vec<((usize, usize), Value)> = Vec::new();
+    for card in cards {
+        let Some(value) = &card.value else {
+            continue;
+        };
+        let Some((i, j)) = card
+            .keyword_str()
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.split_once('_'))
+        else {
+            continue;
+        };
+        let (Some(i), Some(j)) = (index(i), index(j)) else {
+            continue;
+        };
+        if !pairs.iter().any(|(ij, _)| *ij == (i, j)) {
+            pairs.push(((i, j), value.clone()));
+        }
+    }
+    pairs.sort_by_key(|(ij, _)| *ij);
+    pairs
+}
+
+/// Project cards into `(keyword, value, comment)` tuples, skipping END.
+///
+/// Keywords are trimmed of trailing spaces. Commentary cards yield
+/// `(keyword, None, Some(text))`.
+pub fn header_pairs(cards: &[Card]) -> Vec<(String, Option<Value>, Option<String>)> {
+    cards
+        .iter()
+        .filter(|c| !c.is_end())
+        .map(|c| {
+            (
+                String::from(c.keyword_str()),
+                c.value.clone(),
+                c.comment.clone(),
+            )
+        })
+        .collect()
+}
+
+// ── Merging ──
+
+/// Merge `overrides` into `base`, preserving the keyword order of `base`.
+///
+/// For each override card, a card in the result with the same keyword has
+/// its value replaced in place (the override's comment replaces the existing
+/// one only when present). Keywords not found in the result, and all
+/// commentary cards (COMMENT, HISTORY, blank), are appended before any END
+/// card.
+pub fn merge_cards(base: &[Card], overrides: &[Card]) -> Vec<Card> {
+    let mut merged = base.to_vec();
+
+    for card in overrides {
+        if card.is_end() {
+            continue;
+        }
+        if !card.is_commentary() {
+            if let Some(existing) = merged.iter_mut().find(|c| c.keyword == card.keyword) {
+                existing.value = card.value.clone();
+                if card.comment.is_some() {
+                    existing.comment = card.comment.clone();
+                }
+                continue;
+            }
+        }
+        let insert_at = merged
+            .iter()
+            .position(|c| c.is_end())
+            .unwrap_or(merged.len());
+        merged.insert(insert_at, card.clone());
+    }
+
+    merged
 }
 
 // ── Validation ──
@@ -679,6 +1118,17 @@ mod parse_tests {
         }
     }
 
+    #[test]
+    fn parse_card_string_with_slash() {
+        let card = make_card("FILE    =   '/data/a / b.fits' / the path");
+        let c = parse_card(&card).unwrap();
+        assert_eq!(
+            c.value,
+            Some(Value::String(String::from("/data/a / b.fits")))
+        );
+        assert_eq!(c.comment.as_deref(), Some("the path"));
+    }
+
     #[test]
     fn parse_card_logical_true() {
         let card = make_card("SIMPLE  =                    T / standard FITS");
@@ -753,10 +1203,29 @@ mod parse_tests {
     fn parse_card_empty_value_with_comment() {
         let card = make_card("BLANK   =                      / undefined value");
         let c = parse_card(&card).unwrap();
-        assert!(c.value.is_none());
+        assert_eq!(c.value, Some(Value::Undefined));
         assert_eq!(c.comment, Some(String::from("undefined value")));
     }
 
+    #[test]
+    fn undefined_value_round_trips() {
+        let card = Card {
+            keyword: *b"BLANK   ",
+            value: Some(Value::Undefined),
+            comment: Some(String::from("no blank pixels yet")),
+        };
+        let bytes = format_card(&card);
+        assert_eq!(&bytes[..10], b"BLANK   = ");
+        assert!(bytes[10..30].iter().all(|&b| b == b' '));
+        assert_eq!(parse_card(&bytes).unwrap(), card);
+
+        let bare = Card {
+            comment: None,
+            ..card
+        };
+        assert_eq!(parse_card(&format_card(&bare)).unwrap(), bare);
+    }
+
     #[test]
     fn parse_card_hyphen_keyword() {
         let card = make_card("DATE-OBS= '2024-01-15'");
@@ -771,6 +1240,51 @@ mod parse_tests {
         assert_eq!(c.value, Some(Value::String(String::from("it's ok"))));
     }
 
+    #[test]
+    fn parse_card_equals_without_space_strict_has_no_value() {
+        let card = make_card("FOO     =42");
+        let c = parse_card(&card).unwrap();
+        assert_eq!(c.value, None);
+    }
+
+    #[test]
+    fn parse_card_lenient_equals_without_space() {
+        let c = parse_card_lenient(&make_card("FOO     =42")).unwrap();
+        assert_eq!(c.keyword_str(), "FOO");
+        assert_eq!(c.value, Some(Value::Integer(42)));
+
+        let c = parse_card_lenient(&make_card("NAME    ='M31' / target")).unwrap();
+        assert_eq!(c.value, Some(Value::String(String::from("M31"))));
+        assert_eq!(c.comment.as_deref(), Some("target"));
+    }
+
+    #[test]
+    fn parse_card_lenient_standard_card_unchanged() {
+        let card = make_card("TELESCOP= 'Hubble  '           / telescope name");
+        assert_eq!(
+            parse_card_lenient(&card).unwrap(),
+            parse_card(&card).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_header_lenient_equals_without_space() {
+        let cards = [
+            make_card("SIMPLE  =                    T"),
+            make_card("BITPIX  =8"),
+            make_card("NAXIS   =0"),
+            make_card("END"),
+        ];
+        let block = make_header_block(&cards);
+
+        let strict = parse_header_blocks(&block).unwrap();
+        assert_eq!(strict[1].value, None);
+
+        let lenient = parse_header_blocks_lenient(&block).unwrap();
+        assert_eq!(lenient[1].value, Some(Value::Integer(8)));
+        assert_eq!(lenient[2].value, Some(Value::Integer(0)));
+    }
+
     #[test]
     fn parse_header_simple() {
         let cards = [
@@ -901,6 +1415,93 @@ mod parse_tests {
         assert!(parsed.iter().all(|c| c.keyword != KW_CONTINUE));
     }
 
+    #[test]
+    fn continue_merges_comments() {
+        let cards = [
+            make_card("SIMPLE  =                    T"),
+            make_card("BITPIX  =                    8"),
+            make_card("NAXIS   =                    0"),
+            make_card("LONGSTR = 'This is a &' / first part of &"),
+            make_card("CONTINUE  'long string&' / the comm&"),
+            make_card("CONTINUE  'value' / ent, continued"),
+            make_card("END"),
+        ];
+        let block = make_header_block(&cards);
+        let parsed = parse_header_blocks(&block).unwrap();
+        let long = parsed
+            .iter()
+            .find(|c| c.keyword_str() == "LONGSTR")
+            .unwrap();
+        assert_eq!(
+            long.value,
+            Some(Value::String(String::from("This is a long stringvalue")))
+        );
+        assert_eq!(
+            long.comment.as_deref(),
+            Some("first part of the comment, continued")
+        );
+    }
+
+    /// A reader that hands out at most `chunk` bytes per call.
+    struct ChunkedReader<'a> {
+        data: &'a [u8],
+        chunk: usize,
+    }
+
+    impl Read for ChunkedReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> crate::io::Result<usize> {
+            let n = self.chunk.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn streaming_two_block_header() {
+        let mut cards = vec![
+            make_card("SIMPLE  =                    T"),
+            make_card("BITPIX  =                    8"),
+            make_card("NAXIS   =                    0"),
+            make_card("LONGSTR = 'Hello &'"),
+            make_card("CONTINUE  'World'"),
+        ];
+        for i in 0..40 {
+            cards.push(make_card(&alloc::format!(
+                "KEY{:<5}=                 {:>3}",
+                i,
+                i
+            )));
+        }
+        cards.push(make_card("END"));
+        let mut data: Vec<u8> = cards.concat();
+        data.resize(2 * BLOCK_SIZE, b' ');
+        data.extend_from_slice(&[7u8; BLOCK_SIZE]);
+
+        let mut reader = ChunkedReader {
+            data: &data,
+            chunk: 1000,
+        };
+        let parsed = parse_header_blocks_streaming(&mut reader).unwrap();
+        assert_eq!(parsed, parse_header_blocks(&data).unwrap());
+        assert_eq!(
+            keyword_value(&parsed, "LONGSTR"),
+            Some(&Value::String(String::from("Hello World")))
+        );
+        assert_eq!(keyword_value(&parsed, "KEY39"), Some(&Value::Integer(39)));
+        // The data block is left unread.
+        assert_eq!(reader.data.len(), BLOCK_SIZE);
+
+        let mut truncated = ChunkedReader {
+            data: &data[..BLOCK_SIZE + 100],
+            chunk: 1000,
+        };
+        assert!(matches!(
+            parse_header_blocks_streaming(&mut truncated),
+            Err(Error::UnexpectedEof)
+        ));
+    }
+
     #[test]
     fn continue_no_ampersand_no_merge() {
         let cards = [
@@ -943,6 +1544,7 @@ mod parse_tests {
 #[cfg(test)]
 mod write_tests {
     use super::*;
+    use crate::value::parse_value;
     use alloc::string::String;
 
     fn make_keyword(name: &str) -> [u8; 8] {
@@ -986,6 +1588,23 @@ mod write_tests {
         assert_eq!(buf[29], b'2');
     }
 
+    #[test]
+    fn format_card_mandatory_integers_match_canonical_bytes() {
+        let naxis = format_card(&Card {
+            keyword: make_keyword("NAXIS"),
+            value: Some(Value::Integer(2)),
+            comment: None,
+        });
+        assert_eq!(&naxis[..30], b"NAXIS   =                    2");
+        let bitpix = format_card(&Card {
+            keyword: make_keyword("BITPIX"),
+            value: Some(Value::Integer(-32)),
+            comment: None,
+        });
+        assert_eq!(&bitpix[..30], b"BITPIX  =                  -32");
+        assert!(bitpix[30..].iter().all(|&b| b == b' '));
+    }
+
     #[test]
     fn format_card_logical_value() {
         let card = Card {
@@ -1009,6 +1628,19 @@ mod write_tests {
         assert!(s.contains("/ number of axes"));
     }
 
+    #[test]
+    fn long_float_round_trips_with_comment() {
+        let card = Card {
+            keyword: make_keyword("CRVAL1"),
+            value: Some(Value::Float(1.234567890123456e-10)),
+            comment: Some(String::from("reference value")),
+        };
+        let header = serialize_header(&[card]).unwrap();
+        let parsed = parse_header_blocks(&header).unwrap();
+        assert_eq!(parsed[0].value, Some(Value::Float(1.234567890123456e-10)));
+        assert_eq!(parsed[0].comment.as_deref(), Some("reference value"));
+    }
+
     #[test]
     fn end_card_format() {
         let buf = format_end_card();
@@ -1047,6 +1679,75 @@ mod write_tests {
         assert_eq!(header.len(), BLOCK_SIZE);
     }
 
+    #[test]
+    fn pad_header_to_three_blocks() {
+        let mut cards = vec![
+            Card {
+                keyword: make_keyword("SIMPLE"),
+                value: Some(Value::Logical(true)),
+                comment: None,
+            },
+            Card {
+                keyword: make_keyword("BITPIX"),
+                value: Some(Value::Integer(8)),
+                comment: None,
+            },
+            Card {
+                keyword: make_keyword("NAXIS"),
+                value: Some(Value::Integer(0)),
+                comment: None,
+            },
+        ];
+        for _ in 0..40 {
+            cards.push(Card {
+                keyword: [b' '; 8],
+                value: None,
+                comment: None,
+            });
+        }
+
+        let buf = pad_header_to_blocks(&cards, 3).unwrap();
+        assert_eq!(buf.len(), 8640);
+        // END is the last card; everything between NAXIS and END is blank.
+        assert_eq!(&buf[8640 - CARD_SIZE..8640 - CARD_SIZE + 3], b"END");
+        assert!(buf[3 * CARD_SIZE..8640 - CARD_SIZE]
+            .iter()
+            .all(|&b| b == b' '));
+        assert_eq!(header_byte_len(&buf).unwrap(), 8640);
+
+        let parsed = parse_header_blocks(&buf).unwrap();
+        assert_eq!(parsed.len(), 108);
+        assert!(parsed[3..107].iter().all(Card::is_blank));
+
+        // A header already past the minimum keeps its natural size.
+        assert_eq!(
+            pad_header_to_blocks(&cards[..3], 0).unwrap().len(),
+            BLOCK_SIZE
+        );
+    }
+
+    #[test]
+    fn header_serialized_len_block_boundary() {
+        let cards: Vec<Card> = (0..35)
+            .map(|i| Card {
+                keyword: make_keyword(&alloc::format!("KEY{}", i)),
+                value: Some(Value::Integer(i)),
+                comment: None,
+            })
+            .collect();
+        assert_eq!(header_serialized_len(&cards), BLOCK_SIZE);
+        assert_eq!(serialize_header(&cards).unwrap().len(), BLOCK_SIZE);
+
+        let mut cards = cards;
+        cards.push(Card {
+            keyword: make_keyword("KEY35"),
+            value: Some(Value::Integer(35)),
+            comment: None,
+        });
+        assert_eq!(header_serialized_len(&cards), 2 * BLOCK_SIZE);
+        assert_eq!(serialize_header(&cards).unwrap().len(), 2 * BLOCK_SIZE);
+    }
+
     #[test]
     fn serialize_header_contains_end() {
         let cards = minimal_primary_cards();
@@ -1496,6 +2197,22 @@ mod validate_tests {
         assert!(serialize_header(&cards).is_err());
     }
 
+    #[test]
+    fn serialize_raw_skips_validation() {
+        let cards = vec![
+            card(b"SIMPLE", Some(Value::Logical(true))),
+            card(b"NAXIS", Some(Value::Integer(0))),
+        ];
+        let buf = serialize_header_raw(&cards);
+        assert_eq!(buf.len(), BLOCK_SIZE);
+        assert_eq!(&buf[..6], b"SIMPLE");
+        assert_eq!(&buf[CARD_SIZE..CARD_SIZE + 5], b"NAXIS");
+        assert_eq!(&buf[2 * CARD_SIZE..2 * CARD_SIZE + 3], b"END");
+
+        let parsed = parse_header_blocks(&buf).unwrap();
+        assert_eq!(parsed.len(), 3);
+    }
+
     #[test]
     fn serialize_accepts_valid_primary() {
         let cards = vec![
@@ -1553,3 +2270,312 @@ mod validate_tests {
         assert!(serialize_header(&cards).is_ok());
     }
 }
+
+#[cfg(test)]
+mod merge_tests {
+    use super::*;
+    use alloc::string::String;
+
+    fn card(keyword: &[u8], value: Value) -> Card {
+        Card {
+            keyword: kw(keyword),
+            value: Some(value),
+            comment: None,
+        }
+    }
+
+    fn commentary(keyword: &[u8], text: &str) -> Card {
+        Card {
+            keyword: kw(keyword),
+            value: None,
+            comment: Some(String::from(text)),
+        }
+    }
+
+    fn end_card() -> Card {
+        Card {
+            keyword: kw(b"END"),
+            value: None,
+            comment: None,
+        }
+    }
+
+    fn keywords(cards: &[Card]) -> Vec<&str> {
+        cards.iter().map(|c| c.keyword_str()).collect()
+    }
+
+    #[test]
+    fn merge_wcs_into_template() {
+        let template = vec![
+            card(b"SIMPLE", Value::Logical(true)),
+            card(b"BITPIX", Value::Integer(-32)),
+            card(b"NAXIS", Value::Integer(2)),
+            card(b"NAXIS1", Value::Integer(100)),
+            card(b"NAXIS2", Value::Integer(100)),
+            card(b"CTYPE1", Value::String(String::from("RA---TAN"))),
+            card(b"CTYPE2", Value::String(String::from("DEC--TAN"))),
+            card(b"CRVAL1", Value::Float(0.0)),
+            card(b"CRVAL2", Value::Float(0.0)),
+            card(b"OBJECT", Value::String(String::from("M31"))),
+            end_card(),
+        ];
+        let wcs = vec![
+            card(b"CRVAL1", Value::Float(10.68)),
+            card(b"CRVAL2", Value::Float(41.27)),
+            Card {
+                comment: Some(String::from("reference pixel")),
+                ..card(b"CRPIX1", Value::Float(50.5))
+            },
+            card(b"CRPIX2", Value::Float(50.5)),
+            commentary(b"HISTORY", "WCS solved"),
+        ];
+
+        let merged = merge_cards(&template, &wcs);
+        assert_eq!(
+            keywords(&merged),
+            vec![
+                "SIMPLE", "BITPIX", "NAXIS", "NAXIS1", "NAXIS2", "CTYPE1", "CTYPE2", "CRVAL1",
+                "CRVAL2", "OBJECT", "CRPIX1", "CRPIX2", "HISTORY", "END",
+            ]
+        );
+        assert_eq!(merged[7].value, Some(Value::Float(10.68)));
+        assert_eq!(merged[8].value, Some(Value::Float(41.27)));
+        assert_eq!(merged[10].comment.as_deref(), Some("reference pixel"));
+        assert_eq!(merged[12].comment.as_deref(), Some("WCS solved"));
+    }
+
+    #[test]
+    fn merge_keeps_existing_comment_when_override_has_none() {
+        let base = vec![Card {
+            comment: Some(String::from("exposure time")),
+            ..card(b"EXPTIME", Value::Float(10.0))
+        }];
+        let merged = merge_cards(&base, &[card(b"EXPTIME", Value::Float(30.0))]);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].value, Some(Value::Float(30.0)));
+        assert_eq!(merged[0].comment.as_deref(), Some("exposure time"));
+    }
+
+    #[test]
+    fn merge_commentary_always_appends() {
+        let base = vec![commentary(b"COMMENT", "first")];
+        let merged = merge_cards(&base, &[commentary(b"COMMENT", "second")]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[1].comment.as_deref(), Some("second"));
+    }
+
+    #[test]
+    fn merge_without_end_appends_at_tail() {
+        let base = vec![card(b"A", Value::Integer(1))];
+        let merged = merge_cards(&base, &[card(b"B", Value::Integer(2))]);
+        assert_eq!(keywords(&merged), vec!["A", "B"]);
+    }
+}
+
+#[cfg(test)]
+mod lookup_tests {
+    use super::*;
+    use alloc::string::String;
+
+    fn cards() -> Vec<Card> {
+        vec![
+            Card {
+                keyword: kw(b"COMMENT"),
+                value: None,
+                comment: Some(String::from("OBJECT is below")),
+            },
+            Card {
+                keyword: kw(b"OBJECT"),
+                value: Some(Value::String(String::from("M31"))),
+                comment: None,
+            },
+            Card {
+                keyword: kw(b"EXPTIME"),
+                value: Some(Value::Float(30.0)),
+                comment: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn find_keyword_in_raw_header() {
+        let mut cards = crate::primary::build_primary_header(8, &[]).unwrap();
+        cards.extend(self::cards());
+        let header = serialize_header(&cards).unwrap();
+
+        assert_eq!(
+            find_keyword_in_header(&header, "OBJECT").unwrap(),
+            Some(Value::String(String::from("M31")))
+        );
+        assert_eq!(find_keyword_in_header(&header, "TELESCOP").unwrap(), None);
+        // The match is found before the prefix runs out.
+        assert!(find_keyword_in_header(&header[..5 * CARD_SIZE], "OBJECT")
+            .unwrap()
+            .is_some());
+        assert!(matches!(
+            find_keyword_in_header(&header[..5 * CARD_SIZE], "TELESCOP"),
+            Err(Error::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn hierarch_card_round_trips() {
+        let long = "ESO INS OPTI3 FILTER CENTRAL WAVELENGTH";
+        let mut cards = crate::primary::build_primary_header(8, &[]).unwrap();
+        cards.push(hierarch_card(long, &Value::Float(656.28)).unwrap());
+        cards
+            .push(hierarch_card("ESO OBS NAME", &Value::String(String::from("M31 deep"))).unwrap());
+        let header = serialize_header(&cards).unwrap();
+        assert_eq!(
+            &header[3 * CARD_SIZE..4 * CARD_SIZE],
+            alloc::format!("{:<80}", alloc::format!("HIERARCH {} = 656.28", long)).as_bytes()
+        );
+
+        let parsed = parse_header_blocks(&header).unwrap();
+        assert_eq!(hierarch_value(&parsed, long), Some(Value::Float(656.28)));
+        assert_eq!(
+            hierarch_value(&parsed, "ESO OBS NAME"),
+            Some(Value::String(String::from("M31 deep")))
+        );
+        assert_eq!(hierarch_value(&parsed, "ESO OBS"), None);
+
+        let too_long = "X".repeat(70);
+        assert!(matches!(
+            hierarch_card(&too_long, &Value::Integer(1)),
+            Err(Error::InvalidHeader(_))
+        ));
+        assert!(matches!(
+            hierarch_card("A=B", &Value::Integer(1)),
+            Err(Error::InvalidKeyword)
+        ));
+    }
+
+    #[test]
+    fn has_keyword_present_and_absent() {
+        let cards = cards();
+        assert!(has_keyword(&cards, "OBJECT"));
+        assert!(has_keyword(&cards, "COMMENT"));
+        assert!(!has_keyword(&cards, "TELESCOP"));
+    }
+
+    #[test]
+    fn keyword_value_borrows_from_cards() {
+        let cards = cards();
+        let value = keyword_value(&cards, "EXPTIME").unwrap();
+        assert!(core::ptr::eq(value, cards[2].value.as_ref().unwrap()));
+        assert_eq!(value, &Value::Float(30.0));
+    }
+
+    #[test]
+    fn keyword_value_skips_cards_without_value() {
+        let cards = cards();
+        assert_eq!(keyword_value(&cards, "COMMENT"), None);
+        assert_eq!(keyword_value(&cards, "TELESCOP"), None);
+        assert_eq!(
+            keyword_value(&cards, "OBJECT"),
+            Some(&Value::String(String::from("M31")))
+        );
+    }
+
+    #[test]
+    fn header_pairs_projects_cards() {
+        let mut cards = cards();
+        cards.push(Card {
+            keyword: kw(b"END"),
+            value: None,
+            comment: None,
+        });
+        let pairs = header_pairs(&cards);
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(
+            pairs[0],
+            (
+                String::from("COMMENT"),
+                None,
+                Some(String::from("OBJECT is below"))
+            )
+        );
+        assert_eq!(
+            pairs[1],
+            (
+                String::from("OBJECT"),
+                Some(Value::String(String::from("M31"))),
+                None
+            )
+        );
+        assert_eq!(pairs[2].0, "EXPTIME");
+    }
+
+    #[test]
+    fn collect_indexed_pairs_reads_pv_coefficients() {
+        let pv = |name: &[u8], v: f64| Card {
+            keyword: kw(name),
+            value: Some(Value::Float(v)),
+            comment: None,
+        };
+        let cards = vec![
+            pv(b"PV2_3", 0.3),
+            pv(b"PV2_0", 0.0),
+            pv(b"PV1_1", 1.1),
+            pv(b"PV2_5", 0.5),
+            pv(b"PV2_1", 1.0),
+            pv(b"PV2_4", 0.4),
+            pv(b"PV2_2", 0.2),
+            pv(b"PV2_1A", 9.0),
+            pv(b"PV2_1", 7.0),
+            pv(b"PVX_1", 9.0),
+        ];
+        let pairs = collect_indexed_pairs(&cards, "PV");
+        let indices: Vec<(usize, usize)> = pairs.iter().map(|(ij, _)| *ij).collect();
+        assert_eq!(
+            indices,
+            vec![(1, 1), (2, 0), (2, 1), (2, 2), (2, 3), (2, 4), (2, 5)]
+        );
+        assert_eq!(pairs[2].1, Value::Float(1.0));
+        assert_eq!(pairs[6].1, Value::Float(0.5));
+
+        let sip = vec![pv(b"A_2_0", 1e-5), pv(b"A_ORDER", 2.0)];
+        assert_eq!(
+            collect_indexed_pairs(&sip, "A_"),
+            vec![((2, 0), Value::Float(1e-5))]
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+
+    fn card(keyword: &[u8; 8], value: Option<Value>, comment: Option<&str>) -> Card {
+        Card {
+            keyword: *keyword,
+            value,
+            comment: comment.map(String::from),
+        }
+    }
+
+    #[test]
+    fn cards_roundtrip_through_json() {
+        let cards = vec![
+            card(b"SIMPLE  ", Some(Value::Logical(true)), Some("conforms")),
+            card(b"BITPIX  ", Some(Value::Integer(-32)), None),
+            card(b"EXPTIME ", Some(Value::Float(30.5)), Some("seconds")),
+            card(b"OBJECT  ", Some(Value::String(String::from("M31"))), None),
+            card(b"CINT    ", Some(Value::ComplexInt(1, -2)), None),
+            card(b"CFLT    ", Some(Value::ComplexFloat(1.5, 2.5)), None),
+            card(b"COMMENT ", None, Some("free text")),
+        ];
+
+        let json = serde_json::to_string(&cards).unwrap();
+        assert!(json.contains("\"keyword\":\"BITPIX\""));
+        let back: Vec<Card> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, cards);
+        assert_eq!(back[1].keyword, *b"BITPIX  ");
+    }
+
+    #[test]
+    fn overlong_keyword_is_rejected() {
+        let json = r#"{"keyword":"TOOLONGKEY","value":null,"comment":null}"#;
+        assert!(serde_json::from_str::<Card>(json).is_err());
+    }
+}
@@ -3,20 +3,21 @@
 //! Provides functions to extract image pixel data from FITS byte streams,
 //! with support for all standard BITPIX types and BSCALE/BZERO calibration.
 
+use alloc::borrow::Cow;
 use alloc::vec;
 use alloc::vec::Vec;
 
 use bytemuck::pod_collect_to_vec;
 
-use crate::block::padded_byte_len;
+use crate::block::{pad_to_block, Serialized, DATA_PAD_BYTE};
 use crate::endian::{
     buf_f32_native_to_be, buf_f64_native_to_be, buf_i16_native_to_be, buf_i32_native_to_be,
-    buf_i64_native_to_be,
+    buf_i64_native_to_be, read_f32_be, read_f64_be, read_i16_be, read_i32_be, read_i64_be,
 };
 use crate::error::{Error, Result};
 use crate::hdu::{Hdu, HduInfo};
-use crate::header::{serialize_header, Card};
-use crate::primary::build_primary_header;
+use crate::header::{keyword_value, serialize_header, Card};
+use crate::primary::{build_primary_header, VALID_BITPIX};
 use crate::value::Value;
 
 /// Image pixel data extracted from a FITS HDU, typed by BITPIX.
@@ -34,6 +35,68 @@ pub enum ImageData {
     F32(Vec<f32>),
     /// BITPIX = -64: IEEE 754 double-precision floats.
     F64(Vec<f64>),
+    /// BITPIX = 32 with `BZERO = 2^31`: unsigned 32-bit integers.
+    ///
+    /// Holds the physical values, offset already removed. Only produced by
+    /// [`read_image_data_unsigned`]; it serializes back to the stored
+    /// BITPIX = 32 form, and the image builders add the matching
+    /// `BSCALE = 1` and `BZERO = 2147483648` keywords.
+    U32(Vec<u32>),
+}
+
+impl ImageData {
+    /// The BITPIX of the on-disk form of this data.
+    ///
+    /// [`ImageData::U32`] is stored as BITPIX = 32.
+    pub fn bitpix(&self) -> i64 {
+        match self {
+            ImageData::U8(_) => 8,
+            ImageData::I16(_) => 16,
+            ImageData::I32(_) | ImageData::U32(_) => 32,
+            ImageData::I64(_) => 64,
+            ImageData::F32(_) => -32,
+            ImageData::F64(_) => -64,
+        }
+    }
+
+    /// Attach axis lengths (NAXIS1 first) to the pixel data.
+    ///
+    /// Fails with [`Error::DimensionMismatch`] unless the product of `naxes`
+    /// equals the number of pixels. Empty `naxes` describes an empty image.
+    pub fn with_shape(self, naxes: &[usize]) -> Result<ShapedImage> {
+        check_pixel_count(naxes, image_data_len(&self))?;
+        Ok(ShapedImage {
+            data: self,
+            naxes: naxes.to_vec(),
+        })
+    }
+}
+
+/// Image pixel data together with axis lengths that are known to match it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapedImage {
+    data: ImageData,
+    naxes: Vec<usize>,
+}
+
+impl ShapedImage {
+    /// Axis lengths, NAXIS1 first.
+    pub fn naxes(&self) -> &[usize] {
+        &self.naxes
+    }
+
+    /// Consume the image and return the pixel data.
+    pub fn into_data(self) -> ImageData {
+        self.data
+    }
+}
+
+impl core::ops::Deref for ShapedImage {
+    type Target = ImageData;
+
+    fn deref(&self) -> &ImageData {
+        &self.data
+    }
 }
 
 /// Returns the image dimensions (NAXESn values) from an HDU.
@@ -48,6 +111,28 @@ pub fn image_dimensions(hdu: &Hdu) -> Result<Vec<usize>> {
     }
 }
 
+/// Drop the size-1 axes from `naxes`, e.g. `[512, 512, 1]` becomes
+/// `[512, 512]`.
+///
+/// Trivial axes do not change the FITS pixel order, so data read with the
+/// original dimensions can be indexed with the squeezed ones unchanged.
+/// An image whose axes are all 1 keeps a single axis of length 1.
+pub fn squeeze_dimensions(naxes: &[usize]) -> Vec<usize> {
+    let squeezed: Vec<usize> = naxes.iter().copied().filter(|&n| n != 1).collect();
+    if squeezed.is_empty() && !naxes.is_empty() {
+        return vec![1];
+    }
+    squeezed
+}
+
+/// Returns the image dimensions of an HDU with size-1 axes removed.
+///
+/// See [`squeeze_dimensions`]; the pixel data layout is the same as for
+/// [`image_dimensions`].
+pub fn image_dimensions_squeezed(hdu: &Hdu) -> Result<Vec<usize>> {
+    image_dimensions(hdu).map(|naxes| squeeze_dimensions(&naxes))
+}
+
 /// Extracts the BITPIX value from an HDU info, returning an error for
 /// non-image HDU types.
 fn hdu_bitpix(hdu: &Hdu) -> Result<i64> {
@@ -62,72 +147,121 @@ fn hdu_bitpix(hdu: &Hdu) -> Result<i64> {
 ///
 /// Converts big-endian on-disk bytes to native-endian typed arrays.
 /// Returns an `ImageData` enum variant matching the BITPIX type.
+/// Tile-compressed HDUs (e.g. from `.fits.fz` files) are decompressed with
+/// [`read_tiled_image`](crate::tiled::read_tiled_image).
 pub fn read_image_data(fits_data: &[u8], hdu: &Hdu) -> Result<ImageData> {
     if matches!(&hdu.info, HduInfo::CompressedImage { .. }) {
         return crate::tiled::read_tiled_image(fits_data, hdu);
     }
+    let (bitpix, raw) = raw_image_bytes(fits_data, hdu)?;
+    decode_pixels(&raw, bitpix)
+}
+
+/// Read image pixel data into `scratch`, reusing its allocation.
+///
+/// Behaves like [`read_image_data`], but when `scratch` already holds the
+/// variant matching the HDU's BITPIX its vector is cleared and refilled, so
+/// reading many same-sized images in a loop allocates only once. On a
+/// BITPIX mismatch `scratch` is replaced with a freshly decoded value.
+pub fn read_image_data_reuse(fits_data: &[u8], hdu: &Hdu, scratch: &mut ImageData) -> Result<()> {
+    if matches!(&hdu.info, HduInfo::CompressedImage { .. }) {
+        *scratch = crate::tiled::read_tiled_image(fits_data, hdu)?;
+        return Ok(());
+    }
+    // Decode little-endian data straight into `scratch`, without a swapped copy.
+    let (bitpix, raw) = stored_image_bytes(fits_data, hdu)?;
+    let le = hdu.little_endian;
+    match (bitpix, &mut *scratch) {
+        (8, ImageData::U8(v)) => {
+            v.clear();
+            v.extend_from_slice(raw);
+        }
+        (16, ImageData::I16(v)) => refill(v, raw, le, i16::from_le_bytes, i16::from_be_bytes),
+        (32, ImageData::I32(v)) => refill(v, raw, le, i32::from_le_bytes, i32::from_be_bytes),
+        (64, ImageData::I64(v)) => refill(v, raw, le, i64::from_le_bytes, i64::from_be_bytes),
+        (-32, ImageData::F32(v)) => refill(v, raw, le, f32::from_le_bytes, f32::from_be_bytes),
+        (-64, ImageData::F64(v)) => refill(v, raw, le, f64::from_le_bytes, f64::from_be_bytes),
+        _ => {
+            let raw = big_endian_pixels(raw, bytes_per_pixel(bitpix)?, hdu);
+            *scratch = decode_pixels(&raw, bitpix)?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace the contents of `out` with values decoded from `raw`, read
+/// little-endian if `le` is set and big-endian otherwise.
+fn refill<T, const N: usize>(
+    out: &mut Vec<T>,
+    raw: &[u8],
+    le: bool,
+    from_le: fn([u8; N]) -> T,
+    from_be: fn([u8; N]) -> T,
+) {
+    let from_bytes = if le { from_le } else { from_be };
+    out.clear();
+    out.extend(
+        raw.chunks_exact(N)
+            .map(|c| from_bytes(c.try_into().expect("chunk of N bytes"))),
+    );
+}
+
+/// BITPIX and the big-endian data bytes of an uncompressed image HDU.
+fn raw_image_bytes<'a>(fits_data: &'a [u8], hdu: &Hdu) -> Result<(i64, Cow<'a, [u8]>)> {
+    let (bitpix, raw) = stored_image_bytes(fits_data, hdu)?;
+    Ok((
+        bitpix,
+        big_endian_pixels(raw, bytes_per_pixel(bitpix)?, hdu),
+    ))
+}
+
+/// BITPIX and the data bytes of an uncompressed image HDU in stored order.
+fn stored_image_bytes<'a>(fits_data: &'a [u8], hdu: &Hdu) -> Result<(i64, &'a [u8])> {
     let bitpix = hdu_bitpix(hdu)?;
+    let bpp = bytes_per_pixel(bitpix)?;
     let data_len = hdu.data_len;
-
+    // A data length that is not a whole number of pixels means a truncated
+    // or misdeclared segment; refuse it rather than invent a last pixel.
+    if !data_len.is_multiple_of(bpp) {
+        return Err(Error::UnexpectedEof);
+    }
     if data_len == 0 {
-        return match bitpix {
-            8 => Ok(ImageData::U8(Vec::new())),
-            16 => Ok(ImageData::I16(Vec::new())),
-            32 => Ok(ImageData::I32(Vec::new())),
-            64 => Ok(ImageData::I64(Vec::new())),
-            -32 => Ok(ImageData::F32(Vec::new())),
-            -64 => Ok(ImageData::F64(Vec::new())),
-            other => Err(Error::InvalidBitpix(other)),
-        };
+        return Ok((bitpix, &[]));
     }
-
     let end = hdu.data_start + data_len;
     if end > fits_data.len() {
         return Err(Error::UnexpectedEof);
     }
+    Ok((bitpix, &fits_data[hdu.data_start..end]))
+}
 
-    let raw = &fits_data[hdu.data_start..end];
+/// The pixel bytes `raw` in big-endian order. Data of an HDU marked
+/// [`Hdu::little_endian`] is copied with the bytes of each pixel reversed.
+fn big_endian_pixels<'a>(raw: &'a [u8], bpp: usize, hdu: &Hdu) -> Cow<'a, [u8]> {
+    if !hdu.little_endian || bpp == 1 {
+        return Cow::Borrowed(raw);
+    }
+    let mut swapped = raw.to_vec();
+    swapped.chunks_exact_mut(bpp).for_each(<[u8]>::reverse);
+    Cow::Owned(swapped)
+}
 
-    match bitpix {
-        8 => Ok(ImageData::U8(raw.to_vec())),
-        16 => {
-            // Interpret big-endian bytes as i16, collect into properly-aligned Vec<i16>,
-            // then swap each element to native endianness in place.
-            let mut pixels: Vec<i16> = pod_collect_to_vec(raw);
-            for v in &mut pixels {
-                *v = i16::from_be(*v);
-            }
-            Ok(ImageData::I16(pixels))
-        }
-        32 => {
-            let mut pixels: Vec<i32> = pod_collect_to_vec(raw);
-            for v in &mut pixels {
-                *v = i32::from_be(*v);
-            }
-            Ok(ImageData::I32(pixels))
-        }
-        64 => {
-            let mut pixels: Vec<i64> = pod_collect_to_vec(raw);
-            for v in &mut pixels {
-                *v = i64::from_be(*v);
-            }
-            Ok(ImageData::I64(pixels))
-        }
-        -32 => {
-            let mut pixels: Vec<f32> = pod_collect_to_vec(raw);
-            for v in &mut pixels {
-                *v = f32::from_bits(u32::from_be(v.to_bits()));
-            }
-            Ok(ImageData::F32(pixels))
-        }
-        -64 => {
-            let mut pixels: Vec<f64> = pod_collect_to_vec(raw);
-            for v in &mut pixels {
-                *v = f64::from_bits(u64::from_be(v.to_bits()));
-            }
-            Ok(ImageData::F64(pixels))
-        }
-        other => Err(Error::InvalidBitpix(other)),
+/// Read image pixel data, returning unsigned 32-bit images as
+/// [`ImageData::U32`].
+///
+/// A BITPIX = 32 image with `BZERO = 2147483648` and `BSCALE = 1` is the
+/// FITS convention for unsigned 32-bit integers. Its pixels are recovered
+/// exactly by flipping the sign bit of each stored value, with no floating
+/// point involved. Any other image is returned as [`read_image_data`]
+/// returns it.
+pub fn read_image_data_unsigned(fits_data: &[u8], hdu: &Hdu) -> Result<ImageData> {
+    let data = read_image_data(fits_data, hdu)?;
+    let (bscale, bzero) = extract_bscale_bzero(&hdu.cards);
+    match data {
+        ImageData::I32(v) if bscale == 1.0 && bzero == 2_147_483_648.0 => Ok(ImageData::U32(
+            v.into_iter().map(|p| (p as u32) ^ 0x8000_0000).collect(),
+        )),
+        other => Ok(other),
     }
 }
 
@@ -139,7 +273,7 @@ pub fn read_image_data_into_f32(fits_data: &[u8], hdu: &Hdu, buf: &mut [f32]) ->
     let bitpix = hdu_bitpix(hdu)?;
     let bpp = bytes_per_pixel(bitpix)?;
     let data_len = hdu.data_len;
-    let npixels = if bpp > 0 { data_len / bpp } else { 0 };
+    let npixels = data_len.checked_div(bpp).unwrap_or(0);
 
     if buf.len() != npixels {
         return Err(Error::InvalidValue);
@@ -153,7 +287,7 @@ pub fn read_image_data_into_f32(fits_data: &[u8], hdu: &Hdu, buf: &mut [f32]) ->
     if end > fits_data.len() {
         return Err(Error::UnexpectedEof);
     }
-    let raw = &fits_data[hdu.data_start..end];
+    let raw = big_endian_pixels(&fits_data[hdu.data_start..end], bpp, hdu);
 
     match bitpix {
         8 => {
@@ -199,7 +333,7 @@ pub fn read_image_data_into_f64(fits_data: &[u8], hdu: &Hdu, buf: &mut [f64]) ->
     let bitpix = hdu_bitpix(hdu)?;
     let bpp = bytes_per_pixel(bitpix)?;
     let data_len = hdu.data_len;
-    let npixels = if bpp > 0 { data_len / bpp } else { 0 };
+    let npixels = data_len.checked_div(bpp).unwrap_or(0);
 
     if buf.len() != npixels {
         return Err(Error::InvalidValue);
@@ -213,7 +347,7 @@ pub fn read_image_data_into_f64(fits_data: &[u8], hdu: &Hdu, buf: &mut [f64]) ->
     if end > fits_data.len() {
         return Err(Error::UnexpectedEof);
     }
-    let raw = &fits_data[hdu.data_start..end];
+    let raw = big_endian_pixels(&fits_data[hdu.data_start..end], bpp, hdu);
 
     match bitpix {
         8 => {
@@ -254,7 +388,9 @@ pub fn read_image_data_into_f64(fits_data: &[u8], hdu: &Hdu, buf: &mut [f64]) ->
 /// Apply BSCALE/BZERO calibration to raw image data.
 ///
 /// Computes `physical = bzero + bscale * pixel` for every pixel and returns
-/// the results as `Vec<f64>`.
+/// the results as `Vec<f64>`. [`ImageData::U32`] pixels already have the
+/// `BZERO = 2^31` offset applied and are returned as they are, so passing
+/// the HDU's own BSCALE/BZERO does not apply it twice.
 pub fn apply_bscale_bzero(data: &ImageData, bscale: f64, bzero: f64) -> Vec<f64> {
     match data {
         ImageData::U8(v) => v.iter().map(|&p| bzero + bscale * (p as f64)).collect(),
@@ -263,6 +399,7 @@ pub fn apply_bscale_bzero(data: &ImageData, bscale: f64, bzero: f64) -> Vec<f64>
         ImageData::I64(v) => v.iter().map(|&p| bzero + bscale * (p as f64)).collect(),
         ImageData::F32(v) => v.iter().map(|&p| bzero + bscale * (p as f64)).collect(),
         ImageData::F64(v) => v.iter().map(|&p| bzero + bscale * p).collect(),
+        ImageData::U32(v) => v.iter().map(|&p| p as f64).collect(),
     }
 }
 
@@ -287,16 +424,10 @@ pub fn extract_blank(cards: &[Card]) -> Option<i64> {
 
 /// Find an integer-valued keyword in the card list.
 fn find_integer_keyword(cards: &[Card], keyword: &str) -> Option<i64> {
-    cards.iter().find_map(|c| {
-        if c.keyword_str() == keyword {
-            match &c.value {
-                Some(Value::Integer(n)) => Some(*n),
-                _ => None,
-            }
-        } else {
-            None
-        }
-    })
+    match keyword_value(cards, keyword) {
+        Some(Value::Integer(n)) => Some(*n),
+        _ => None,
+    }
 }
 
 /// Create a boolean mask indicating which pixels are undefined (BLANK).
@@ -335,6 +466,14 @@ pub fn blank_mask(data: &ImageData, blank: Option<i64>) -> Option<Vec<bool>> {
         },
         ImageData::F32(v) => v.iter().map(|p| p.is_nan()).collect(),
         ImageData::F64(v) => v.iter().map(|p| p.is_nan()).collect(),
+        // BLANK names the stored value, before the 2^31 offset.
+        ImageData::U32(v) => match blank {
+            Some(b) => {
+                let bv = (b as u32) ^ 0x8000_0000;
+                v.iter().map(|&p| p == bv).collect()
+            }
+            None => return None,
+        },
     };
     if mask.iter().any(|&b| b) {
         Some(mask)
@@ -346,17 +485,25 @@ pub fn blank_mask(data: &ImageData, blank: Option<i64>) -> Option<Vec<bool>> {
 /// Find a float-valued keyword in the card list, accepting both Float and
 /// Integer values (integers are promoted to f64).
 fn find_float_keyword(cards: &[Card], keyword: &str) -> Option<f64> {
-    cards.iter().find_map(|c| {
-        if c.keyword_str() == keyword {
-            match &c.value {
-                Some(Value::Float(f)) => Some(*f),
-                Some(Value::Integer(n)) => Some(*n as f64),
-                _ => None,
-            }
-        } else {
-            None
-        }
-    })
+    match keyword_value(cards, keyword) {
+        Some(Value::Float(f)) => Some(*f),
+        Some(Value::Integer(n)) => Some(*n as f64),
+        _ => None,
+    }
+}
+
+/// Read raw image pixels together with their row-major (C order) shape.
+///
+/// FITS stores NAXIS1 fastest, which is exactly C order once the axes are
+/// listed slowest first, so the pixels are returned as stored and the shape
+/// is `[..., NAXIS2, NAXIS1]` (e.g. `[planes, rows, cols]`). Element
+/// `[r][c]` of a 2D image is at index `r * NAXIS1 + c` and is the FITS pixel
+/// `(c + 1, r + 1)`.
+pub fn read_image_data_c_order(fits_data: &[u8], hdu: &Hdu) -> Result<(ImageData, Vec<usize>)> {
+    let mut shape = image_dimensions(hdu)?;
+    shape.reverse();
+    let data = read_image_data(fits_data, hdu)?;
+    Ok((data, shape))
 }
 
 /// Read image data with BSCALE/BZERO calibration applied.
@@ -365,6 +512,14 @@ fn find_float_keyword(cards: &[Card], keyword: &str) -> Option<f64> {
 /// header cards, and returns calibrated physical values as `Vec<f64>`.
 /// Pixels matching the BLANK keyword value are set to NaN.
 pub fn read_image_physical(fits_data: &[u8], hdu: &Hdu) -> Result<Vec<f64>> {
+    read_image_raw_and_physical(fits_data, hdu).map(|(_, physical)| physical)
+}
+
+/// Read both the raw pixels and their calibrated physical values.
+///
+/// The data is decoded once; the physical values are derived from the raw
+/// pixels exactly as [`read_image_physical`] does, with BLANK pixels as NaN.
+pub fn read_image_raw_and_physical(fits_data: &[u8], hdu: &Hdu) -> Result<(ImageData, Vec<f64>)> {
     let raw = read_image_data(fits_data, hdu)?;
     let (bscale, bzero) = extract_bscale_bzero(&hdu.cards);
     let blank = extract_blank(&hdu.cards);
@@ -376,69 +531,350 @@ pub fn read_image_physical(fits_data: &[u8], hdu: &Hdu) -> Result<Vec<f64>> {
             }
         }
     }
+    Ok((raw, physical))
+}
+
+/// SI prefixes accepted in front of a base unit, with their power of ten.
+const UNIT_PREFIXES: &[(&str, i32)] = &[
+    ("da", 1),
+    ("y", -24),
+    ("z", -21),
+    ("a", -18),
+    ("f", -15),
+    ("p", -12),
+    ("n", -9),
+    ("u", -6),
+    ("\u{b5}", -6),
+    ("m", -3),
+    ("c", -2),
+    ("d", -1),
+    ("h", 2),
+    ("k", 3),
+    ("M", 6),
+    ("G", 9),
+    ("T", 12),
+    ("P", 15),
+    ("E", 18),
+];
+
+/// Base units that may carry an SI prefix.
+const BASE_UNITS: &[&str] = &[
+    "Jy", "Jy/beam", "Jy/pixel", "K", "Hz", "m", "s", "g", "W", "eV", "erg", "erg/s", "Ry",
+];
+
+/// Split a unit string into its base unit and power-of-ten scale.
+fn split_unit_prefix(unit: &str) -> Option<(&str, i32)> {
+    let unit = unit.trim();
+    if BASE_UNITS.contains(&unit) {
+        return Some((unit, 0));
+    }
+    UNIT_PREFIXES.iter().find_map(|&(prefix, exp)| {
+        unit.strip_prefix(prefix)
+            .filter(|base| BASE_UNITS.contains(base))
+            .map(|base| (base, exp))
+    })
+}
+
+/// Read physical pixel values converted from the HDU's `BUNIT` to `target`.
+///
+/// Both units must be the same base unit with an optional SI prefix (e.g.
+/// `mJy` and `Jy`, or `kHz` and `MHz`). Returns `MissingKeyword` if the
+/// header has no BUNIT and `InvalidValue` for unknown or incompatible units.
+pub fn read_image_physical_in_unit(fits_data: &[u8], hdu: &Hdu, target: &str) -> Result<Vec<f64>> {
+    let bunit = match keyword_value(&hdu.cards, "BUNIT") {
+        Some(Value::String(s)) => s.as_str(),
+        _ => return Err(Error::MissingKeyword("BUNIT")),
+    };
+    let (from_base, from_exp) = split_unit_prefix(bunit).ok_or(Error::InvalidValue)?;
+    let (to_base, to_exp) = split_unit_prefix(target).ok_or(Error::InvalidValue)?;
+    if from_base != to_base {
+        return Err(Error::InvalidValue);
+    }
+
+    let mut physical = read_image_physical(fits_data, hdu)?;
+    if from_exp != to_exp {
+        let factor = libm::pow(10.0, (from_exp - to_exp) as f64);
+        physical.iter_mut().for_each(|v| *v *= factor);
+    }
     Ok(physical)
 }
 
+/// Visit every pixel of an image HDU as a calibrated physical value.
+///
+/// `npixels_expected` must equal the pixel count of the HDU. BLANK pixels
+/// of integer images are passed to `f` as NaN.
+fn for_each_physical_pixel(
+    fits_data: &[u8],
+    hdu: &Hdu,
+    npixels_expected: usize,
+    mut f: impl FnMut(usize, f64),
+) -> Result<()> {
+    if matches!(&hdu.info, HduInfo::CompressedImage { .. }) {
+        let physical = read_image_physical(fits_data, hdu)?;
+        if physical.len() != npixels_expected {
+            return Err(Error::InvalidValue);
+        }
+        for (i, v) in physical.into_iter().enumerate() {
+            f(i, v);
+        }
+        return Ok(());
+    }
+
+    let bitpix = hdu_bitpix(hdu)?;
+    let bpp = bytes_per_pixel(bitpix)?;
+    let data_len = hdu.data_len;
+    let npixels = data_len.checked_div(bpp).unwrap_or(0);
+
+    if npixels_expected != npixels {
+        return Err(Error::InvalidValue);
+    }
+
+    if npixels == 0 {
+        return Ok(());
+    }
+
+    let end = hdu.data_start + data_len;
+    if end > fits_data.len() {
+        return Err(Error::UnexpectedEof);
+    }
+    let raw = big_endian_pixels(&fits_data[hdu.data_start..end], bpp, hdu);
+
+    let (bscale, bzero) = extract_bscale_bzero(&hdu.cards);
+    let blank = extract_blank(&hdu.cards);
+    let scale_int = |v: i64| {
+        if blank == Some(v) {
+            f64::NAN
+        } else {
+            bzero + bscale * (v as f64)
+        }
+    };
+
+    match bitpix {
+        8 => {
+            for (i, &b) in raw.iter().enumerate() {
+                f(i, scale_int(b as i64));
+            }
+        }
+        16 => {
+            for i in 0..npixels {
+                f(i, scale_int(read_i16_be(&raw[i * 2..]) as i64));
+            }
+        }
+        32 => {
+            for i in 0..npixels {
+                f(i, scale_int(read_i32_be(&raw[i * 4..]) as i64));
+            }
+        }
+        64 => {
+            for i in 0..npixels {
+                f(i, scale_int(read_i64_be(&raw[i * 8..])));
+            }
+        }
+        -32 => {
+            for i in 0..npixels {
+                let v = read_f32_be(&raw[i * 4..]) as f64;
+                f(i, bzero + bscale * v);
+            }
+        }
+        -64 => {
+            for i in 0..npixels {
+                let v = read_f64_be(&raw[i * 8..]);
+                f(i, bzero + bscale * v);
+            }
+        }
+        other => return Err(Error::InvalidBitpix(other)),
+    }
+    Ok(())
+}
+
+/// Read calibrated image data into a pre-allocated `f32` buffer.
+///
+/// Like [`read_image_physical`], but fills `buf` in a single pass without
+/// allocating. BSCALE/BZERO are applied and BLANK pixels become NaN. The
+/// buffer must have exactly the right number of elements for the image.
+pub fn read_image_physical_into_f32(fits_data: &[u8], hdu: &Hdu, buf: &mut [f32]) -> Result<()> {
+    for_each_physical_pixel(fits_data, hdu, buf.len(), |i, v| buf[i] = v as f32)
+}
+
+/// Read calibrated image data into a pre-allocated `f64` buffer.
+///
+/// Like [`read_image_physical`], but fills `buf` in a single pass without
+/// allocating. BSCALE/BZERO are applied and BLANK pixels become NaN. The
+/// buffer must have exactly the right number of elements for the image.
+pub fn read_image_physical_into_f64(fits_data: &[u8], hdu: &Hdu, buf: &mut [f64]) -> Result<()> {
+    for_each_physical_pixel(fits_data, hdu, buf.len(), |i, v| buf[i] = v)
+}
+
+/// Read calibrated image data as `i32`, failing instead of clamping.
+///
+/// BSCALE/BZERO are applied and each value is rounded to the nearest
+/// integer (halves away from zero). Returns `Error::InvalidValue` if any
+/// rounded value falls outside the `i32` range, or if a pixel is NaN and
+/// the header has no BLANK. When BLANK is present, undefined pixels are
+/// returned as the BLANK value itself, which must then fit in `i32`.
+pub fn read_image_physical_i32(fits_data: &[u8], hdu: &Hdu) -> Result<Vec<i32>> {
+    let npixels = image_pixel_count(hdu)?;
+    let blank = extract_blank(&hdu.cards);
+    let mut out = vec![0i32; npixels];
+    let mut in_range = true;
+    for_each_physical_pixel(fits_data, hdu, npixels, |i, v| {
+        let converted = if v.is_nan() {
+            blank.and_then(|b| i32::try_from(b).ok())
+        } else {
+            let rounded = libm::round(v);
+            (rounded >= i32::MIN as f64 && rounded <= i32::MAX as f64).then_some(rounded as i32)
+        };
+        match converted {
+            Some(n) => out[i] = n,
+            None => in_range = false,
+        }
+    })?;
+    if !in_range {
+        return Err(Error::InvalidValue);
+    }
+    Ok(out)
+}
+
+/// Number of histogram bins used by [`image_percentiles`].
+const PERCENTILE_BINS: usize = 65536;
+
+/// Product of the axis lengths `dims`, or 0 when there are no axes.
+///
+/// Returns `Error::InvalidHeader` if the product overflows `usize`, as it
+/// can for a hostile header, instead of wrapping to a small count.
+pub(crate) fn checked_pixel_count(dims: &[usize]) -> Result<usize> {
+    if dims.is_empty() {
+        return Ok(0);
+    }
+    dims.iter()
+        .try_fold(1usize, |acc, &d| acc.checked_mul(d))
+        .ok_or(Error::InvalidHeader("pixel count overflow"))
+}
+
+/// Number of pixels in an image HDU, as used by the physical pixel readers.
+fn image_pixel_count(hdu: &Hdu) -> Result<usize> {
+    match &hdu.info {
+        HduInfo::CompressedImage { znaxes, .. } => checked_pixel_count(znaxes),
+        _ => {
+            let bpp = bytes_per_pixel(hdu_bitpix(hdu)?)?;
+            Ok(hdu.data_len.checked_div(bpp).unwrap_or(0))
+        }
+    }
+}
+
+/// Compute the `lo` and `hi` percentiles (0 to 100) of the physical pixel
+/// values, e.g. `(0.5, 99.5)` for display clipping.
+///
+/// BSCALE/BZERO are applied; NaN and BLANK pixels are skipped. Pixels are
+/// streamed twice, first for the value range and then into a fixed-size
+/// histogram, so the result is accurate to within one bin
+/// (`(max - min) / 65536`). Returns `Error::InvalidValue` if the percentiles
+/// are out of range or the image has no valid pixels.
+pub fn image_percentiles(fits_data: &[u8], hdu: &Hdu, lo: f64, hi: f64) -> Result<(f64, f64)> {
+    if !(0.0..=100.0).contains(&lo) || !(0.0..=100.0).contains(&hi) || lo > hi {
+        return Err(Error::InvalidValue);
+    }
+    let npixels = image_pixel_count(hdu)?;
+
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    let mut count = 0usize;
+    for_each_physical_pixel(fits_data, hdu, npixels, |_, v| {
+        if v.is_finite() {
+            min = min.min(v);
+            max = max.max(v);
+            count += 1;
+        }
+    })?;
+
+    if count == 0 {
+        return Err(Error::InvalidValue);
+    }
+    if min == max {
+        return Ok((min, max));
+    }
+
+    let width = (max - min) / PERCENTILE_BINS as f64;
+    let mut histogram = vec![0usize; PERCENTILE_BINS];
+    for_each_physical_pixel(fits_data, hdu, npixels, |_, v| {
+        if v.is_finite() {
+            let bin = ((v - min) / width) as usize;
+            histogram[bin.min(PERCENTILE_BINS - 1)] += 1;
+        }
+    })?;
+
+    let value_at = |p: f64| -> f64 {
+        let rank = p / 100.0 * count as f64;
+        let mut cumulative = 0usize;
+        for (bin, &n) in histogram.iter().enumerate() {
+            if n > 0 && (cumulative + n) as f64 >= rank {
+                let frac = (rank - cumulative as f64) / n as f64;
+                return min + (bin as f64 + frac.clamp(0.0, 1.0)) * width;
+            }
+            cumulative += n;
+        }
+        max
+    };
+
+    Ok((value_at(lo).max(min), value_at(hi).min(max)))
+}
+
 // ---- Image write functions ----
 
 /// Serialize a slice of `u8` pixel values into a block-padded FITS data segment.
 pub fn serialize_image_u8(pixels: &[u8]) -> Vec<u8> {
-    let raw_len = pixels.len();
-    let padded_len = padded_byte_len(raw_len);
-    let mut buf = vec![0u8; padded_len];
-    buf[..raw_len].copy_from_slice(pixels);
-    buf
+    pad_to_block(pixels.to_vec(), DATA_PAD_BYTE)
 }
 
 /// Serialize a slice of `i16` pixel values into big-endian, block-padded FITS data.
 pub fn serialize_image_i16(pixels: &[i16]) -> Vec<u8> {
-    let raw_len = pixels.len() * 2;
-    let padded_len = padded_byte_len(raw_len);
     // Copy pixel bytes via bytemuck, then swap endianness in-place
     let mut buf: Vec<u8> = pod_collect_to_vec(pixels);
     buf_i16_native_to_be(&mut buf);
-    buf.resize(padded_len, 0);
-    buf
+    pad_to_block(buf, DATA_PAD_BYTE)
 }
 
 /// Serialize a slice of `i32` pixel values into big-endian, block-padded FITS data.
 pub fn serialize_image_i32(pixels: &[i32]) -> Vec<u8> {
-    let raw_len = pixels.len() * 4;
-    let padded_len = padded_byte_len(raw_len);
     let mut buf: Vec<u8> = pod_collect_to_vec(pixels);
     buf_i32_native_to_be(&mut buf);
-    buf.resize(padded_len, 0);
-    buf
+    pad_to_block(buf, DATA_PAD_BYTE)
 }
 
 /// Serialize a slice of `i64` pixel values into big-endian, block-padded FITS data.
 pub fn serialize_image_i64(pixels: &[i64]) -> Vec<u8> {
-    let raw_len = pixels.len() * 8;
-    let padded_len = padded_byte_len(raw_len);
     let mut buf: Vec<u8> = pod_collect_to_vec(pixels);
     buf_i64_native_to_be(&mut buf);
-    buf.resize(padded_len, 0);
-    buf
+    pad_to_block(buf, DATA_PAD_BYTE)
 }
 
 /// Serialize a slice of `f32` pixel values into big-endian, block-padded FITS data.
 pub fn serialize_image_f32(pixels: &[f32]) -> Vec<u8> {
-    let raw_len = pixels.len() * 4;
-    let padded_len = padded_byte_len(raw_len);
     let mut buf: Vec<u8> = pod_collect_to_vec(pixels);
     buf_f32_native_to_be(&mut buf);
-    buf.resize(padded_len, 0);
-    buf
+    pad_to_block(buf, DATA_PAD_BYTE)
 }
 
 /// Serialize a slice of `f64` pixel values into big-endian, block-padded FITS data.
 pub fn serialize_image_f64(pixels: &[f64]) -> Vec<u8> {
-    let raw_len = pixels.len() * 8;
-    let padded_len = padded_byte_len(raw_len);
     let mut buf: Vec<u8> = pod_collect_to_vec(pixels);
     buf_f64_native_to_be(&mut buf);
-    buf.resize(padded_len, 0);
-    buf
+    pad_to_block(buf, DATA_PAD_BYTE)
+}
+
+/// Serialize image pixels like [`serialize_image`], also returning the
+/// unpadded length (pixel count times bytes per pixel).
+pub fn serialize_image_with_len(data: &ImageData) -> Serialized {
+    let bytes_per_pixel = match data {
+        ImageData::U8(_) => 1,
+        ImageData::I16(_) => 2,
+        ImageData::I32(_) | ImageData::F32(_) | ImageData::U32(_) => 4,
+        ImageData::I64(_) | ImageData::F64(_) => 8,
+    };
+    Serialized {
+        raw_len: image_data_len(data) * bytes_per_pixel,
+        bytes: serialize_image(data),
+    }
 }
 
 /// Serialize an `ImageData` variant into block-padded FITS data bytes.
@@ -450,14 +886,49 @@ pub fn serialize_image(data: &ImageData) -> Vec<u8> {
         ImageData::I64(v) => serialize_image_i64(v),
         ImageData::F32(v) => serialize_image_f32(v),
         ImageData::F64(v) => serialize_image_f64(v),
+        ImageData::U32(v) => {
+            let stored: Vec<i32> = v.iter().map(|&p| (p ^ 0x8000_0000) as i32).collect();
+            serialize_image_i32(&stored)
+        }
     }
 }
 
+fn image_data_len(data: &ImageData) -> usize {
+    match data {
+        ImageData::U8(v) => v.len(),
+        ImageData::I16(v) => v.len(),
+        ImageData::I32(v) => v.len(),
+        ImageData::I64(v) => v.len(),
+        ImageData::F32(v) => v.len(),
+        ImageData::F64(v) => v.len(),
+        ImageData::U32(v) => v.len(),
+    }
+}
+
+/// Check that `got` pixels fill the axes exactly. No axes means no pixels.
+fn check_pixel_count(naxes: &[usize], got: usize) -> Result<()> {
+    let expected = if naxes.is_empty() {
+        0
+    } else {
+        naxes
+            .iter()
+            .try_fold(1usize, |acc, &d| acc.checked_mul(d))
+            .ok_or(Error::InvalidHeader("pixel count overflow"))?
+    };
+    if expected != got {
+        return Err(Error::DimensionMismatch { expected, got });
+    }
+    Ok(())
+}
+
 /// Build a complete image HDU (header + data) as a byte vector.
-pub fn build_image_hdu(bitpix: i64, naxes: &[usize], data: &ImageData) -> Result<Vec<u8>> {
-    let cards = build_primary_header(bitpix, naxes)?;
+///
+/// The NAXISn keywords come from the image's shape.
+pub fn build_image_hdu(bitpix: i64, image: &ShapedImage) -> Result<Vec<u8>> {
+    let mut cards = build_primary_header(bitpix, image.naxes())?;
+    push_unsigned_offset(&mut cards, image);
     let header_bytes = serialize_header(&cards)?;
-    let data_bytes = serialize_image(data);
+    let data_bytes = serialize_image(image);
 
     let mut hdu = Vec::with_capacity(header_bytes.len() + data_bytes.len());
     hdu.extend_from_slice(&header_bytes);
@@ -465,6 +936,33 @@ pub fn build_image_hdu(bitpix: i64, naxes: &[usize], data: &ImageData) -> Result
     Ok(hdu)
 }
 
+/// Build a complete single-HDU FITS file holding `data` as the primary
+/// image, e.g. for test fixtures and examples.
+///
+/// `naxes` lists the axis lengths, NAXIS1 first, and must account for every
+/// pixel. Returns `Error::InvalidValue` if the `data` variant does not match
+/// `bitpix`.
+pub fn minimal_image_fits(bitpix: i64, naxes: &[usize], data: &ImageData) -> Result<Vec<u8>> {
+    let image = data.clone().with_shape(naxes)?;
+    if VALID_BITPIX.contains(&bitpix) && !bitpix_matches(bitpix, &image) {
+        return Err(Error::InvalidValue);
+    }
+    build_image_hdu(bitpix, &image)
+}
+
+/// Whether `data` holds the pixel type that `bitpix` describes.
+fn bitpix_matches(bitpix: i64, data: &ImageData) -> bool {
+    matches!(
+        (bitpix, data),
+        (8, ImageData::U8(_))
+            | (16, ImageData::I16(_))
+            | (32, ImageData::I32(_) | ImageData::U32(_))
+            | (64, ImageData::I64(_))
+            | (-32, ImageData::F32(_))
+            | (-64, ImageData::F64(_))
+    )
+}
+
 /// Reverse BSCALE/BZERO calibration: convert physical f64 values to raw
 /// integer values using `raw = (physical - bzero) / bscale`.
 ///
@@ -540,22 +1038,12 @@ pub fn build_image_hdu_with_scaling(
     bscale: f64,
     bzero: f64,
 ) -> Result<Vec<u8>> {
+    check_pixel_count(naxes, physical.len())?;
     let raw = reverse_bscale_bzero(physical, bscale, bzero, bitpix)?;
     let mut cards = build_primary_header(bitpix, naxes)?;
 
     let is_non_default = bscale != 1.0 || bzero != 0.0;
     if is_non_default {
-        fn make_card(keyword: &str, value: Value) -> Card {
-            let mut kw = [b' '; 8];
-            let bytes = keyword.as_bytes();
-            let len = bytes.len().min(8);
-            kw[..len].copy_from_slice(&bytes[..len]);
-            Card {
-                keyword: kw,
-                value: Some(value),
-                comment: None,
-            }
-        }
         cards.push(make_card("BSCALE", Value::Float(bscale)));
         cards.push(make_card("BZERO", Value::Float(bzero)));
     }
@@ -569,6 +1057,58 @@ pub fn build_image_hdu_with_scaling(
     Ok(hdu)
 }
 
+/// Build a complete integer image HDU with a `BLANK` keyword.
+///
+/// Pixels equal to `blank` are written unchanged and mark undefined values;
+/// readers such as [`read_image_physical`] turn them into NaN. `bitpix`
+/// must be a positive (integer) BITPIX matching the image's data type, and
+/// `blank` must be representable in it.
+pub fn build_image_hdu_with_blank(bitpix: i64, image: &ShapedImage, blank: i64) -> Result<Vec<u8>> {
+    let range = match bitpix {
+        8 => 0..=i64::from(u8::MAX),
+        16 => i64::from(i16::MIN)..=i64::from(i16::MAX),
+        32 => i64::from(i32::MIN)..=i64::from(i32::MAX),
+        64 => i64::MIN..=i64::MAX,
+        other => return Err(Error::InvalidBitpix(other)),
+    };
+    if !bitpix_matches(bitpix, image) || !range.contains(&blank) {
+        return Err(Error::InvalidValue);
+    }
+
+    let mut cards = build_primary_header(bitpix, image.naxes())?;
+    push_unsigned_offset(&mut cards, image);
+    cards.push(make_card("BLANK", Value::Integer(blank)));
+
+    let header_bytes = serialize_header(&cards)?;
+    let data_bytes = serialize_image(image);
+
+    let mut hdu = Vec::with_capacity(header_bytes.len() + data_bytes.len());
+    hdu.extend_from_slice(&header_bytes);
+    hdu.extend_from_slice(&data_bytes);
+    Ok(hdu)
+}
+
+/// Append the `BSCALE = 1`, `BZERO = 2147483648` pair that marks stored
+/// 32-bit integers as unsigned when `data` is [`ImageData::U32`].
+fn push_unsigned_offset(cards: &mut Vec<Card>, data: &ImageData) {
+    if matches!(data, ImageData::U32(_)) {
+        cards.push(make_card("BSCALE", Value::Integer(1)));
+        cards.push(make_card("BZERO", Value::Integer(2_147_483_648)));
+    }
+}
+
+fn make_card(keyword: &str, value: Value) -> Card {
+    let mut kw = [b' '; 8];
+    let bytes = keyword.as_bytes();
+    let len = bytes.len().min(8);
+    kw[..len].copy_from_slice(&bytes[..len]);
+    Card {
+        keyword: kw,
+        value: Some(value),
+        comment: None,
+    }
+}
+
 // ---- Image region/section/row functions ----
 
 /// Returns the number of bytes per pixel for a given BITPIX value.
@@ -594,7 +1134,9 @@ fn hdu_bitpix_naxes(hdu: &Hdu) -> Result<(i64, &[usize])> {
 
 /// Decode a contiguous byte slice into an `ImageData` variant based on BITPIX.
 fn decode_pixels(raw: &[u8], bitpix: i64) -> Result<ImageData> {
-    bytes_per_pixel(bitpix)?; // validate
+    if !raw.len().is_multiple_of(bytes_per_pixel(bitpix)?) {
+        return Err(Error::UnexpectedEof);
+    }
     match bitpix {
         8 => Ok(ImageData::U8(raw.to_vec())),
         16 => {
@@ -632,8 +1174,106 @@ fn decode_pixels(raw: &[u8], bitpix: i64) -> Result<ImageData> {
             }
             Ok(ImageData::F64(pixels))
         }
-        _ => Err(Error::InvalidBitpix(bitpix)),
+        _ => Err(Error::InvalidBitpix(bitpix)),
+    }
+}
+
+/// Absolute byte offset within the file of the pixel at 0-based `coords`.
+///
+/// `coords` has one entry per axis with NAXIS1 first (FITS order). Useful for
+/// patching individual pixels in place. Tile-compressed images have no fixed
+/// pixel layout and are rejected.
+pub fn pixel_byte_offset(hdu: &Hdu, coords: &[usize]) -> Result<usize> {
+    if matches!(hdu.info, HduInfo::CompressedImage { .. }) {
+        return Err(Error::InvalidHeader(
+            "compressed image pixels are not addressable",
+        ));
+    }
+    let (bitpix, naxes) = hdu_bitpix_naxes(hdu)?;
+    let bpp = bytes_per_pixel(bitpix)?;
+
+    if naxes.is_empty() || coords.len() != naxes.len() {
+        return Err(Error::InvalidValue);
+    }
+
+    let mut flat = 0usize;
+    let mut stride = 1usize;
+    for (&c, &dim) in coords.iter().zip(naxes) {
+        if c >= dim {
+            return Err(Error::InvalidValue);
+        }
+        flat += c * stride;
+        stride *= dim;
+    }
+
+    Ok(hdu.data_start + flat * bpp)
+}
+
+/// Write one physical pixel value into `fits_data` in place.
+///
+/// The value is converted to a stored value by reverse-applying BSCALE/BZERO
+/// and encoded big-endian at the HDU's BITPIX. For integer BITPIX the stored
+/// value is rounded; NaN is written as BLANK when the header defines one, and
+/// values outside the BITPIX range are rejected with `InvalidValue`.
+pub fn write_pixel(fits_data: &mut [u8], hdu: &Hdu, coords: &[usize], value: f64) -> Result<()> {
+    let offset = pixel_byte_offset(hdu, coords)?;
+    let (bitpix, _) = hdu_bitpix_naxes(hdu)?;
+    let bpp = bytes_per_pixel(bitpix)?;
+    if offset + bpp > fits_data.len() {
+        return Err(Error::UnexpectedEof);
+    }
+
+    let (bscale, bzero) = extract_bscale_bzero(&hdu.cards);
+    let stored = (value - bzero) / bscale;
+    let dest = &mut fits_data[offset..offset + bpp];
+
+    if bitpix < 0 {
+        if bitpix == -32 {
+            crate::endian::write_f32_be(dest, stored as f32);
+        } else {
+            crate::endian::write_f64_be(dest, stored);
+        }
+    } else {
+        write_int_pixel(dest, bitpix, stored, &hdu.cards, value.is_nan())?;
+    }
+    if hdu.little_endian {
+        dest.reverse();
+    }
+    Ok(())
+}
+
+/// Write the rounded integer `stored` (or BLANK when `is_nan`) big-endian.
+fn write_int_pixel(
+    dest: &mut [u8],
+    bitpix: i64,
+    stored: f64,
+    cards: &[Card],
+    is_nan: bool,
+) -> Result<()> {
+    let int_value = if is_nan {
+        extract_blank(cards).ok_or(Error::InvalidValue)?
+    } else {
+        // Exclusive upper bounds are powers of two, so they are exact in f64.
+        let (min, end) = match bitpix {
+            8 => (0.0, 256.0),
+            16 => (-32768.0, 32768.0),
+            32 => (-2147483648.0, 2147483648.0),
+            _ => (-9223372036854775808.0, 9223372036854775808.0),
+        };
+        let rounded = libm::round(stored);
+        if !(rounded >= min && rounded < end) {
+            return Err(Error::InvalidValue);
+        }
+        rounded as i64
+    };
+
+    match bitpix {
+        8 => dest[0] = int_value as u8,
+        16 => crate::endian::write_i16_be(dest, int_value as i16),
+        32 => crate::endian::write_i32_be(dest, int_value as i32),
+        _ => crate::endian::write_i64_be(dest, int_value),
     }
+    Ok(())
 }
 
 /// Read a flat range of pixels `[start_pixel..start_pixel+count)` from the image data.
@@ -646,24 +1286,30 @@ pub fn read_image_section(
     let (bitpix, naxes) = hdu_bitpix_naxes(hdu)?;
     let bpp = bytes_per_pixel(bitpix)?;
 
-    let total_pixels: usize = if naxes.is_empty() {
-        0
-    } else {
-        naxes.iter().copied().product()
-    };
+    let total_pixels = checked_pixel_count(naxes)?;
 
     let end_pixel = start_pixel.checked_add(count).ok_or(Error::InvalidValue)?;
     if end_pixel > total_pixels {
         return Err(Error::UnexpectedEof);
     }
 
-    let byte_offset = hdu.data_start + start_pixel * bpp;
-    let byte_end = byte_offset + count * bpp;
+    // Offsets past usize::MAX cannot lie within `fits_data` either.
+    let byte_offset = start_pixel
+        .checked_mul(bpp)
+        .and_then(|n| n.checked_add(hdu.data_start))
+        .ok_or(Error::UnexpectedEof)?;
+    let byte_end = count
+        .checked_mul(bpp)
+        .and_then(|n| n.checked_add(byte_offset))
+        .ok_or(Error::UnexpectedEof)?;
     if byte_end > fits_data.len() {
         return Err(Error::UnexpectedEof);
     }
 
-    decode_pixels(&fits_data[byte_offset..byte_end], bitpix)
+    decode_pixels(
+        &big_endian_pixels(&fits_data[byte_offset..byte_end], bpp, hdu),
+        bitpix,
+    )
 }
 
 /// Read complete rows `[start_row..start_row+num_rows)` from a 2D+ image.
@@ -683,13 +1329,15 @@ pub fn read_image_rows(
     }
 
     let row_len = naxes[0];
-    let total_rows: usize = naxes[1..].iter().copied().product();
+    checked_pixel_count(naxes)?;
+    let total_rows = checked_pixel_count(&naxes[1..])?;
 
     let end_row = start_row.checked_add(num_rows).ok_or(Error::InvalidValue)?;
     if end_row > total_rows {
         return Err(Error::UnexpectedEof);
     }
 
+    // Both are bounded by the total pixel count checked above.
     let start_pixel = start_row * row_len;
     let pixel_count = num_rows * row_len;
     read_image_section(fits_data, hdu, start_pixel, pixel_count)
@@ -720,11 +1368,9 @@ pub fn read_image_region(
         sub_dims.push(end - start);
     }
 
-    let total_out: usize = if sub_dims.is_empty() {
-        0
-    } else {
-        sub_dims.iter().copied().product()
-    };
+    // The strides below multiply up to the full image size.
+    checked_pixel_count(naxes)?;
+    let total_out = checked_pixel_count(&sub_dims)?;
 
     if total_out == 0 {
         return decode_pixels(&[], bitpix);
@@ -768,12 +1414,13 @@ pub fn read_image_region(
         }
     }
 
-    decode_pixels(&raw, bitpix)
+    decode_pixels(&big_endian_pixels(&raw, bpp, hdu), bitpix)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::block::padded_byte_len;
     use crate::endian::{
         read_f32_be, read_f64_be, read_i16_be, read_i32_be, write_f32_be, write_f64_be,
         write_i16_be, write_i32_be, write_i64_be,
@@ -1098,6 +1745,33 @@ mod tests {
         assert_eq!(physical, vec![10.0, 20.0]);
     }
 
+    #[test]
+    fn physical_in_unit_converts_mjy_to_jy() {
+        let mut cards = crate::primary::build_primary_header(16, &[3]).unwrap();
+        cards.push(card("BUNIT", Value::String("mJy".into())));
+        let mut raw = vec![0u8; 6];
+        write_i16_be(&mut raw[0..], 1500);
+        write_i16_be(&mut raw[2..], -20);
+        write_i16_be(&mut raw[4..], 0);
+        let fits = build_fits(&cards, &raw);
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = parsed.primary();
+
+        let jy = read_image_physical_in_unit(&fits, hdu, "Jy").unwrap();
+        assert!((jy[0] - 1.5).abs() < 1e-12);
+        assert!((jy[1] + 0.02).abs() < 1e-12);
+        assert_eq!(jy[2], 0.0);
+
+        let ujy = read_image_physical_in_unit(&fits, hdu, "uJy").unwrap();
+        assert!((ujy[0] - 1.5e6).abs() < 1e-6);
+
+        assert!(matches!(
+            read_image_physical_in_unit(&fits, hdu, "K"),
+            Err(Error::InvalidValue)
+        ));
+        assert!(read_image_physical_in_unit(&fits, hdu, "furlong").is_err());
+    }
+
     // ---- image_dimensions ----
 
     #[test]
@@ -1120,6 +1794,65 @@ mod tests {
         assert!(dims.is_empty());
     }
 
+    #[test]
+    fn image_dimensions_squeezed_drops_trivial_axes() {
+        assert_eq!(squeeze_dimensions(&[512, 512, 1]), vec![512, 512]);
+        assert_eq!(squeeze_dimensions(&[1, 64, 1, 3]), vec![64, 3]);
+        assert_eq!(squeeze_dimensions(&[1, 1]), vec![1]);
+        assert!(squeeze_dimensions(&[]).is_empty());
+
+        let cards = primary_header_image(8, &[4, 3, 1]);
+        let fits = build_fits(&cards, &[0u8; 12]);
+        let hdu = parse_primary(&fits);
+        assert_eq!(image_dimensions_squeezed(&hdu).unwrap(), vec![4, 3]);
+    }
+
+    #[test]
+    fn read_image_data_little_endian_hdu() {
+        let cards = primary_header_image(16, &[3]);
+        let raw: Vec<u8> = [1i16, -2, 300]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let fits = build_fits(&cards, &raw);
+        let mut hdu = parse_primary(&fits);
+        hdu.little_endian = true;
+
+        assert_eq!(
+            read_image_data(&fits, &hdu).unwrap(),
+            ImageData::I16(vec![1, -2, 300])
+        );
+        let mut scratch = ImageData::I16(Vec::with_capacity(3));
+        let ptr = match &scratch {
+            ImageData::I16(v) => v.as_ptr(),
+            _ => unreachable!(),
+        };
+        read_image_data_reuse(&fits, &hdu, &mut scratch).unwrap();
+        assert_eq!(scratch, ImageData::I16(vec![1, -2, 300]));
+        assert!(matches!(&scratch, ImageData::I16(v) if v.as_ptr() == ptr));
+        assert_eq!(
+            read_image_section(&fits, &hdu, 1, 2).unwrap(),
+            ImageData::I16(vec![-2, 300])
+        );
+        assert_eq!(
+            read_image_region(&fits, &hdu, &[(2, 3)]).unwrap(),
+            ImageData::I16(vec![300])
+        );
+        let mut buf = [0f64; 3];
+        read_image_data_into_f64(&fits, &hdu, &mut buf).unwrap();
+        assert_eq!(buf, [1.0, -2.0, 300.0]);
+        let mut buf = [0f32; 3];
+        read_image_physical_into_f32(&fits, &hdu, &mut buf).unwrap();
+        assert_eq!(buf, [1.0, -2.0, 300.0]);
+
+        let mut fits = fits;
+        write_pixel(&mut fits, &hdu, &[0], -1234.0).unwrap();
+        assert_eq!(
+            read_image_physical(&fits, &hdu).unwrap(),
+            vec![-1234.0, -2.0, 300.0]
+        );
+    }
+
     // ---- Invalid BITPIX ----
 
     #[test]
@@ -1134,6 +1867,8 @@ mod tests {
             data_start: 2880,
             data_len: 10,
             cards: vec![],
+            header_pad_byte: b' ',
+            little_endian: false,
         };
         let fits = vec![0u8; 5760];
         let result = read_image_data(&fits, &hdu);
@@ -1153,6 +1888,8 @@ mod tests {
             data_start: 2880,
             data_len: 100,
             cards: vec![],
+            header_pad_byte: b' ',
+            little_endian: false,
         };
         // Provide a buffer that is too small
         let fits = vec![0u8; 2900];
@@ -1160,6 +1897,148 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn read_image_data_partial_pixel() {
+        let hdu = Hdu {
+            info: HduInfo::Primary {
+                bitpix: 16,
+                naxes: vec![3],
+            },
+            header_start: 0,
+            data_start: 2880,
+            data_len: 5,
+            cards: vec![],
+            header_pad_byte: b' ',
+            little_endian: false,
+        };
+        let fits = vec![0u8; 5760];
+        assert!(matches!(
+            read_image_data(&fits, &hdu),
+            Err(Error::UnexpectedEof)
+        ));
+        assert!(matches!(
+            decode_pixels(&[0u8; 7], -64),
+            Err(Error::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn read_image_data_reuse_keeps_capacity() {
+        let plane = |offset: f32| {
+            let mut raw = vec![0u8; 12 * 4];
+            for i in 0..12 {
+                write_f32_be(&mut raw[i * 4..], offset + i as f32);
+            }
+            build_fits(&primary_header_image(-32, &[4, 3]), &raw)
+        };
+        let first = plane(0.0);
+        let second = plane(100.0);
+
+        let mut scratch = ImageData::U8(Vec::new());
+        read_image_data_reuse(&first, &parse_primary(&first), &mut scratch).unwrap();
+        let (capacity, ptr) = match &scratch {
+            ImageData::F32(v) => {
+                assert_eq!(v[11], 11.0);
+                (v.capacity(), v.as_ptr())
+            }
+            other => panic!("expected F32, got {other:?}"),
+        };
+
+        read_image_data_reuse(&second, &parse_primary(&second), &mut scratch).unwrap();
+        match &scratch {
+            ImageData::F32(v) => {
+                assert_eq!(v.len(), 12);
+                assert_eq!(v[0], 100.0);
+                assert_eq!(v.capacity(), capacity);
+                assert_eq!(v.as_ptr(), ptr);
+            }
+            other => panic!("expected F32, got {other:?}"),
+        }
+        assert_eq!(
+            scratch,
+            read_image_data(&second, &parse_primary(&second)).unwrap()
+        );
+    }
+
+    #[test]
+    fn read_image_data_unsigned_u32_edges() {
+        let values = [0u32, 1, 0x7FFF_FFFF, 0x8000_0000, u32::MAX - 1, u32::MAX];
+        let mut raw = vec![0u8; values.len() * 4];
+        for (i, &v) in values.iter().enumerate() {
+            write_i32_be(&mut raw[i * 4..], (v ^ 0x8000_0000) as i32);
+        }
+        let mut cards = primary_header_with_bscale(32, &[values.len()], 1.0, 2_147_483_648.0);
+        cards.push(card("BLANK", Value::Integer(i32::MAX as i64)));
+        let fits = build_fits(&cards, &raw);
+        let hdu = parse_primary(&fits);
+
+        let data = read_image_data_unsigned(&fits, &hdu).unwrap();
+        assert_eq!(data, ImageData::U32(values.to_vec()));
+        assert_eq!(serialize_image(&data)[..raw.len()], raw[..]);
+
+        let mask = blank_mask(&data, extract_blank(&hdu.cards)).unwrap();
+        assert_eq!(mask, [false, false, false, false, false, true]);
+
+        let (bscale, bzero) = extract_bscale_bzero(&hdu.cards);
+        let physical = apply_bscale_bzero(&data, bscale, bzero);
+        assert_eq!(physical, values.map(f64::from));
+
+        // Without the unsigned offset the signed pixels come back as is.
+        let fits = build_fits(&primary_header_image(32, &[values.len()]), &raw);
+        let data = read_image_data_unsigned(&fits, &parse_primary(&fits)).unwrap();
+        assert!(matches!(data, ImageData::I32(_)));
+    }
+
+    #[test]
+    fn u32_image_round_trips_through_builders() {
+        let values = vec![0u32, 1, 0x8000_0000, u32::MAX];
+        let data = ImageData::U32(values.clone());
+
+        let fits = minimal_image_fits(32, &[2, 2], &data).unwrap();
+        let hdu = parse_primary(&fits);
+        assert_eq!(extract_bscale_bzero(&hdu.cards), (1.0, 2_147_483_648.0));
+        assert_eq!(read_image_data_unsigned(&fits, &hdu).unwrap(), data);
+        assert_eq!(
+            read_image_physical(&fits, &hdu).unwrap(),
+            values.iter().map(|&v| f64::from(v)).collect::<Vec<_>>()
+        );
+
+        let image = data.clone().with_shape(&[4]).unwrap();
+        let fits = build_image_hdu_with_blank(32, &image, i32::MAX as i64).unwrap();
+        let hdu = parse_primary(&fits);
+        assert_eq!(read_image_data_unsigned(&fits, &hdu).unwrap(), data);
+        let mask = blank_mask(&data, extract_blank(&hdu.cards)).unwrap();
+        assert_eq!(mask, [false, false, false, true]);
+    }
+
+    #[test]
+    fn huge_axes_report_overflow_instead_of_wrapping() {
+        let hdu = Hdu {
+            info: HduInfo::Image {
+                bitpix: 8,
+                naxes: vec![1 << 30, 1 << 30, 1 << 30],
+            },
+            header_start: 0,
+            data_start: 2880,
+            data_len: 0,
+            cards: vec![],
+            header_pad_byte: b' ',
+            little_endian: false,
+        };
+        let fits = vec![0u8; 5760];
+        let overflow =
+            |r: Result<ImageData>| matches!(r, Err(Error::InvalidHeader("pixel count overflow")));
+        assert!(overflow(read_image_section(&fits, &hdu, 0, 1)));
+        assert!(overflow(read_image_rows(&fits, &hdu, 0, 1)));
+        assert!(overflow(read_image_region(
+            &fits,
+            &hdu,
+            &[(0, 1), (0, 1), (0, 1)]
+        )));
+        assert!(checked_pixel_count(&[usize::MAX, 2]).is_err());
+        assert_eq!(checked_pixel_count(&[]).unwrap(), 0);
+    }
+
     // ---- Non-image HDU ----
 
     #[test]
@@ -1174,6 +2053,8 @@ mod tests {
             data_start: 2880,
             data_len: 5000,
             cards: vec![],
+            header_pad_byte: b' ',
+            little_endian: false,
         };
         assert!(image_dimensions(&hdu).is_err());
     }
@@ -1280,6 +2161,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn serialize_f32_preserves_nan_and_inf_bits() {
+        let pixels = [
+            f32::NAN,
+            f32::from_bits(0x7fc0_0000),
+            f32::from_bits(0x7f80_0001), // signaling NaN
+            f32::from_bits(0xffc0_1234), // negative NaN with payload
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+        ];
+        let data = serialize_image_f32(&pixels);
+        assert_eq!(&data[8..12], &0x7f80_0001u32.to_be_bytes());
+
+        let cards = primary_header_image(-32, &[pixels.len()]);
+        let fits = build_fits(&cards, &data[..pixels.len() * 4]);
+        let hdu = parse_primary(&fits);
+        match read_image_data(&fits, &hdu).unwrap() {
+            ImageData::F32(v) => {
+                let got: Vec<u32> = v.iter().map(|x| x.to_bits()).collect();
+                let expected: Vec<u32> = pixels.iter().map(|x| x.to_bits()).collect();
+                assert_eq!(got, expected);
+            }
+            other => panic!("Expected F32, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn serialize_f64_preserves_nan_and_inf_bits() {
+        let pixels = [
+            f64::NAN,
+            f64::from_bits(0x7ff0_0000_0000_0001), // signaling NaN
+            f64::from_bits(0xfff8_0000_dead_beef),
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ];
+        let data = serialize_image_f64(&pixels);
+
+        let cards = primary_header_image(-64, &[pixels.len()]);
+        let fits = build_fits(&cards, &data[..pixels.len() * 8]);
+        let hdu = parse_primary(&fits);
+        match read_image_data(&fits, &hdu).unwrap() {
+            ImageData::F64(v) => {
+                let got: Vec<u64> = v.iter().map(|x| x.to_bits()).collect();
+                let expected: Vec<u64> = pixels.iter().map(|x| x.to_bits()).collect();
+                assert_eq!(got, expected);
+            }
+            other => panic!("Expected F64, got {:?}", other),
+        }
+    }
+
     #[test]
     fn serialize_empty_images() {
         assert!(serialize_image_u8(&[]).is_empty());
@@ -1321,14 +2252,94 @@ mod tests {
     #[test]
     fn build_image_hdu_block_aligned() {
         let data = ImageData::U8(vec![1; 100]);
-        let hdu = build_image_hdu(8, &[100], &data).unwrap();
+        let hdu = build_image_hdu(8, &data.with_shape(&[100]).unwrap()).unwrap();
         assert_eq!(hdu.len() % crate::block::BLOCK_SIZE, 0);
     }
 
     #[test]
     fn build_image_hdu_invalid_bitpix() {
         let data = ImageData::U8(vec![1]);
-        assert!(build_image_hdu(12, &[1], &data).is_err());
+        assert!(build_image_hdu(12, &data.with_shape(&[1]).unwrap()).is_err());
+    }
+
+    #[test]
+    fn build_image_hdu_uses_shape() {
+        let image = ImageData::I16(vec![0; 12]).with_shape(&[4, 3]).unwrap();
+        assert_eq!(image.naxes(), &[4, 3]);
+        assert!(matches!(*image, ImageData::I16(ref v) if v.len() == 12));
+
+        let hdu = build_image_hdu(16, &image).unwrap();
+        let parsed = crate::hdu::parse_fits(&hdu).unwrap();
+        assert_eq!(image_dimensions(parsed.primary()).unwrap(), vec![4, 3]);
+    }
+
+    #[test]
+    fn build_image_hdu_with_blank_round_trips_as_nan() {
+        let image = ImageData::I16(vec![10, -1, 30, -1])
+            .with_shape(&[2, 2])
+            .unwrap();
+        let hdu = build_image_hdu_with_blank(16, &image, -1).unwrap();
+
+        let parsed = crate::hdu::parse_fits(&hdu).unwrap();
+        let physical = read_image_physical(&hdu, parsed.primary()).unwrap();
+        assert_eq!(physical[0], 10.0);
+        assert!(physical[1].is_nan());
+        assert_eq!(physical[2], 30.0);
+        assert!(physical[3].is_nan());
+
+        assert!(matches!(
+            build_image_hdu_with_blank(-32, &image, -1),
+            Err(Error::InvalidBitpix(-32))
+        ));
+        assert!(build_image_hdu_with_blank(32, &image, -1).is_err());
+        assert!(build_image_hdu_with_blank(16, &image, 40000).is_err());
+    }
+
+    #[test]
+    fn minimal_image_fits_parses_to_one_hdu() {
+        let pixels = ImageData::F32(vec![0.5, 1.5, 2.5, 3.5, 4.5, 5.5]);
+        let fits = minimal_image_fits(-32, &[3, 2], &pixels).unwrap();
+
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(image_dimensions(parsed.primary()).unwrap(), vec![3, 2]);
+        assert_eq!(parsed.read_primary_image(&fits).unwrap(), pixels);
+
+        assert!(matches!(
+            minimal_image_fits(16, &[3, 2], &pixels),
+            Err(Error::InvalidValue)
+        ));
+        assert!(matches!(
+            minimal_image_fits(-32, &[4, 2], &pixels),
+            Err(Error::DimensionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn with_shape_rejects_wrong_pixel_count() {
+        assert!(matches!(
+            ImageData::I16(vec![0; 10]).with_shape(&[4, 3]),
+            Err(Error::DimensionMismatch {
+                expected: 12,
+                got: 10
+            })
+        ));
+        assert!(matches!(
+            build_image_hdu_with_scaling(16, &[4, 3], &[0.0; 13], 1.0, 0.0),
+            Err(Error::DimensionMismatch {
+                expected: 12,
+                got: 13
+            })
+        ));
+    }
+
+    #[test]
+    fn with_shape_empty_axes_and_data() {
+        let image = ImageData::U8(vec![]).with_shape(&[]).unwrap();
+        let hdu = build_image_hdu(8, &image).unwrap();
+        let parsed = crate::hdu::parse_fits(&hdu).unwrap();
+        assert_eq!(parsed.primary().data_len, 0);
+        assert!(ImageData::U8(vec![1]).with_shape(&[]).is_err());
     }
 
     // ---- Region/section/row tests ----
@@ -1479,6 +2490,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn pixel_byte_offset_matches_flat_index() {
+        let (fits, expected) = build_i16_image_fits(10, 8);
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = parsed.primary();
+
+        let (x, y) = (3, 5);
+        let offset = pixel_byte_offset(hdu, &[x, y]).unwrap();
+        assert_eq!(offset, hdu.data_start + (y * 10 + x) * 2);
+        assert_eq!(read_i16_be(&fits[offset..]), expected[y * 10 + x]);
+
+        assert!(pixel_byte_offset(hdu, &[10, 0]).is_err());
+        assert!(pixel_byte_offset(hdu, &[0, 8]).is_err());
+        assert!(pixel_byte_offset(hdu, &[0]).is_err());
+    }
+
+    #[test]
+    fn write_pixel_round_trips_through_scaling() {
+        let mut cards = crate::primary::build_primary_header(16, &[4, 3]).unwrap();
+        cards.push(card("BSCALE", Value::Float(0.5)));
+        cards.push(card("BZERO", Value::Float(100.0)));
+        let mut fits = build_fits(&cards, &[0u8; 24]);
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = parsed.primary();
+
+        write_pixel(&mut fits, hdu, &[2, 1], 110.5).unwrap();
+        match read_image_data(&fits, hdu).unwrap() {
+            ImageData::I16(v) => {
+                assert_eq!(v[4 + 2], 21);
+                assert_eq!(v.iter().filter(|&&p| p != 0).count(), 1);
+            }
+            other => panic!("Expected I16, got {:?}", other),
+        }
+        assert_eq!(read_image_physical(&fits, hdu).unwrap()[6], 110.5);
+
+        // (100000 - 100) / 0.5 overflows i16.
+        assert!(matches!(
+            write_pixel(&mut fits, hdu, &[0, 0], 100_000.0),
+            Err(Error::InvalidValue)
+        ));
+        assert!(write_pixel(&mut fits, hdu, &[0, 0], f64::NAN).is_err());
+        assert!(write_pixel(&mut fits, hdu, &[4, 0], 1.0).is_err());
+    }
+
     // ---- BLANK keyword ----
 
     #[test]
@@ -1550,6 +2605,180 @@ mod tests {
         assert!(physical[3].is_nan());
     }
 
+    #[test]
+    fn c_order_shape_lists_rows_before_columns() {
+        // NAXIS1 = 4 columns, NAXIS2 = 3 rows; pixel (x, y) holds 10 * y + x.
+        let mut pixels = Vec::new();
+        for y in 1..=3u8 {
+            for x in 1..=4u8 {
+                pixels.push(10 * y + x);
+            }
+        }
+        let fits = build_fits(&primary_header_image(8, &[4, 3]), &pixels);
+        let hdu = parse_primary(&fits);
+
+        let (data, shape) = read_image_data_c_order(&fits, &hdu).unwrap();
+        assert_eq!(shape, vec![3, 4]);
+        let ImageData::U8(values) = data else {
+            panic!("expected U8 data");
+        };
+        // Row 1, column 2 (0-based) is FITS pixel (3, 2).
+        assert_eq!(values[shape[1] + 2], 23);
+    }
+
+    #[test]
+    fn read_raw_and_physical_together() {
+        let values: [i16; 4] = [0, -1, 7, -1];
+        let mut raw = vec![0u8; 8];
+        for (i, &v) in values.iter().enumerate() {
+            write_i16_be(&mut raw[i * 2..], v);
+        }
+
+        let mut cards = primary_header_with_bscale(16, &[4], 0.5, 10.0);
+        cards.push(card("BLANK", Value::Integer(-1)));
+        let fits = build_fits(&cards, &raw);
+        let hdu = parse_primary(&fits);
+
+        let (data, physical) = read_image_raw_and_physical(&fits, &hdu).unwrap();
+        assert_eq!(data, ImageData::I16(values.to_vec()));
+        let expected = apply_bscale_bzero(&data, 0.5, 10.0);
+        assert_eq!(physical[0], expected[0]);
+        assert_eq!(physical[2], expected[2]);
+        assert!(physical[1].is_nan());
+        assert!(physical[3].is_nan());
+    }
+
+    #[test]
+    fn read_physical_into_unsigned_16bit() {
+        let values: [i16; 4] = [-32768, -1, 0, 32767];
+        let mut raw = vec![0u8; 8];
+        for (i, &v) in values.iter().enumerate() {
+            write_i16_be(&mut raw[i * 2..], v);
+        }
+
+        let mut cards = primary_header_image(16, &[4]);
+        cards.push(card("BZERO", Value::Float(32768.0)));
+        let fits = build_fits(&cards, &raw);
+        let hdu = parse_primary(&fits);
+
+        let mut buf64 = [0.0f64; 4];
+        read_image_physical_into_f64(&fits, &hdu, &mut buf64).unwrap();
+        assert_eq!(buf64, [0.0, 32767.0, 32768.0, 65535.0]);
+
+        let mut buf32 = [0.0f32; 4];
+        read_image_physical_into_f32(&fits, &hdu, &mut buf32).unwrap();
+        assert_eq!(buf32, [0.0, 32767.0, 32768.0, 65535.0]);
+
+        let mut short = [0.0f64; 3];
+        assert!(matches!(
+            read_image_physical_into_f64(&fits, &hdu, &mut short),
+            Err(Error::InvalidValue)
+        ));
+    }
+
+    #[test]
+    fn read_physical_into_blank_is_nan() {
+        let values: [i16; 3] = [10, -32768, 20];
+        let mut raw = vec![0u8; 6];
+        for (i, &v) in values.iter().enumerate() {
+            write_i16_be(&mut raw[i * 2..], v);
+        }
+
+        let mut cards = primary_header_image(16, &[3]);
+        cards.push(card("BSCALE", Value::Float(2.0)));
+        cards.push(card("BLANK", Value::Integer(-32768)));
+        let fits = build_fits(&cards, &raw);
+        let hdu = parse_primary(&fits);
+
+        let mut buf = [0.0f32; 3];
+        read_image_physical_into_f32(&fits, &hdu, &mut buf).unwrap();
+        assert_eq!(buf[0], 20.0);
+        assert!(buf[1].is_nan());
+        assert_eq!(buf[2], 40.0);
+    }
+
+    #[test]
+    fn read_physical_i32_rounds_in_range_values() {
+        let values: [i16; 4] = [0, 1, -3, -32768];
+        let mut raw = vec![0u8; 8];
+        for (i, &v) in values.iter().enumerate() {
+            write_i16_be(&mut raw[i * 2..], v);
+        }
+
+        let mut cards = primary_header_with_bscale(16, &[4], 2.5, 1000.0);
+        cards.push(card("BLANK", Value::Integer(-32768)));
+        let fits = build_fits(&cards, &raw);
+        let hdu = parse_primary(&fits);
+
+        let pixels = read_image_physical_i32(&fits, &hdu).unwrap();
+        assert_eq!(pixels, vec![1000, 1003, 993, -32768]);
+    }
+
+    #[test]
+    fn read_physical_i32_rejects_overflow_and_nan() {
+        let mut raw = vec![0u8; 8];
+        write_i32_be(&mut raw[0..], 1);
+        write_i32_be(&mut raw[4..], i32::MAX);
+        let mut cards = primary_header_image(32, &[2]);
+        cards.push(card("BZERO", Value::Float(1.0)));
+        let fits = build_fits(&cards, &raw);
+        let hdu = parse_primary(&fits);
+        assert!(matches!(
+            read_image_physical_i32(&fits, &hdu),
+            Err(Error::InvalidValue)
+        ));
+
+        let mut raw = vec![0u8; 8];
+        write_f32_be(&mut raw[0..], 1.5);
+        write_f32_be(&mut raw[4..], f32::NAN);
+        let fits = build_fits(&primary_header_image(-32, &[2]), &raw);
+        let hdu = parse_primary(&fits);
+        assert!(matches!(
+            read_image_physical_i32(&fits, &hdu),
+            Err(Error::InvalidValue)
+        ));
+    }
+
+    #[test]
+    fn percentiles_of_uniform_ramp() {
+        let n = 10_000;
+        let values: Vec<i16> = (0..n as i16).collect();
+        let mut raw = vec![0u8; n * 2];
+        for (i, &v) in values.iter().enumerate() {
+            write_i16_be(&mut raw[i * 2..], v);
+        }
+        let fits = build_fits(&primary_header_image(16, &[100, 100]), &raw);
+        let hdu = parse_primary(&fits);
+
+        let (p25, p75) = image_percentiles(&fits, &hdu, 25.0, 75.0).unwrap();
+        assert!((p25 - 2500.0).abs() < 1.0, "p25 = {p25}");
+        assert!((p75 - 7500.0).abs() < 1.0, "p75 = {p75}");
+
+        let (p0, p100) = image_percentiles(&fits, &hdu, 0.0, 100.0).unwrap();
+        assert_eq!((p0, p100), (0.0, 9999.0));
+    }
+
+    #[test]
+    fn percentiles_skip_blank_and_apply_scaling() {
+        let values: [i16; 6] = [-1, 0, 1, 2, 3, -1];
+        let mut raw = vec![0u8; 12];
+        for (i, &v) in values.iter().enumerate() {
+            write_i16_be(&mut raw[i * 2..], v);
+        }
+        let mut cards = primary_header_image(16, &[6]);
+        cards.push(card("BSCALE", Value::Float(10.0)));
+        cards.push(card("BLANK", Value::Integer(-1)));
+        let fits = build_fits(&cards, &raw);
+        let hdu = parse_primary(&fits);
+
+        let (lo, hi) = image_percentiles(&fits, &hdu, 0.0, 100.0).unwrap();
+        assert_eq!((lo, hi), (0.0, 30.0));
+        assert!(matches!(
+            image_percentiles(&fits, &hdu, 60.0, 40.0),
+            Err(Error::InvalidValue)
+        ));
+    }
+
     // ---- BSCALE/BZERO write path ----
 
     #[test]
@@ -1620,7 +2849,11 @@ mod tests {
     #[test]
     fn read_into_f32_from_f32_image() {
         let pixels: Vec<f32> = vec![1.0, 2.5, 3.125];
-        let fits = build_image_hdu(-32, &[3], &ImageData::F32(pixels.clone())).unwrap();
+        let fits = build_image_hdu(
+            -32,
+            &ImageData::F32(pixels.clone()).with_shape(&[3]).unwrap(),
+        )
+        .unwrap();
         let parsed = crate::hdu::parse_fits(&fits).unwrap();
         let hdu = parsed.primary();
 
@@ -1632,7 +2865,7 @@ mod tests {
     #[test]
     fn read_into_f64_from_i16_image() {
         let pixels: Vec<i16> = vec![100, -200, 300];
-        let fits = build_image_hdu(16, &[3], &ImageData::I16(pixels)).unwrap();
+        let fits = build_image_hdu(16, &ImageData::I16(pixels).with_shape(&[3]).unwrap()).unwrap();
         let parsed = crate::hdu::parse_fits(&fits).unwrap();
         let hdu = parsed.primary();
 
@@ -1644,7 +2877,7 @@ mod tests {
     #[test]
     fn read_into_wrong_size_errors() {
         let pixels: Vec<f32> = vec![1.0, 2.0, 3.0];
-        let fits = build_image_hdu(-32, &[3], &ImageData::F32(pixels)).unwrap();
+        let fits = build_image_hdu(-32, &ImageData::F32(pixels).with_shape(&[3]).unwrap()).unwrap();
         let parsed = crate::hdu::parse_fits(&fits).unwrap();
         let hdu = parsed.primary();
 
@@ -0,0 +1,82 @@
+//! Small arrays stored inline as base64 text in a string keyword.
+//!
+//! This is not part of the FITS standard, but some data-exchange tools
+//! write short byte arrays this way, e.g. `LUT = 'AAECAwQ='`. Header
+//! parsing never interprets such values; call [`decode_inline_array`] on
+//! the string value of the keyword to recover the bytes.
+
+use alloc::vec::Vec;
+
+use crate::error::{Error, Result};
+
+/// Map one character of the standard base64 alphabet to its 6-bit value.
+fn sextet(c: u8) -> Option<u32> {
+    let v = match c {
+        b'A'..=b'Z' => c - b'A',
+        b'a'..=b'z' => c - b'a' + 26,
+        b'0'..=b'9' => c - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => return None,
+    };
+    Some(u32::from(v))
+}
+
+/// Decode a base64 keyword value into bytes.
+///
+/// Uses the standard alphabet (`A-Z a-z 0-9 + /`). Whitespace is ignored,
+/// so values split across CONTINUE cards or padded with spaces decode as
+/// one; trailing `=` padding is optional. Returns `Error::InvalidValue`
+/// for any other character, for data after padding, or for a length that
+/// cannot come from whole bytes.
+pub fn decode_inline_array(card_value: &str) -> Result<Vec<u8>> {
+    let text: Vec<u8> = card_value
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    let body = text.strip_suffix(b"==").or_else(|| text.strip_suffix(b"="));
+    let body = body.unwrap_or(&text);
+    if body.len() % 4 == 1 || (body.len() < text.len() && !text.len().is_multiple_of(4)) {
+        return Err(Error::InvalidValue);
+    }
+
+    let mut out = Vec::with_capacity(body.len() * 3 / 4);
+    for chunk in body.chunks(4) {
+        let mut acc = 0u32;
+        for &c in chunk {
+            acc = (acc << 6) | sextet(c).ok_or(Error::InvalidValue)?;
+        }
+        acc <<= 6 * (4 - chunk.len() as u32);
+        let bytes = acc.to_be_bytes();
+        out.extend_from_slice(&bytes[1..chunk.len()]);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_known_values() {
+        assert_eq!(
+            decode_inline_array("AAECAwT/").unwrap(),
+            [0u8, 1, 2, 3, 4, 255]
+        );
+        assert_eq!(decode_inline_array("TWFu").unwrap(), b"Man");
+        assert_eq!(decode_inline_array("TWE=").unwrap(), b"Ma");
+        assert_eq!(decode_inline_array("TQ==").unwrap(), b"M");
+        assert_eq!(decode_inline_array(" TW Fu\nTQ ").unwrap(), b"ManM");
+        assert!(decode_inline_array("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn decode_rejects_malformed_input() {
+        for bad in ["T", "TWFuT", "TW=u", "TWE*", "TQ=", "TWFu===="] {
+            assert!(
+                matches!(decode_inline_array(bad), Err(Error::InvalidValue)),
+                "{bad}"
+            );
+        }
+    }
+}
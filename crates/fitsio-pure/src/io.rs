@@ -233,6 +233,8 @@ pub use nostd::*;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
 
     #[test]
     fn cursor_read_basic() {
@@ -422,6 +424,8 @@ mod tests {
     #[cfg(not(feature = "std"))]
     #[test]
     fn io_error_display() {
+        use alloc::format;
+
         let e = IoError::UnexpectedEof;
         assert_eq!(format!("{e}"), "unexpected end of file");
         let e = IoError::WriteZero;
@@ -22,6 +22,8 @@ pub mod checksum;
 pub mod endian;
 /// Error types used throughout the crate.
 pub mod error;
+/// Delimited-text (CSV/TSV) export of table HDUs.
+pub mod export;
 /// Extension HDU (IMAGE/TABLE/BINTABLE) header parsing.
 pub mod extension;
 /// Top-level FITS parsing: HDU discovery and metadata extraction.
@@ -32,6 +34,8 @@ pub mod header;
 pub mod image;
 /// Minimal `Read`/`Write`/`Seek` traits for `no_std` environments.
 pub mod io;
+/// Observation identity metadata (TELESCOP, INSTRUME, OBJECT, DATE-OBS).
+pub mod observation;
 /// Primary HDU header parsing and construction.
 pub mod primary;
 /// ASCII table (TABLE) column parsing and data extraction.
@@ -40,9 +44,19 @@ pub mod table;
 pub mod tiled;
 /// FITS header value representation (integer, float, string, logical).
 pub mod value;
+/// World Coordinate System keyword parsing.
+pub mod wcs;
 
 pub use block::{BLOCK_SIZE, CARDS_PER_BLOCK, CARD_SIZE};
-pub use error::{Error, Result};
+pub use error::{Error, ParseWarning, Result};
+
+/// Apache Arrow conversion of binary table columns.
+#[cfg(feature = "arrow")]
+pub mod arrow;
+
+/// Decoding of base64 arrays stored in string keywords (non-standard).
+#[cfg(feature = "inline")]
+pub mod inline;
 
 /// Compatibility layer mirroring the `fitsio` crate API.
 #[cfg(feature = "compat")]
@@ -0,0 +1,248 @@
+//! Identity metadata of an observation.
+//!
+//! Collects the keywords catalogs usually key on — `TELESCOP`, `INSTRUME`,
+//! `OBJECT`, `DATE-OBS` and `EXPTIME` — into an [`Observation`]. Missing or
+//! malformed keywords leave the matching field `None`. The observatory
+//! position is read separately by [`observatory_location`].
+
+use alloc::string::String;
+
+use crate::header::{keyword_value, Card};
+use crate::value::Value;
+
+/// A calendar date and time as written in FITS date keywords.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FitsDateTime {
+    /// Four-digit year.
+    pub year: i32,
+    /// Month, 1-12.
+    pub month: u8,
+    /// Day of the month, 1-31.
+    pub day: u8,
+    /// Hour, 0-23 (0 for date-only values).
+    pub hour: u8,
+    /// Minute, 0-59.
+    pub minute: u8,
+    /// Seconds including any fraction, in `[0, 61)` to allow leap seconds.
+    pub second: f64,
+}
+
+impl FitsDateTime {
+    /// Parse `YYYY-MM-DD`, `YYYY-MM-DDThh:mm:ss[.sss]`, or the pre-2000
+    /// `DD/MM/YY` form (read as 19YY).
+    ///
+    /// Returns `None` if the text is not one of these forms or a field is
+    /// out of range.
+    pub fn parse(s: &str) -> Option<FitsDateTime> {
+        let s = s.trim();
+        let (year, month, day, time) = if let Some((d, m, y)) = split3(s, '/') {
+            if y.len() != 2 {
+                return None;
+            }
+            (1900 + number(y)? as i32, number(m)?, number(d)?, None)
+        } else {
+            let (date, time) = match s.split_once('T') {
+                Some((date, time)) => (date, Some(time)),
+                None => (s, None),
+            };
+            let (y, m, d) = split3(date, '-')?;
+            if y.len() != 4 {
+                return None;
+            }
+            (number(y)? as i32, number(m)?, number(d)?, time)
+        };
+
+        let (hour, minute, second) = match time {
+            Some(t) => {
+                let (h, m, sec) = split3(t, ':')?;
+                let second: f64 = sec.parse().ok().filter(|v: &f64| (0.0..61.0).contains(v))?;
+                (number(h)?, number(m)?, second)
+            }
+            None => (0, 0, 0.0),
+        };
+
+        let valid =
+            (1..=12).contains(&month) && (1..=31).contains(&day) && hour < 24 && minute < 60;
+        valid.then_some(FitsDateTime {
+            year,
+            month: month as u8,
+            day: day as u8,
+            hour: hour as u8,
+            minute: minute as u8,
+            second,
+        })
+    }
+}
+
+/// Split `s` into exactly three parts at `sep`.
+fn split3(s: &str, sep: char) -> Option<(&str, &str, &str)> {
+    let mut parts = s.split(sep);
+    let parts = (parts.next()?, parts.next()?, parts.next()?, parts.next());
+    match parts {
+        (a, b, c, None) => Some((a, b, c)),
+        _ => None,
+    }
+}
+
+/// Parse an unsigned decimal field of digits only.
+fn number(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Who observed what, when, and for how long.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Observation {
+    /// Telescope or mission, from `TELESCOP`.
+    pub telescope: Option<String>,
+    /// Instrument, from `INSTRUME`.
+    pub instrument: Option<String>,
+    /// Target name, from `OBJECT`.
+    pub object: Option<String>,
+    /// Start of the observation, from `DATE-OBS`.
+    pub date_obs: Option<FitsDateTime>,
+    /// Exposure time in seconds, from `EXPTIME` or else `EXPOSURE`.
+    pub exptime: Option<f64>,
+}
+
+fn card_string_value(cards: &[Card], keyword: &str) -> Option<String> {
+    match keyword_value(cards, keyword) {
+        Some(Value::String(s)) => Some(s.trim()).filter(|s| !s.is_empty()).map(String::from),
+        _ => None,
+    }
+}
+
+fn card_float_value(cards: &[Card], keyword: &str) -> Option<f64> {
+    match keyword_value(cards, keyword) {
+        Some(Value::Float(f)) => Some(*f),
+        Some(Value::Integer(n)) => Some(*n as f64),
+        _ => None,
+    }
+}
+
+/// Read the observation identity keywords from a header.
+///
+/// Blank strings count as missing, and a `DATE-OBS` that does not parse as
+/// a [`FitsDateTime`] is ignored.
+pub fn observation_metadata(cards: &[Card]) -> Observation {
+    Observation {
+        telescope: card_string_value(cards, "TELESCOP"),
+        instrument: card_string_value(cards, "INSTRUME"),
+        object: card_string_value(cards, "OBJECT"),
+        date_obs: card_string_value(cards, "DATE-OBS").and_then(|s| FitsDateTime::parse(&s)),
+        exptime: card_float_value(cards, "EXPTIME").or_else(|| card_float_value(cards, "EXPOSURE")),
+    }
+}
+
+/// Geocentric Cartesian position of an observatory, in meters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeoLocation {
+    /// `OBSGEO-X`: toward the intersection of the equator and the prime
+    /// meridian.
+    pub x: f64,
+    /// `OBSGEO-Y`: toward 90 degrees east longitude on the equator.
+    pub y: f64,
+    /// `OBSGEO-Z`: toward the north pole.
+    pub z: f64,
+}
+
+/// Read the observatory position from `OBSGEO-X`, `OBSGEO-Y` and
+/// `OBSGEO-Z`.
+///
+/// Returns `None` unless all three are present as numbers.
+pub fn observatory_location(cards: &[Card]) -> Option<GeoLocation> {
+    Some(GeoLocation {
+        x: card_float_value(cards, "OBSGEO-X")?,
+        y: card_float_value(cards, "OBSGEO-Y")?,
+        z: card_float_value(cards, "OBSGEO-Z")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    fn card(keyword: &str, value: Value) -> Card {
+        let mut kw = [b' '; 8];
+        kw[..keyword.len()].copy_from_slice(keyword.as_bytes());
+        Card {
+            keyword: kw,
+            value: Some(value),
+            comment: None,
+        }
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(String::from(s))
+    }
+
+    #[test]
+    fn observation_from_realistic_header() {
+        let cards: Vec<Card> = vec![
+            card("SIMPLE", Value::Logical(true)),
+            card("BITPIX", Value::Integer(-32)),
+            card("NAXIS", Value::Integer(0)),
+            card("TELESCOP", string("HST")),
+            card("INSTRUME", string("WFC3    ")),
+            card("OBJECT", string("NGC 4151")),
+            card("DATE-OBS", string("2019-03-14T05:26:53.5")),
+            card("EXPTIME", Value::Float(1102.5)),
+        ];
+
+        let obs = observation_metadata(&cards);
+        assert_eq!(obs.telescope.as_deref(), Some("HST"));
+        assert_eq!(obs.instrument.as_deref(), Some("WFC3"));
+        assert_eq!(obs.object.as_deref(), Some("NGC 4151"));
+        assert_eq!(
+            obs.date_obs,
+            Some(FitsDateTime {
+                year: 2019,
+                month: 3,
+                day: 14,
+                hour: 5,
+                minute: 26,
+                second: 53.5,
+            })
+        );
+        assert_eq!(obs.exptime, Some(1102.5));
+
+        assert_eq!(observation_metadata(&cards[..3]), Observation::default());
+    }
+
+    #[test]
+    fn date_forms() {
+        let d = FitsDateTime::parse("2024-01-15").unwrap();
+        assert_eq!((d.year, d.month, d.day, d.hour), (2024, 1, 15, 0));
+        let d = FitsDateTime::parse("31/12/98").unwrap();
+        assert_eq!((d.year, d.month, d.day), (1998, 12, 31));
+        assert!(FitsDateTime::parse("2024-13-01").is_none());
+        assert!(FitsDateTime::parse("2024-01-15T25:00:00").is_none());
+        assert!(FitsDateTime::parse("24-01-15").is_none());
+        assert!(FitsDateTime::parse("yesterday").is_none());
+    }
+
+    #[test]
+    fn observatory_location_needs_all_three_axes() {
+        let cards: Vec<Card> = vec![
+            card("OBSGEO-X", Value::Float(-1601185.4)),
+            card("OBSGEO-Y", Value::Float(-5041977.5)),
+            card("OBSGEO-Z", Value::Integer(3554876)),
+        ];
+        assert_eq!(
+            observatory_location(&cards),
+            Some(GeoLocation {
+                x: -1601185.4,
+                y: -5041977.5,
+                z: 3554876.0,
+            })
+        );
+        assert_eq!(observatory_location(&cards[..2]), None);
+    }
+}
@@ -10,7 +10,8 @@ use crate::error::{Error, Result};
 use crate::header::{validate_required_keywords, Card, HduType};
 use crate::value::Value;
 
-const VALID_BITPIX: [i64; 6] = [8, 16, 32, 64, -32, -64];
+/// The BITPIX values allowed by the FITS standard.
+pub(crate) const VALID_BITPIX: [i64; 6] = [8, 16, 32, 64, -32, -64];
 
 /// Pad a short keyword name to 8 bytes with trailing ASCII spaces.
 const fn kw(name: &[u8]) -> [u8; 8] {
@@ -153,9 +154,64 @@ pub fn build_primary_header(bitpix: i64, naxes: &[usize]) -> Result<Vec<Card>> {
     Ok(cards)
 }
 
+fn is_mandatory_primary_keyword(keyword: &str) -> bool {
+    match keyword {
+        "SIMPLE" | "BITPIX" | "NAXIS" | "END" => true,
+        _ => keyword
+            .strip_prefix("NAXIS")
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit())),
+    }
+}
+
+/// Build a primary HDU header with additional cards after the mandatory ones.
+///
+/// The mandatory keywords come first, followed by `EXTEND = T` when `extras`
+/// is non-empty, then `extras` in order. An `EXTEND` card in `extras` takes
+/// the place of the default one. Returns `Error::InvalidHeader` if `extras`
+/// contains `SIMPLE`, `BITPIX`, `NAXIS`, `NAXISn` or `END`.
+pub fn build_primary_header_ext(
+    bitpix: i64,
+    naxes: &[usize],
+    extras: &[Card],
+) -> Result<Vec<Card>> {
+    if extras
+        .iter()
+        .any(|c| is_mandatory_primary_keyword(c.keyword_str()))
+    {
+        return Err(Error::InvalidHeader(
+            "extra card duplicates a mandatory primary keyword",
+        ));
+    }
+
+    let mut cards = build_primary_header(bitpix, naxes)?;
+    if extras.is_empty() {
+        return Ok(cards);
+    }
+
+    let extend = extras
+        .iter()
+        .find(|c| c.keyword_str() == "EXTEND")
+        .cloned()
+        .unwrap_or_else(|| Card {
+            keyword: kw(b"EXTEND"),
+            value: Some(Value::Logical(true)),
+            comment: Some(String::from("FITS dataset may contain extensions")),
+        });
+    cards.push(extend);
+    cards.extend(
+        extras
+            .iter()
+            .filter(|c| c.keyword_str() != "EXTEND")
+            .cloned(),
+    );
+
+    Ok(cards)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
     fn card(keyword: &[u8], value: Option<Value>) -> Card {
         Card {
             keyword: kw(keyword),
@@ -434,4 +490,55 @@ mod tests {
         let cards = build_primary_header(8, &[]).unwrap();
         assert_eq!(cards.len(), 3);
     }
+
+    #[test]
+    fn build_ext_places_extras_after_mandatory() {
+        let extras = [
+            card(b"OBJECT", Some(Value::String(String::from("M31")))),
+            card(b"TELESCOP", Some(Value::String(String::from("HST")))),
+        ];
+        let cards = build_primary_header_ext(16, &[10, 20], &extras).unwrap();
+        let names: Vec<&str> = cards.iter().map(|c| c.keyword_str()).collect();
+        assert_eq!(
+            names,
+            vec!["SIMPLE", "BITPIX", "NAXIS", "NAXIS1", "NAXIS2", "EXTEND", "OBJECT", "TELESCOP"]
+        );
+        assert_eq!(cards[5].value, Some(Value::Logical(true)));
+
+        let hdr = parse_primary_header(&cards).unwrap();
+        assert_eq!(hdr.naxes, vec![10, 20]);
+        crate::header::serialize_header(&cards).unwrap();
+    }
+
+    #[test]
+    fn build_ext_uses_caller_extend() {
+        let extras = [
+            card(b"OBJECT", Some(Value::String(String::from("M31")))),
+            card(b"EXTEND", Some(Value::Logical(false))),
+        ];
+        let cards = build_primary_header_ext(8, &[], &extras).unwrap();
+        assert_eq!(cards.len(), 5);
+        assert_eq!(cards[3].keyword_str(), "EXTEND");
+        assert_eq!(cards[3].value, Some(Value::Logical(false)));
+        assert_eq!(cards[4].keyword_str(), "OBJECT");
+    }
+
+    #[test]
+    fn build_ext_without_extras_matches_minimal() {
+        let cards = build_primary_header_ext(8, &[4], &[]).unwrap();
+        assert_eq!(cards, build_primary_header(8, &[4]).unwrap());
+    }
+
+    #[test]
+    fn build_ext_rejects_duplicate_mandatory() {
+        for name in [&b"BITPIX"[..], b"NAXIS", b"NAXIS2", b"SIMPLE"] {
+            let extras = [card(name, Some(Value::Integer(1)))];
+            assert!(matches!(
+                build_primary_header_ext(8, &[4], &extras),
+                Err(Error::InvalidHeader(_))
+            ));
+        }
+        let extras = [card(b"NAXISFOO", Some(Value::Integer(1)))];
+        assert!(build_primary_header_ext(8, &[4], &extras).is_ok());
+    }
 }
@@ -5,10 +5,11 @@ use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
 
-use crate::block::padded_byte_len;
+use crate::bintable::unique_column_name;
+use crate::block::{pad_to_block, Serialized, DATA_PAD_BYTE};
 use crate::error::{Error, Result};
 use crate::hdu::{Hdu, HduInfo};
-use crate::header::Card;
+use crate::header::{keyword_value, Card};
 use crate::value::Value;
 
 // ── Column Format ──
@@ -162,6 +163,28 @@ pub fn parse_ascii_table_columns(
 /// `fits_data` is the entire FITS byte stream. The HDU must describe an
 /// `AsciiTable`.  `col_index` is 0-based.
 pub fn read_ascii_column(fits_data: &[u8], hdu: &Hdu, col_index: usize) -> Result<AsciiColumnData> {
+    read_ascii_column_inner(fits_data, hdu, col_index, false)
+}
+
+/// Read an ASCII table column, honouring Fortran implied decimal points.
+///
+/// Like [`read_ascii_column`], but a float field written without a `.`
+/// (e.g. `12345` under `F8.3`) is read with the TFORM `d` as an implied
+/// scale, giving `12.345`. Fields that contain a `.` are read unchanged.
+pub fn read_ascii_column_implied_decimal(
+    fits_data: &[u8],
+    hdu: &Hdu,
+    col_index: usize,
+) -> Result<AsciiColumnData> {
+    read_ascii_column_inner(fits_data, hdu, col_index, true)
+}
+
+fn read_ascii_column_inner(
+    fits_data: &[u8],
+    hdu: &Hdu,
+    col_index: usize,
+    implied_decimal: bool,
+) -> Result<AsciiColumnData> {
     let (naxis1, naxis2, tfields) = ascii_table_dims(hdu)?;
     if col_index >= tfields {
         return Err(Error::InvalidValue);
@@ -175,7 +198,31 @@ pub fn read_ascii_column(fits_data: &[u8], hdu: &Hdu, col_index: usize) -> Resul
         return Err(Error::UnexpectedEof);
     }
 
-    parse_column_values(fits_data, data_start, naxis1, naxis2, col)
+    parse_column_values(fits_data, data_start, naxis1, naxis2, col, implied_decimal)
+}
+
+/// Read every column of an ASCII table HDU, paired with its display name.
+///
+/// Names follow the same rule as
+/// [`column_display_name`](crate::bintable::column_display_name): the
+/// `TTYPEn` value when unique, `"col{n}"` for unnamed columns and an
+/// occurrence suffix for duplicates. The descriptors are parsed once.
+pub fn ascii_columns_named(fits_data: &[u8], hdu: &Hdu) -> Result<Vec<(String, AsciiColumnData)>> {
+    let (naxis1, naxis2, tfields) = ascii_table_dims(hdu)?;
+    let columns = parse_ascii_table_columns(&hdu.cards, tfields)?;
+    if hdu.data_start + naxis1 * naxis2 > fits_data.len() {
+        return Err(Error::UnexpectedEof);
+    }
+
+    let names: Vec<Option<&str>> = columns.iter().map(|c| c.name.as_deref()).collect();
+    columns
+        .iter()
+        .enumerate()
+        .map(|(i, col)| {
+            let data = parse_column_values(fits_data, hdu.data_start, naxis1, naxis2, col, false)?;
+            Ok((unique_column_name(&names, i), data))
+        })
+        .collect()
 }
 
 /// Read all columns for a single row of an ASCII table HDU.
@@ -314,6 +361,16 @@ pub fn serialize_ascii_table(
     col_data: &[AsciiColumnData],
     naxis1: usize,
 ) -> Result<Vec<u8>> {
+    serialize_ascii_table_with_len(columns, col_data, naxis1).map(|s| s.bytes)
+}
+
+/// Serialize an ASCII table like [`serialize_ascii_table`], also returning
+/// the unpadded length (`NAXIS1 * NAXIS2`).
+pub fn serialize_ascii_table_with_len(
+    columns: &[AsciiColumnDescriptor],
+    col_data: &[AsciiColumnData],
+    naxis1: usize,
+) -> Result<Serialized> {
     if columns.len() != col_data.len() {
         return Err(Error::InvalidValue);
     }
@@ -326,8 +383,7 @@ pub fn serialize_ascii_table(
     };
 
     let raw_len = naxis1 * naxis2;
-    let padded_len = padded_byte_len(raw_len);
-    let mut buf = vec![b' '; padded_len];
+    let mut buf = vec![b' '; raw_len];
 
     for row in 0..naxis2 {
         let row_start = row * naxis1;
@@ -344,12 +400,10 @@ pub fn serialize_ascii_table(
         }
     }
 
-    // Zero-pad after the raw data (FITS data blocks are zero-padded).
-    for b in &mut buf[raw_len..] {
-        *b = 0;
-    }
-
-    Ok(buf)
+    Ok(Serialized {
+        bytes: pad_to_block(buf, DATA_PAD_BYTE),
+        raw_len,
+    })
 }
 
 /// Build and serialize a complete ASCII table HDU (header + data).
@@ -384,7 +438,7 @@ pub fn serialize_ascii_table_hdu(
 
 // ── Internal Helpers ──
 
-fn ascii_table_dims(hdu: &Hdu) -> Result<(usize, usize, usize)> {
+pub(crate) fn ascii_table_dims(hdu: &Hdu) -> Result<(usize, usize, usize)> {
     match &hdu.info {
         HduInfo::AsciiTable {
             naxis1,
@@ -396,81 +450,57 @@ fn ascii_table_dims(hdu: &Hdu) -> Result<(usize, usize, usize)> {
 }
 
 fn find_card_string(cards: &[Card], keyword: &str) -> Option<String> {
-    cards.iter().find_map(|c| {
-        if c.keyword_str() == keyword {
-            match &c.value {
-                Some(Value::String(s)) => Some(s.trim().into()),
-                _ => None,
-            }
-        } else {
-            None
-        }
-    })
+    match keyword_value(cards, keyword) {
+        Some(Value::String(s)) => Some(s.trim().into()),
+        _ => None,
+    }
 }
 
 fn find_card_integer(cards: &[Card], keyword: &str) -> Option<i64> {
-    cards.iter().find_map(|c| {
-        if c.keyword_str() == keyword {
-            match &c.value {
-                Some(Value::Integer(n)) => Some(*n),
-                _ => None,
-            }
-        } else {
-            None
-        }
-    })
+    match keyword_value(cards, keyword) {
+        Some(Value::Integer(n)) => Some(*n),
+        _ => None,
+    }
 }
 
-fn parse_column_values(
+pub(crate) fn parse_column_values(
     fits_data: &[u8],
     data_start: usize,
     naxis1: usize,
     naxis2: usize,
     col: &AsciiColumnDescriptor,
+    implied_decimal: bool,
 ) -> Result<AsciiColumnData> {
+    let fields = column_fields(fits_data, data_start, naxis1, naxis2, col)?;
     match &col.format {
-        AsciiColumnFormat::Character(w) => {
+        AsciiColumnFormat::Character(_) => {
             let mut vals = Vec::with_capacity(naxis2);
-            for row in 0..naxis2 {
-                let offset = data_start + row * naxis1 + col.tbcol;
-                let end = offset + w;
-                if end > fits_data.len() {
-                    return Err(Error::UnexpectedEof);
-                }
-                let s = core::str::from_utf8(&fits_data[offset..end])
-                    .map_err(|_| Error::InvalidValue)?;
+            for field in fields {
+                let s = core::str::from_utf8(field).map_err(|_| Error::InvalidValue)?;
                 vals.push(String::from(s.trim_end()));
             }
             Ok(AsciiColumnData::Character(vals))
         }
-        AsciiColumnFormat::Integer(w) => {
+        AsciiColumnFormat::Integer(_) => {
             let mut vals = Vec::with_capacity(naxis2);
-            for row in 0..naxis2 {
-                let offset = data_start + row * naxis1 + col.tbcol;
-                let end = offset + w;
-                if end > fits_data.len() {
-                    return Err(Error::UnexpectedEof);
-                }
-                let s = core::str::from_utf8(&fits_data[offset..end])
-                    .map_err(|_| Error::InvalidValue)?;
+            for field in fields {
+                let s = core::str::from_utf8(field).map_err(|_| Error::InvalidValue)?;
                 let n: i64 = s.trim().parse().map_err(|_| Error::InvalidValue)?;
                 vals.push(n);
             }
             Ok(AsciiColumnData::Integer(vals))
         }
-        AsciiColumnFormat::FloatF(w, _)
-        | AsciiColumnFormat::FloatE(w, _)
-        | AsciiColumnFormat::DoubleE(w, _) => {
+        AsciiColumnFormat::FloatF(_, d)
+        | AsciiColumnFormat::FloatE(_, d)
+        | AsciiColumnFormat::DoubleE(_, d) => {
             let mut vals = Vec::with_capacity(naxis2);
-            for row in 0..naxis2 {
-                let offset = data_start + row * naxis1 + col.tbcol;
-                let end = offset + w;
-                if end > fits_data.len() {
-                    return Err(Error::UnexpectedEof);
-                }
-                let s = core::str::from_utf8(&fits_data[offset..end])
-                    .map_err(|_| Error::InvalidValue)?;
-                let f = parse_fits_float(s.trim())?;
+            for field in fields {
+                let s = core::str::from_utf8(field).map_err(|_| Error::InvalidValue)?;
+                let f = if implied_decimal {
+                    parse_fits_float_implied(s.trim(), *d)?
+                } else {
+                    parse_fits_float(s.trim())?
+                };
                 vals.push(f);
             }
             Ok(AsciiColumnData::Float(vals))
@@ -478,6 +508,34 @@ fn parse_column_values(
     }
 }
 
+/// The raw bytes of `col` in each of `naxis2` rows.
+///
+/// Every field lies below the end of the last row's field, so one check up
+/// front covers them all and the slicing below cannot panic.
+fn column_fields<'a>(
+    fits_data: &'a [u8],
+    data_start: usize,
+    naxis1: usize,
+    naxis2: usize,
+    col: &AsciiColumnDescriptor,
+) -> Result<impl Iterator<Item = &'a [u8]> + 'a> {
+    let (tbcol, width) = (col.tbcol, col.format.width());
+    if naxis2 > 0 {
+        let end = (naxis2 - 1)
+            .checked_mul(naxis1)
+            .and_then(|n| n.checked_add(data_start))
+            .and_then(|n| n.checked_add(tbcol))
+            .and_then(|n| n.checked_add(width));
+        if end.is_none_or(|end| end > fits_data.len()) {
+            return Err(Error::UnexpectedEof);
+        }
+    }
+    Ok((0..naxis2).map(move |row| {
+        let start = data_start + row * naxis1 + tbcol;
+        &fits_data[start..start + width]
+    }))
+}
+
 fn parse_single_field(field_str: &str, fmt: &AsciiColumnFormat) -> Result<AsciiColumnData> {
     match fmt {
         AsciiColumnFormat::Character(_) => Ok(AsciiColumnData::Character(vec![String::from(
@@ -502,6 +560,25 @@ fn parse_fits_float(s: &str) -> Result<f64> {
     normalized.parse::<f64>().map_err(|_| Error::InvalidValue)
 }
 
+/// Parse a FITS float string, applying an implied decimal point of `d`
+/// places when the mantissa has no explicit `.` (Fortran `Fw.d` input rules).
+fn parse_fits_float_implied(s: &str, d: usize) -> Result<f64> {
+    let exp_at = s.find(['E', 'e', 'D', 'd']).unwrap_or(s.len());
+    let mantissa = &s[..exp_at];
+    if mantissa.contains('.') {
+        return parse_fits_float(s);
+    }
+    let exponent = &s[exp_at..];
+    let shifted = format!("{}E-{}", mantissa, d);
+    let value = parse_fits_float(&shifted)?;
+    if exponent.is_empty() {
+        Ok(value)
+    } else {
+        let scale = parse_fits_float(&format!("1{}", exponent))?;
+        Ok(value * scale)
+    }
+}
+
 fn pad_or_truncate_left(s: &str, width: usize) -> String {
     if s.len() >= width {
         String::from(&s[..width])
@@ -691,6 +768,8 @@ mod tests {
             data_start: header_bytes.len(),
             data_len: data.len(),
             cards,
+            header_pad_byte: b' ',
+            little_endian: false,
         };
 
         (fits_data, hdu)
@@ -749,6 +828,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ascii_columns_named_pairs_names_with_data() {
+        let naxis1 = 12;
+        let naxis2 = 2;
+        let cards = build_table_cards(naxis1, naxis2, &[(Some("STAR"), "A6", 1), (None, "I6", 7)]);
+
+        let mut raw = vec![b' '; naxis1 * naxis2];
+        raw[0..4].copy_from_slice(b"Vega");
+        raw[6..12].copy_from_slice(b"    42");
+        raw[12..18].copy_from_slice(b"Deneb ");
+        raw[18..24].copy_from_slice(b"    -7");
+
+        let (fits_data, hdu) = build_hdu(cards, &raw);
+        assert_eq!(
+            ascii_columns_named(&fits_data, &hdu).unwrap(),
+            vec![
+                (
+                    String::from("STAR"),
+                    AsciiColumnData::Character(vec![String::from("Vega"), String::from("Deneb")])
+                ),
+                (String::from("col2"), AsciiColumnData::Integer(vec![42, -7])),
+            ]
+        );
+    }
+
     // ---- Reading: integer column ----
 
     #[test]
@@ -800,6 +904,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn read_column_float_f_implied_decimal() {
+        let naxis1 = 8;
+        let naxis2 = 3;
+        let cards = build_table_cards(naxis1, naxis2, &[(None, "F8.3", 1)]);
+
+        let mut raw = vec![b' '; naxis1 * naxis2];
+        raw[0..8].copy_from_slice(b"   12345");
+        raw[8..16].copy_from_slice(b"  -1.500");
+        raw[16..24].copy_from_slice(b"  25E2  ");
+
+        let (fits_data, hdu) = build_hdu(cards, &raw);
+        match read_ascii_column_implied_decimal(&fits_data, &hdu, 0).unwrap() {
+            AsciiColumnData::Float(vals) => {
+                assert!((vals[0] - 12.345).abs() < 1e-12);
+                assert_eq!(vals[1], -1.5);
+                // 25E2 with three implied places is 0.025E2.
+                assert!((vals[2] - 2.5).abs() < 1e-12);
+            }
+            other => panic!("Expected Float, got {:?}", other),
+        }
+
+        // Off by default: the field is read as written.
+        match read_ascii_column(&fits_data, &hdu, 0).unwrap() {
+            AsciiColumnData::Float(vals) => assert_eq!(vals[0], 12345.0),
+            other => panic!("Expected Float, got {:?}", other),
+        }
+    }
+
     // ---- Reading: float E column ----
 
     #[test]
@@ -1079,6 +1212,8 @@ mod tests {
             data_start: header_bytes.len(),
             data_len: naxis1 * naxis2,
             cards,
+            header_pad_byte: b' ',
+            little_endian: false,
         };
 
         let col = read_ascii_column(&fits_data, &hdu, 0).unwrap();
@@ -1119,6 +1254,8 @@ mod tests {
             data_start: header_bytes.len(),
             data_len: naxis1 * naxis2,
             cards,
+            header_pad_byte: b' ',
+            little_endian: false,
         };
 
         let col = read_ascii_column(&fits_data, &hdu, 0).unwrap();
@@ -1159,6 +1296,8 @@ mod tests {
             data_start: header_bytes.len(),
             data_len: naxis1 * naxis2,
             cards,
+            header_pad_byte: b' ',
+            little_endian: false,
         };
 
         let col = read_ascii_column(&fits_data, &hdu, 0).unwrap();
@@ -1217,6 +1356,8 @@ mod tests {
             data_start: header_bytes.len(),
             data_len: naxis1 * naxis2,
             cards,
+            header_pad_byte: b' ',
+            little_endian: false,
         };
 
         let col0 = read_ascii_column(&fits_data, &hdu, 0).unwrap();
@@ -1332,6 +1473,8 @@ mod tests {
             data_start: 0,
             data_len: 0,
             cards: vec![],
+            header_pad_byte: b' ',
+            little_endian: false,
         };
         assert!(read_ascii_column(&[], &hdu, 0).is_err());
     }
@@ -1406,4 +1549,25 @@ mod tests {
             other => panic!("Expected Integer, got {:?}", other),
         }
     }
+
+    #[test]
+    fn serialize_hdu_zero_columns() {
+        let hdu_bytes = serialize_ascii_table_hdu(&[], &[]).unwrap();
+        assert_eq!(hdu_bytes.len(), crate::block::BLOCK_SIZE);
+
+        let primary_cards = vec![
+            mk_card("SIMPLE", Value::Logical(true)),
+            mk_card("BITPIX", Value::Integer(8)),
+            mk_card("NAXIS", Value::Integer(0)),
+        ];
+        let mut fits = serialize_header(&primary_cards).unwrap();
+        fits.extend_from_slice(&hdu_bytes);
+
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = parsed.get(1).unwrap();
+        assert_eq!(ascii_table_dims(hdu).unwrap(), (0, 0, 0));
+        assert_eq!(hdu.data_len, 0);
+        assert!(parse_ascii_table_columns(&hdu.cards, 0).unwrap().is_empty());
+        assert!(read_ascii_column(&fits, hdu, 0).is_err());
+    }
 }
@@ -1,15 +1,17 @@
 //! Tile-compressed image decompression for FITS.
 //!
 //! Supports RICE_1/RICE_ONE and GZIP_1 compression algorithms per the
-//! FITS tiled image compression convention.
+//! FITS tiled image compression convention. GZIP_1 tiles are inflated with
+//! `miniz_oxide`, so both algorithms work under `no_std` + `alloc`.
 
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::ops::Range;
 
 use crate::endian::{read_f64_be, read_i32_be};
 use crate::error::{Error, Result};
 use crate::hdu::{Hdu, HduInfo};
-use crate::header::Card;
+use crate::header::{keyword_value, Card};
 use crate::image::ImageData;
 use crate::value::Value;
 
@@ -19,27 +21,29 @@ use crate::value::Value;
 
 struct ColumnInfo {
     compressed_data_offset: usize,
+    /// Per-tile fallback: gzip-compressed raw pixels.
+    gzip_data_offset: Option<usize>,
+    /// Per-tile fallback: raw pixels, with the element size in bytes.
+    uncompressed_data: Option<(usize, usize)>,
     zscale_offset: Option<usize>,
     zzero_offset: Option<usize>,
 }
 
 fn card_string_value(cards: &[Card], keyword: &str) -> Option<String> {
-    cards.iter().find_map(|c| {
-        if c.keyword_str() == keyword {
-            match &c.value {
-                Some(Value::String(s)) => Some(s.trim().into()),
-                _ => None,
-            }
-        } else {
-            None
-        }
-    })
+    match keyword_value(cards, keyword) {
+        Some(Value::String(s)) => Some(s.trim().into()),
+        _ => None,
+    }
 }
 
-/// Parse the binary table column layout to find COMPRESSED_DATA, ZSCALE, and ZZERO columns.
+/// Parse the binary table column layout to find COMPRESSED_DATA, ZSCALE, and ZZERO columns,
+/// plus the optional GZIP_COMPRESSED_DATA and UNCOMPRESSED_DATA fallback columns.
 fn parse_column_layout(cards: &[Card], tfields: usize) -> Result<ColumnInfo> {
     let mut offsets = Vec::with_capacity(tfields);
+    let mut elem_sizes = Vec::with_capacity(tfields);
     let mut compressed_data_col = None;
+    let mut gzip_data_col = None;
+    let mut uncompressed_data_col = None;
     let mut zscale_col = None;
     let mut zzero_col = None;
 
@@ -53,6 +57,10 @@ fn parse_column_layout(cards: &[Card], tfields: usize) -> Result<ColumnInfo> {
             zscale_col = Some(i - 1);
         } else if name == "ZZERO" {
             zzero_col = Some(i - 1);
+        } else if name == "GZIP_COMPRESSED_DATA" {
+            gzip_data_col = Some(i - 1);
+        } else if name == "UNCOMPRESSED_DATA" {
+            uncompressed_data_col = Some(i - 1);
         }
     }
 
@@ -64,6 +72,11 @@ fn parse_column_layout(cards: &[Card], tfields: usize) -> Result<ColumnInfo> {
         let tform = card_string_value(cards, &tform_kw)
             .ok_or(Error::InvalidHeader("missing TFORM in compressed image"))?;
         let (repeat, col_type) = crate::bintable::parse_tform_binary(&tform)?;
+        elem_sizes.push(match col_type {
+            crate::bintable::BinaryColumnType::VarArrayP(code)
+            | crate::bintable::BinaryColumnType::VarArrayQ(code) => var_elem_size(code),
+            _ => None,
+        });
         let width = match col_type {
             crate::bintable::BinaryColumnType::Bit => repeat.div_ceil(8),
             crate::bintable::BinaryColumnType::VarArrayP(_) => 8 * repeat,
@@ -76,13 +89,36 @@ fn parse_column_layout(cards: &[Card], tfields: usize) -> Result<ColumnInfo> {
     let compressed_idx =
         compressed_data_col.ok_or(Error::InvalidHeader("missing COMPRESSED_DATA column"))?;
 
+    let uncompressed_data = match uncompressed_data_col {
+        Some(i) => Some((
+            offsets[i],
+            elem_sizes[i].ok_or(Error::InvalidHeader(
+                "UNCOMPRESSED_DATA column must be a numeric variable-length array",
+            ))?,
+        )),
+        None => None,
+    };
+
     Ok(ColumnInfo {
         compressed_data_offset: offsets[compressed_idx],
+        gzip_data_offset: gzip_data_col.map(|i| offsets[i]),
+        uncompressed_data,
         zscale_offset: zscale_col.map(|i| offsets[i]),
         zzero_offset: zzero_col.map(|i| offsets[i]),
     })
 }
 
+/// Element size in bytes of a numeric variable-length array type code.
+fn var_elem_size(code: char) -> Option<usize> {
+    match code {
+        'B' => Some(1),
+        'I' => Some(2),
+        'J' | 'E' => Some(4),
+        'K' | 'D' => Some(8),
+        _ => None,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // P-descriptor / heap reading
 // ---------------------------------------------------------------------------
@@ -108,6 +144,23 @@ fn extract_tile_bytes(
     row: usize,
     col_offset: usize,
 ) -> Result<(&[u8], usize)> {
+    let (tile_start, count) =
+        tile_location(fits_data, data_start, naxis1, naxis2, row, col_offset)?;
+    Ok((&fits_data[tile_start..], count))
+}
+
+/// Locate a tile's bytes in the heap: `(absolute_start, count)`.
+///
+/// Checks that `count` bytes starting at the returned offset lie within
+/// `fits_data`.
+fn tile_location(
+    fits_data: &[u8],
+    data_start: usize,
+    naxis1: usize,
+    naxis2: usize,
+    row: usize,
+    col_offset: usize,
+) -> Result<(usize, usize)> {
     let desc_pos = data_start + row * naxis1 + col_offset;
     if desc_pos + 8 > fits_data.len() {
         return Err(Error::UnexpectedEof);
@@ -119,7 +172,7 @@ fn extract_tile_bytes(
     if tile_end > fits_data.len() {
         return Err(Error::UnexpectedEof);
     }
-    Ok((&fits_data[tile_start..], count))
+    Ok((tile_start, count))
 }
 
 // ---------------------------------------------------------------------------
@@ -347,7 +400,7 @@ fn rice_decompress(
 // ---------------------------------------------------------------------------
 
 /// Strip the gzip header and trailer, returning the raw deflate payload.
-fn strip_gzip_header(data: &[u8]) -> Result<&[u8]> {
+pub(crate) fn strip_gzip_header(data: &[u8]) -> Result<&[u8]> {
     if data.len() < 18 || data[0] != 0x1f || data[1] != 0x8b || data[2] != 0x08 {
         return Err(Error::DecompressionError("invalid gzip header"));
     }
@@ -399,6 +452,11 @@ fn gzip_decompress(compressed: &[u8]) -> Result<Vec<u8>> {
         .map_err(|_| Error::DecompressionError("zlib/deflate inflate failed"))
 }
 
+/// Copy decompressed bytes into u8 values.
+fn bytes_to_u8(data: &[u8]) -> Vec<u8> {
+    data.to_vec()
+}
+
 /// Convert big-endian decompressed bytes to i16 values.
 fn bytes_to_i16(data: &[u8]) -> Vec<i16> {
     data.chunks_exact(2)
@@ -434,6 +492,143 @@ fn bytes_to_f64(data: &[u8]) -> Vec<f64> {
         .collect()
 }
 
+// ---------------------------------------------------------------------------
+// Tile grid validation
+// ---------------------------------------------------------------------------
+
+/// Number of tiles needed to cover an image of `znaxes` with tiles of `ztile`.
+fn tile_grid_count(znaxes: &[usize], ztile: &[usize]) -> Result<usize> {
+    if ztile.len() != znaxes.len() {
+        return Err(Error::InvalidHeader(
+            "ZTILEn count does not match ZNAXIS in compressed image",
+        ));
+    }
+    znaxes
+        .iter()
+        .zip(ztile)
+        .try_fold(1usize, |count, (&n, &t)| {
+            if t == 0 {
+                return Err(Error::InvalidHeader("ZTILEn must be positive"));
+            }
+            count.checked_mul(n.div_ceil(t)).ok_or(Error::InvalidHeader(
+                "compressed image tile count overflows",
+            ))
+        })
+}
+
+/// Check that the binary table holds exactly one row per tile.
+fn validate_tile_grid(znaxes: &[usize], ztile: &[usize], naxis2: usize) -> Result<()> {
+    if tile_grid_count(znaxes, ztile)? != naxis2 {
+        return Err(Error::InvalidHeader(
+            "NAXIS2 does not match the tile count implied by ZNAXISn/ZTILEn",
+        ));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tile inspection
+// ---------------------------------------------------------------------------
+
+/// Location of one tile of a compressed image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileDescriptor {
+    /// Row of the compressed binary table holding the tile (0-based).
+    pub row: usize,
+    /// Tile coordinates along each image axis, 0-based with ZNAXIS1 first.
+    pub grid: Vec<usize>,
+    /// Column the tile's bytes are stored in: `"COMPRESSED_DATA"`,
+    /// `"GZIP_COMPRESSED_DATA"` or `"UNCOMPRESSED_DATA"`.
+    pub column: &'static str,
+    /// Absolute byte range of the stored tile within the file.
+    pub byte_range: Range<usize>,
+}
+
+/// List every tile of a compressed image with its grid position and the
+/// byte range of its stored data in the heap.
+///
+/// Tiles are returned in table row order. A tile with an empty
+/// COMPRESSED_DATA entry is reported from whichever fallback column holds
+/// its pixels; if none does, it is reported as an empty COMPRESSED_DATA range.
+pub fn tile_descriptors(fits_data: &[u8], hdu: &Hdu) -> Result<Vec<TileDescriptor>> {
+    let (znaxes, ztile, naxis1, naxis2, tfields) = match &hdu.info {
+        HduInfo::CompressedImage {
+            znaxes,
+            ztile,
+            naxis1,
+            naxis2,
+            tfields,
+            ..
+        } => (znaxes, ztile, *naxis1, *naxis2, *tfields),
+        _ => return Err(Error::InvalidHeader("not a compressed image HDU")),
+    };
+    if znaxes.is_empty() || znaxes.contains(&0) {
+        return Ok(Vec::new());
+    }
+    validate_tile_grid(znaxes, ztile, naxis2)?;
+    let col_info = parse_column_layout(&hdu.cards, tfields)?;
+    let tiles_per_axis: Vec<usize> = znaxes
+        .iter()
+        .zip(ztile)
+        .map(|(&n, &t)| n.div_ceil(t))
+        .collect();
+
+    let locate = |row: usize, offset: usize| {
+        tile_location(fits_data, hdu.data_start, naxis1, naxis2, row, offset)
+    };
+
+    let mut tiles = Vec::with_capacity(naxis2);
+    for row in 0..naxis2 {
+        let mut rest = row;
+        let grid = tiles_per_axis
+            .iter()
+            .map(|&count| {
+                let coord = rest % count;
+                rest /= count;
+                coord
+            })
+            .collect();
+
+        let (start, count) = locate(row, col_info.compressed_data_offset)?;
+        let mut column = "COMPRESSED_DATA";
+        let mut byte_range = start..start + count;
+        if count == 0 {
+            if let Some(offset) = col_info.gzip_data_offset {
+                let (start, count) = locate(row, offset)?;
+                if count > 0 {
+                    column = "GZIP_COMPRESSED_DATA";
+                    byte_range = start..start + count;
+                }
+            }
+        }
+        if byte_range.is_empty() {
+            if let Some((offset, elem_size)) = col_info.uncompressed_data {
+                let (start, count) = locate(row, offset)?;
+                // The descriptor counts elements, not bytes.
+                let end = count
+                    .checked_mul(elem_size)
+                    .and_then(|n| start.checked_add(n))
+                    .ok_or(Error::UnexpectedEof)?;
+                if end > fits_data.len() {
+                    return Err(Error::UnexpectedEof);
+                }
+                if count > 0 {
+                    column = "UNCOMPRESSED_DATA";
+                    byte_range = start..end;
+                }
+            }
+        }
+
+        tiles.push(TileDescriptor {
+            row,
+            grid,
+            column,
+            byte_range,
+        });
+    }
+    Ok(tiles)
+}
+
 // ---------------------------------------------------------------------------
 // Top-level decompression
 // ---------------------------------------------------------------------------
@@ -442,8 +637,14 @@ fn bytes_to_f64(data: &[u8]) -> Vec<f64> {
 ///
 /// The HDU must have `HduInfo::CompressedImage`. This function extracts
 /// each compressed tile from the binary table heap, decompresses it, and
-/// reassembles the full image.
+/// reassembles the full image. HDUs marked [`Hdu::little_endian`] are
+/// rejected with `Error::InvalidHeader`.
 pub fn read_tiled_image(fits_data: &[u8], hdu: &Hdu) -> Result<ImageData> {
+    if hdu.little_endian {
+        return Err(Error::InvalidHeader(
+            "little-endian compressed images are not supported",
+        ));
+    }
     let (
         zbitpix,
         znaxes,
@@ -484,11 +685,7 @@ pub fn read_tiled_image(fits_data: &[u8], hdu: &Hdu) -> Result<ImageData> {
 
     let _ = pcount; // used implicitly via heap
 
-    let total_pixels: usize = if znaxes.is_empty() {
-        0
-    } else {
-        znaxes.iter().copied().product()
-    };
+    let total_pixels = crate::image::checked_pixel_count(znaxes)?;
 
     if total_pixels == 0 {
         return match zbitpix {
@@ -502,6 +699,8 @@ pub fn read_tiled_image(fits_data: &[u8], hdu: &Hdu) -> Result<ImageData> {
         };
     }
 
+    validate_tile_grid(znaxes, ztile, naxis2)?;
+
     let col_info = parse_column_layout(&hdu.cards, tfields)?;
     let is_rice = zcmptype.contains("RICE");
     let is_gzip = zcmptype.contains("GZIP");
@@ -512,185 +711,205 @@ pub fn read_tiled_image(fits_data: &[u8], hdu: &Hdu) -> Result<ImageData> {
     }
 
     // Determine tile pixel count
-    let tile_pixels: usize = ztile.iter().copied().product();
+    let tile_pixels = crate::image::checked_pixel_count(ztile)?;
 
     // For float types with quantization, we need ZSCALE/ZZERO
     let is_quantized = (zbitpix == -32 || zbitpix == -64)
         && col_info.zscale_offset.is_some()
         && col_info.zzero_offset.is_some();
 
+    let tiles = TileRows {
+        fits_data,
+        hdu,
+        naxis1,
+        naxis2,
+        col_info: &col_info,
+        tile_pixels,
+        total_pixels,
+    };
     if is_rice {
         let params = RiceParams::for_bytepix(rice_bytepix)?;
-        decompress_rice_tiles(
-            fits_data,
-            hdu,
-            zbitpix,
-            total_pixels,
-            tile_pixels,
-            naxis1,
-            naxis2,
-            blocksize,
-            &params,
-            &col_info,
-            is_quantized,
-        )
+        decompress_rice_tiles(&tiles, zbitpix, blocksize, &params, is_quantized)
     } else {
-        decompress_gzip_tiles(
-            fits_data,
-            hdu,
-            zbitpix,
-            total_pixels,
-            tile_pixels,
-            naxis1,
-            naxis2,
-            &col_info,
-            is_quantized,
+        decompress_gzip_tiles(&tiles, zbitpix, is_quantized)
+    }
+}
+
+/// The tile rows of one compressed image, as laid out in its binary table.
+struct TileRows<'a> {
+    fits_data: &'a [u8],
+    hdu: &'a Hdu,
+    naxis1: usize,
+    naxis2: usize,
+    col_info: &'a ColumnInfo,
+    tile_pixels: usize,
+    total_pixels: usize,
+}
+
+impl TileRows<'_> {
+    /// Bytes of `row` in the column at `col_offset`, see [`extract_tile_bytes`].
+    fn column_bytes(&self, row: usize, col_offset: usize) -> Result<(&[u8], usize)> {
+        extract_tile_bytes(
+            self.fits_data,
+            self.hdu.data_start,
+            self.naxis1,
+            self.naxis2,
+            row,
+            col_offset,
+        )
+    }
+
+    /// COMPRESSED_DATA bytes of `row`.
+    fn compressed(&self, row: usize) -> Result<(&[u8], usize)> {
+        self.column_bytes(row, self.col_info.compressed_data_offset)
+    }
+
+    /// Read a tile stored in one of the fallback columns.
+    ///
+    /// Tiles that could not be compressed with the main algorithm have an
+    /// empty COMPRESSED_DATA entry and their pixels in GZIP_COMPRESSED_DATA
+    /// (gzip of the raw big-endian pixels) or UNCOMPRESSED_DATA (raw
+    /// big-endian pixels). Returns `None` if the tile is in COMPRESSED_DATA
+    /// as usual. Fallback pixels are never quantized, so `convert` reads them
+    /// directly in the ZBITPIX type.
+    fn fallback<T>(&self, row: usize, convert: fn(&[u8]) -> Vec<T>) -> Result<Option<Vec<T>>> {
+        let col_info = self.col_info;
+        if col_info.gzip_data_offset.is_none() && col_info.uncompressed_data.is_none() {
+            return Ok(None);
+        }
+        if self.compressed(row)?.1 > 0 {
+            return Ok(None);
+        }
+
+        if let Some(offset) = col_info.gzip_data_offset {
+            let (tile_data, count) = self.column_bytes(row, offset)?;
+            if count > 0 {
+                let raw = gzip_decompress(&tile_data[..count])?;
+                if raw.len() % core::mem::size_of::<T>() != 0 {
+                    return Err(Error::UnexpectedEof);
+                }
+                return Ok(Some(convert(&raw)));
+            }
+        }
+
+        if let Some((offset, elem_size)) = col_info.uncompressed_data {
+            let (tile_data, count) = self.column_bytes(row, offset)?;
+            if count > 0 {
+                if elem_size != core::mem::size_of::<T>() {
+                    return Err(Error::InvalidHeader(
+                        "UNCOMPRESSED_DATA element type does not match ZBITPIX",
+                    ));
+                }
+                // The descriptor counts elements, not bytes.
+                let len = count.checked_mul(elem_size).ok_or(Error::UnexpectedEof)?;
+                if tile_data.len() < len {
+                    return Err(Error::UnexpectedEof);
+                }
+                return Ok(Some(convert(&tile_data[..len])));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// ZSCALE and ZZERO of `row`; only called for quantized images.
+    fn scaling(&self, row: usize) -> (f64, f64) {
+        read_zscale_zzero(
+            self.fits_data,
+            self.hdu.data_start,
+            self.naxis1,
+            row,
+            self.col_info.zscale_offset.unwrap(),
+            self.col_info.zzero_offset.unwrap(),
         )
     }
+
+    /// Decode every tile in row order into one pixel buffer.
+    ///
+    /// Tiles stored in a fallback column are read with `convert` and must
+    /// hold exactly `pixels_in_tile` pixels, else `Error::UnexpectedEof`; all
+    /// others go through `decode(row, pixels_in_tile)` and contribute at most
+    /// that many. `pixels_in_tile` is smaller than a full tile only for the
+    /// last one.
+    fn collect<T: Copy>(
+        &self,
+        convert: fn(&[u8]) -> Vec<T>,
+        mut decode: impl FnMut(usize, usize) -> Result<Vec<T>>,
+    ) -> Result<Vec<T>> {
+        let mut output = Vec::with_capacity(self.total_pixels);
+        for row in 0..self.naxis2 {
+            let pixels_in_tile = self.tile_pixels.min(self.total_pixels - output.len());
+            let vals = match self.fallback(row, convert)? {
+                Some(vals) if vals.len() != pixels_in_tile => {
+                    return Err(Error::UnexpectedEof);
+                }
+                Some(vals) => vals,
+                None => decode(row, pixels_in_tile)?,
+            };
+            let count = vals.len().min(pixels_in_tile);
+            output.extend_from_slice(&vals[..count]);
+        }
+        Ok(output)
+    }
 }
 
-#[allow(clippy::too_many_arguments)]
 fn decompress_rice_tiles(
-    fits_data: &[u8],
-    hdu: &Hdu,
+    tiles: &TileRows,
     zbitpix: i64,
-    total_pixels: usize,
-    tile_pixels: usize,
-    naxis1: usize,
-    naxis2: usize,
     blocksize: usize,
     params: &RiceParams,
-    col_info: &ColumnInfo,
     is_quantized: bool,
 ) -> Result<ImageData> {
+    let rice = |row: usize, pixels_in_tile: usize| {
+        let (compressed, _tile_count) = tiles.compressed(row)?;
+        rice_decompress(compressed, pixels_in_tile, blocksize, params)
+    };
+
     if is_quantized && zbitpix == -32 {
-        let mut output = Vec::with_capacity(total_pixels);
-        for row in 0..naxis2 {
-            let (compressed, _tile_count) = extract_tile_bytes(
-                fits_data,
-                hdu.data_start,
-                naxis1,
-                naxis2,
-                row,
-                col_info.compressed_data_offset,
-            )?;
-            let pixels_in_tile = tile_pixels.min(total_pixels - output.len());
-            let int_vals = rice_decompress(compressed, pixels_in_tile, blocksize, params)?;
-
-            let (scale, zero) = read_zscale_zzero(
-                fits_data,
-                hdu.data_start,
-                naxis1,
-                row,
-                col_info.zscale_offset.unwrap(),
-                col_info.zzero_offset.unwrap(),
-            );
-            for &iv in &int_vals {
-                output.push((zero + scale * iv as f64) as f32);
-            }
-        }
+        let output = tiles.collect(bytes_to_f32, |row, pixels_in_tile| {
+            let int_vals = rice(row, pixels_in_tile)?;
+            let (scale, zero) = tiles.scaling(row);
+            Ok(int_vals
+                .iter()
+                .map(|&iv| (zero + scale * iv as f64) as f32)
+                .collect())
+        })?;
         Ok(ImageData::F32(output))
     } else if is_quantized && zbitpix == -64 {
-        let mut output = Vec::with_capacity(total_pixels);
-        for row in 0..naxis2 {
-            let (compressed, _tile_count) = extract_tile_bytes(
-                fits_data,
-                hdu.data_start,
-                naxis1,
-                naxis2,
-                row,
-                col_info.compressed_data_offset,
-            )?;
-            let pixels_in_tile = tile_pixels.min(total_pixels - output.len());
-            let int_vals = rice_decompress(compressed, pixels_in_tile, blocksize, params)?;
-
-            let (scale, zero) = read_zscale_zzero(
-                fits_data,
-                hdu.data_start,
-                naxis1,
-                row,
-                col_info.zscale_offset.unwrap(),
-                col_info.zzero_offset.unwrap(),
-            );
-            for &iv in &int_vals {
-                output.push(zero + scale * iv as f64);
-            }
-        }
+        let output = tiles.collect(bytes_to_f64, |row, pixels_in_tile| {
+            let int_vals = rice(row, pixels_in_tile)?;
+            let (scale, zero) = tiles.scaling(row);
+            Ok(int_vals
+                .iter()
+                .map(|&iv| zero + scale * iv as f64)
+                .collect())
+        })?;
         Ok(ImageData::F64(output))
     } else {
         match zbitpix {
             8 => {
-                let mut output = Vec::with_capacity(total_pixels);
-                for row in 0..naxis2 {
-                    let (compressed, _tile_count) = extract_tile_bytes(
-                        fits_data,
-                        hdu.data_start,
-                        naxis1,
-                        naxis2,
-                        row,
-                        col_info.compressed_data_offset,
-                    )?;
-                    let pixels_in_tile = tile_pixels.min(total_pixels - output.len());
-                    let vals = rice_decompress(compressed, pixels_in_tile, blocksize, params)?;
-                    for &v in &vals {
-                        output.push(v as u8);
-                    }
-                }
+                let output = tiles.collect(bytes_to_u8, |row, pixels_in_tile| {
+                    let vals = rice(row, pixels_in_tile)?;
+                    Ok(vals.iter().map(|&v| v as u8).collect())
+                })?;
                 Ok(ImageData::U8(output))
             }
             16 => {
-                let mut output = Vec::with_capacity(total_pixels);
-                for row in 0..naxis2 {
-                    let (compressed, _tile_count) = extract_tile_bytes(
-                        fits_data,
-                        hdu.data_start,
-                        naxis1,
-                        naxis2,
-                        row,
-                        col_info.compressed_data_offset,
-                    )?;
-                    let pixels_in_tile = tile_pixels.min(total_pixels - output.len());
-                    let vals = rice_decompress(compressed, pixels_in_tile, blocksize, params)?;
-                    for &v in &vals {
-                        output.push(v as i16);
-                    }
-                }
+                let output = tiles.collect(bytes_to_i16, |row, pixels_in_tile| {
+                    let vals = rice(row, pixels_in_tile)?;
+                    Ok(vals.iter().map(|&v| v as i16).collect())
+                })?;
                 Ok(ImageData::I16(output))
             }
             32 => {
-                let mut output = Vec::with_capacity(total_pixels);
-                for row in 0..naxis2 {
-                    let (compressed, _tile_count) = extract_tile_bytes(
-                        fits_data,
-                        hdu.data_start,
-                        naxis1,
-                        naxis2,
-                        row,
-                        col_info.compressed_data_offset,
-                    )?;
-                    let pixels_in_tile = tile_pixels.min(total_pixels - output.len());
-                    let vals = rice_decompress(compressed, pixels_in_tile, blocksize, params)?;
-                    output.extend_from_slice(&vals);
-                }
+                let output = tiles.collect(bytes_to_i32, rice)?;
                 Ok(ImageData::I32(output))
             }
             64 => {
-                let mut output = Vec::with_capacity(total_pixels);
-                for row in 0..naxis2 {
-                    let (compressed, _tile_count) = extract_tile_bytes(
-                        fits_data,
-                        hdu.data_start,
-                        naxis1,
-                        naxis2,
-                        row,
-                        col_info.compressed_data_offset,
-                    )?;
-                    let pixels_in_tile = tile_pixels.min(total_pixels - output.len());
-                    let vals = rice_decompress(compressed, pixels_in_tile, blocksize, params)?;
-                    for &v in &vals {
-                        output.push(v as i64);
-                    }
-                }
+                let output = tiles.collect(bytes_to_i64, |row, pixels_in_tile| {
+                    let vals = rice(row, pixels_in_tile)?;
+                    Ok(vals.iter().map(|&v| v as i64).collect())
+                })?;
                 Ok(ImageData::I64(output))
             }
             other => Err(Error::InvalidBitpix(other)),
@@ -698,207 +917,76 @@ fn decompress_rice_tiles(
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-fn decompress_gzip_tiles(
-    fits_data: &[u8],
-    hdu: &Hdu,
-    zbitpix: i64,
-    total_pixels: usize,
-    tile_pixels: usize,
-    naxis1: usize,
-    naxis2: usize,
-    col_info: &ColumnInfo,
-    is_quantized: bool,
-) -> Result<ImageData> {
+fn decompress_gzip_tiles(tiles: &TileRows, zbitpix: i64, is_quantized: bool) -> Result<ImageData> {
+    let gunzip = |row: usize| {
+        let (tile_data, tile_count) = tiles.compressed(row)?;
+        gzip_decompress(&tile_data[..tile_count])
+    };
+
     if is_quantized && zbitpix == -32 {
-        let mut output = Vec::with_capacity(total_pixels);
-        for row in 0..naxis2 {
-            let (tile_data, tile_count) = extract_tile_bytes(
-                fits_data,
-                hdu.data_start,
-                naxis1,
-                naxis2,
-                row,
-                col_info.compressed_data_offset,
-            )?;
-            let raw = gzip_decompress(&tile_data[..tile_count])?;
-            let int_vals = bytes_to_i32(&raw);
-
-            let (scale, zero) = read_zscale_zzero(
-                fits_data,
-                hdu.data_start,
-                naxis1,
-                row,
-                col_info.zscale_offset.unwrap(),
-                col_info.zzero_offset.unwrap(),
-            );
-            let count = int_vals
-                .len()
-                .min(tile_pixels)
-                .min(total_pixels - output.len());
-            for &iv in &int_vals[..count] {
-                output.push((zero + scale * iv as f64) as f32);
-            }
-        }
+        let output = tiles.collect(bytes_to_f32, |row, _| {
+            let int_vals = bytes_to_i32(&gunzip(row)?);
+            let (scale, zero) = tiles.scaling(row);
+            Ok(int_vals
+                .iter()
+                .map(|&iv| (zero + scale * iv as f64) as f32)
+                .collect())
+        })?;
         Ok(ImageData::F32(output))
     } else if is_quantized && zbitpix == -64 {
-        let mut output = Vec::with_capacity(total_pixels);
-        for row in 0..naxis2 {
-            let (tile_data, tile_count) = extract_tile_bytes(
-                fits_data,
-                hdu.data_start,
-                naxis1,
-                naxis2,
-                row,
-                col_info.compressed_data_offset,
-            )?;
-            let raw = gzip_decompress(&tile_data[..tile_count])?;
-            let int_vals = bytes_to_i32(&raw);
-
-            let (scale, zero) = read_zscale_zzero(
-                fits_data,
-                hdu.data_start,
-                naxis1,
-                row,
-                col_info.zscale_offset.unwrap(),
-                col_info.zzero_offset.unwrap(),
-            );
-            let count = int_vals
-                .len()
-                .min(tile_pixels)
-                .min(total_pixels - output.len());
-            for &iv in &int_vals[..count] {
-                output.push(zero + scale * iv as f64);
-            }
-        }
+        let output = tiles.collect(bytes_to_f64, |row, _| {
+            let int_vals = bytes_to_i32(&gunzip(row)?);
+            let (scale, zero) = tiles.scaling(row);
+            Ok(int_vals
+                .iter()
+                .map(|&iv| zero + scale * iv as f64)
+                .collect())
+        })?;
         Ok(ImageData::F64(output))
     } else {
         match zbitpix {
             8 => {
-                let mut output = Vec::with_capacity(total_pixels);
-                for row in 0..naxis2 {
-                    let (tile_data, tile_count) = extract_tile_bytes(
-                        fits_data,
-                        hdu.data_start,
-                        naxis1,
-                        naxis2,
-                        row,
-                        col_info.compressed_data_offset,
-                    )?;
-                    let raw = gzip_decompress(&tile_data[..tile_count])?;
-                    let remaining = total_pixels - output.len();
-                    if raw.len() == tile_pixels * 4 {
+                let output = tiles.collect(bytes_to_u8, |row, _| {
+                    let raw = gunzip(row)?;
+                    if raw.len() == tiles.tile_pixels * 4 {
                         // cfitsio encodes as i32; truncate to u8
-                        let vals = bytes_to_i32(&raw);
-                        let count = vals.len().min(tile_pixels).min(remaining);
-                        for &v in &vals[..count] {
-                            output.push(v as u8);
-                        }
+                        Ok(bytes_to_i32(&raw).iter().map(|&v| v as u8).collect())
                     } else {
-                        let count = raw.len().min(tile_pixels).min(remaining);
-                        output.extend_from_slice(&raw[..count]);
+                        Ok(raw)
                     }
-                }
+                })?;
                 Ok(ImageData::U8(output))
             }
             16 => {
-                let mut output = Vec::with_capacity(total_pixels);
-                for row in 0..naxis2 {
-                    let (tile_data, tile_count) = extract_tile_bytes(
-                        fits_data,
-                        hdu.data_start,
-                        naxis1,
-                        naxis2,
-                        row,
-                        col_info.compressed_data_offset,
-                    )?;
-                    let raw = gzip_decompress(&tile_data[..tile_count])?;
-                    let remaining = total_pixels - output.len();
-                    if raw.len() == tile_pixels * 4 {
+                let output = tiles.collect(bytes_to_i16, |row, _| {
+                    let raw = gunzip(row)?;
+                    if raw.len() == tiles.tile_pixels * 4 {
                         // cfitsio encodes as i32; truncate to i16
-                        let vals = bytes_to_i32(&raw);
-                        let count = vals.len().min(tile_pixels).min(remaining);
-                        for &v in &vals[..count] {
-                            output.push(v as i16);
-                        }
+                        Ok(bytes_to_i32(&raw).iter().map(|&v| v as i16).collect())
                     } else {
-                        let vals = bytes_to_i16(&raw);
-                        let count = vals.len().min(tile_pixels).min(remaining);
-                        output.extend_from_slice(&vals[..count]);
+                        Ok(bytes_to_i16(&raw))
                     }
-                }
+                })?;
                 Ok(ImageData::I16(output))
             }
             32 => {
-                let mut output = Vec::with_capacity(total_pixels);
-                for row in 0..naxis2 {
-                    let (tile_data, tile_count) = extract_tile_bytes(
-                        fits_data,
-                        hdu.data_start,
-                        naxis1,
-                        naxis2,
-                        row,
-                        col_info.compressed_data_offset,
-                    )?;
-                    let raw = gzip_decompress(&tile_data[..tile_count])?;
-                    let vals = bytes_to_i32(&raw);
-                    let count = vals.len().min(tile_pixels).min(total_pixels - output.len());
-                    output.extend_from_slice(&vals[..count]);
-                }
+                let output =
+                    tiles.collect(bytes_to_i32, |row, _| Ok(bytes_to_i32(&gunzip(row)?)))?;
                 Ok(ImageData::I32(output))
             }
             64 => {
-                let mut output = Vec::with_capacity(total_pixels);
-                for row in 0..naxis2 {
-                    let (tile_data, tile_count) = extract_tile_bytes(
-                        fits_data,
-                        hdu.data_start,
-                        naxis1,
-                        naxis2,
-                        row,
-                        col_info.compressed_data_offset,
-                    )?;
-                    let raw = gzip_decompress(&tile_data[..tile_count])?;
-                    let vals = bytes_to_i64(&raw);
-                    let count = vals.len().min(tile_pixels).min(total_pixels - output.len());
-                    output.extend_from_slice(&vals[..count]);
-                }
+                let output =
+                    tiles.collect(bytes_to_i64, |row, _| Ok(bytes_to_i64(&gunzip(row)?)))?;
                 Ok(ImageData::I64(output))
             }
             -32 => {
-                let mut output = Vec::with_capacity(total_pixels);
-                for row in 0..naxis2 {
-                    let (tile_data, tile_count) = extract_tile_bytes(
-                        fits_data,
-                        hdu.data_start,
-                        naxis1,
-                        naxis2,
-                        row,
-                        col_info.compressed_data_offset,
-                    )?;
-                    let raw = gzip_decompress(&tile_data[..tile_count])?;
-                    let vals = bytes_to_f32(&raw);
-                    let count = vals.len().min(tile_pixels).min(total_pixels - output.len());
-                    output.extend_from_slice(&vals[..count]);
-                }
+                let output =
+                    tiles.collect(bytes_to_f32, |row, _| Ok(bytes_to_f32(&gunzip(row)?)))?;
                 Ok(ImageData::F32(output))
             }
             -64 => {
-                let mut output = Vec::with_capacity(total_pixels);
-                for row in 0..naxis2 {
-                    let (tile_data, tile_count) = extract_tile_bytes(
-                        fits_data,
-                        hdu.data_start,
-                        naxis1,
-                        naxis2,
-                        row,
-                        col_info.compressed_data_offset,
-                    )?;
-                    let raw = gzip_decompress(&tile_data[..tile_count])?;
-                    let vals = bytes_to_f64(&raw);
-                    let count = vals.len().min(tile_pixels).min(total_pixels - output.len());
-                    output.extend_from_slice(&vals[..count]);
-                }
+                let output =
+                    tiles.collect(bytes_to_f64, |row, _| Ok(bytes_to_f64(&gunzip(row)?)))?;
                 Ok(ImageData::F64(output))
             }
             other => Err(Error::InvalidBitpix(other)),
@@ -923,6 +1011,7 @@ fn read_zscale_zzero(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn test_rice_params() {
@@ -975,4 +1064,366 @@ mod tests {
         let result = rice_decompress(&data, 5, blocksize, &params).unwrap();
         assert_eq!(result, vec![42, 42, 42, 42, 42]);
     }
+
+    // -----------------------------------------------------------------------
+    // GZIP_1 tiles (no `std` required)
+    // -----------------------------------------------------------------------
+
+    fn card(keyword: &str, value: Value) -> Card {
+        let mut kw = [b' '; 8];
+        kw[..keyword.len()].copy_from_slice(keyword.as_bytes());
+        Card {
+            keyword: kw,
+            value: Some(value),
+            comment: None,
+        }
+    }
+
+    /// Wrap a raw deflate stream in a minimal gzip member. The CRC32 is left
+    /// as zero since the decoder does not check it.
+    fn gzip_wrap(deflate: &[u8], isize: usize) -> Vec<u8> {
+        let mut out = vec![0x1f, 0x8b, 0x08, 0, 0, 0, 0, 0, 0, 0xff];
+        out.extend_from_slice(deflate);
+        out.extend_from_slice(&[0, 0, 0, 0]);
+        out.extend_from_slice(&(isize as u32).to_le_bytes());
+        out
+    }
+
+    /// Build a FITS file with a GZIP_1 compressed 16-bit image, one tile per row.
+    fn build_gzip_i16_fits(width: usize, rows: &[Vec<i16>]) -> Vec<u8> {
+        build_gzip_i16_fits_with_height(width, rows.len(), rows)
+    }
+
+    /// Like [`build_gzip_i16_fits`], but declares `height` image rows in
+    /// ZNAXIS2 regardless of how many tiles are stored.
+    fn build_gzip_i16_fits_with_height(width: usize, height: usize, rows: &[Vec<i16>]) -> Vec<u8> {
+        let mut heap = Vec::new();
+        let mut descriptors = Vec::new();
+        for (i, row) in rows.iter().enumerate() {
+            let raw: Vec<u8> = row.iter().flat_map(|v| v.to_be_bytes()).collect();
+            // Alternate between gzip and zlib framing; both occur in the wild.
+            let tile = if i % 2 == 0 {
+                gzip_wrap(&miniz_oxide::deflate::compress_to_vec(&raw, 6), raw.len())
+            } else {
+                miniz_oxide::deflate::compress_to_vec_zlib(&raw, 6)
+            };
+            descriptors.extend_from_slice(&(tile.len() as u32).to_be_bytes());
+            descriptors.extend_from_slice(&(heap.len() as u32).to_be_bytes());
+            heap.extend_from_slice(&tile);
+        }
+
+        let primary = vec![
+            card("SIMPLE", Value::Logical(true)),
+            card("BITPIX", Value::Integer(8)),
+            card("NAXIS", Value::Integer(0)),
+        ];
+        let ext = vec![
+            card("XTENSION", Value::String(String::from("BINTABLE"))),
+            card("BITPIX", Value::Integer(8)),
+            card("NAXIS", Value::Integer(2)),
+            card("NAXIS1", Value::Integer(8)),
+            card("NAXIS2", Value::Integer(rows.len() as i64)),
+            card("PCOUNT", Value::Integer(heap.len() as i64)),
+            card("GCOUNT", Value::Integer(1)),
+            card("TFIELDS", Value::Integer(1)),
+            card("TTYPE1", Value::String(String::from("COMPRESSED_DATA"))),
+            card("TFORM1", Value::String(String::from("1PB"))),
+            card("ZIMAGE", Value::Logical(true)),
+            card("ZBITPIX", Value::Integer(16)),
+            card("ZNAXIS", Value::Integer(2)),
+            card("ZNAXIS1", Value::Integer(width as i64)),
+            card("ZNAXIS2", Value::Integer(height as i64)),
+            card("ZTILE1", Value::Integer(width as i64)),
+            card("ZTILE2", Value::Integer(1)),
+            card("ZCMPTYPE", Value::String(String::from("GZIP_1"))),
+        ];
+
+        let mut fits = crate::header::serialize_header(&primary).unwrap();
+        fits.extend_from_slice(&crate::header::serialize_header(&ext).unwrap());
+        let data_start = fits.len();
+        fits.extend_from_slice(&descriptors);
+        fits.extend_from_slice(&heap);
+        fits.resize(
+            data_start + crate::block::padded_byte_len(fits.len() - data_start),
+            0,
+        );
+        fits
+    }
+
+    #[test]
+    fn read_gzip_quantized_float_tiles() {
+        // Each row is one tile with its own ZSCALE/ZZERO.
+        let quantized = [vec![0i32, 1, 2, -3], vec![10, -20, 30, 0]];
+        let scaling = [(0.5f64, 100.0f64), (0.25, -1.0)];
+
+        let mut table = Vec::new();
+        let mut heap = Vec::new();
+        for (ints, &(zscale, zzero)) in quantized.iter().zip(&scaling) {
+            let raw: Vec<u8> = ints.iter().flat_map(|v| v.to_be_bytes()).collect();
+            let tile = miniz_oxide::deflate::compress_to_vec_zlib(&raw, 6);
+            table.extend_from_slice(&(tile.len() as u32).to_be_bytes());
+            table.extend_from_slice(&(heap.len() as u32).to_be_bytes());
+            table.extend_from_slice(&zscale.to_be_bytes());
+            table.extend_from_slice(&zzero.to_be_bytes());
+            heap.extend_from_slice(&tile);
+        }
+
+        let primary = vec![
+            card("SIMPLE", Value::Logical(true)),
+            card("BITPIX", Value::Integer(8)),
+            card("NAXIS", Value::Integer(0)),
+        ];
+        let ext = vec![
+            card("XTENSION", Value::String(String::from("BINTABLE"))),
+            card("BITPIX", Value::Integer(8)),
+            card("NAXIS", Value::Integer(2)),
+            card("NAXIS1", Value::Integer(24)),
+            card("NAXIS2", Value::Integer(2)),
+            card("PCOUNT", Value::Integer(heap.len() as i64)),
+            card("GCOUNT", Value::Integer(1)),
+            card("TFIELDS", Value::Integer(3)),
+            card("TTYPE1", Value::String(String::from("COMPRESSED_DATA"))),
+            card("TFORM1", Value::String(String::from("1PB"))),
+            card("TTYPE2", Value::String(String::from("ZSCALE"))),
+            card("TFORM2", Value::String(String::from("1D"))),
+            card("TTYPE3", Value::String(String::from("ZZERO"))),
+            card("TFORM3", Value::String(String::from("1D"))),
+            card("ZIMAGE", Value::Logical(true)),
+            card("ZBITPIX", Value::Integer(-32)),
+            card("ZNAXIS", Value::Integer(2)),
+            card("ZNAXIS1", Value::Integer(4)),
+            card("ZNAXIS2", Value::Integer(2)),
+            card("ZTILE1", Value::Integer(4)),
+            card("ZTILE2", Value::Integer(1)),
+            card("ZCMPTYPE", Value::String(String::from("GZIP_1"))),
+        ];
+
+        let mut fits = crate::header::serialize_header(&primary).unwrap();
+        fits.extend_from_slice(&crate::header::serialize_header(&ext).unwrap());
+        let data_start = fits.len();
+        fits.extend_from_slice(&table);
+        fits.extend_from_slice(&heap);
+        fits.resize(
+            data_start + crate::block::padded_byte_len(fits.len() - data_start),
+            0,
+        );
+
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = parsed.get(1).unwrap();
+        assert_eq!(
+            read_tiled_image(&fits, hdu).unwrap(),
+            ImageData::F32(vec![100.0, 100.5, 101.0, 98.5, 1.5, -6.0, 6.5, -1.0])
+        );
+    }
+
+    #[test]
+    fn gzip_decompress_gzip_and_zlib_framing() {
+        let raw: Vec<u8> = (0..200u8).collect();
+        let gz = gzip_wrap(&miniz_oxide::deflate::compress_to_vec(&raw, 6), raw.len());
+        assert_eq!(gzip_decompress(&gz).unwrap(), raw);
+
+        let zlib = miniz_oxide::deflate::compress_to_vec_zlib(&raw, 6);
+        assert_eq!(gzip_decompress(&zlib).unwrap(), raw);
+    }
+
+    #[test]
+    fn read_gzip_tiled_i16_image() {
+        let rows = vec![
+            vec![1i16, 2, 3, 4],
+            vec![-5, 600, -7000, 8],
+            vec![i16::MIN, 0, i16::MAX, 9],
+        ];
+        let fits = build_gzip_i16_fits(4, &rows);
+
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = parsed.get(1).unwrap();
+        assert!(matches!(hdu.info, HduInfo::CompressedImage { .. }));
+
+        let expected: Vec<i16> = rows.concat();
+        assert_eq!(
+            read_tiled_image(&fits, hdu).unwrap(),
+            ImageData::I16(expected)
+        );
+    }
+
+    #[test]
+    fn tile_grid_count_rounds_up_partial_tiles() {
+        assert_eq!(tile_grid_count(&[100, 50], &[100, 1]).unwrap(), 50);
+        assert_eq!(tile_grid_count(&[10, 10], &[4, 3]).unwrap(), 12);
+        assert!(tile_grid_count(&[10, 10], &[0, 3]).is_err());
+        assert!(tile_grid_count(&[10, 10], &[10]).is_err());
+    }
+
+    #[test]
+    fn mismatched_naxis2_is_rejected() {
+        let rows = vec![vec![1i16, 2, 3, 4], vec![5, 6, 7, 8]];
+        // Three image rows declared, but only two tiles stored.
+        let fits = build_gzip_i16_fits_with_height(4, 3, &rows);
+
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = parsed.get(1).unwrap();
+        assert!(matches!(
+            read_tiled_image(&fits, hdu),
+            Err(Error::InvalidHeader(msg)) if msg.contains("NAXIS2")
+        ));
+    }
+
+    /// Build a RICE_1 compressed 4x3 16-bit image whose second and third
+    /// tiles fall back to UNCOMPRESSED_DATA and GZIP_COMPRESSED_DATA.
+    fn build_rice_fits_with_fallback_tiles() -> Vec<u8> {
+        let width = 4;
+        let rice_tile = vec![0u8, 42, 0x00];
+        let raw_tile: Vec<u8> = [1i16, -2, 3, -4]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect();
+        let gzip_raw: Vec<u8> = [5i16, 6, 7, 8]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect();
+        let gzip_tile = miniz_oxide::deflate::compress_to_vec_zlib(&gzip_raw, 6);
+
+        // Row layout: COMPRESSED_DATA, GZIP_COMPRESSED_DATA, UNCOMPRESSED_DATA
+        let mut heap = Vec::new();
+        let mut table = Vec::new();
+        let mut descriptor = |count: usize, bytes: &[u8], heap: &mut Vec<u8>| {
+            table.extend_from_slice(&(count as u32).to_be_bytes());
+            table.extend_from_slice(&(heap.len() as u32).to_be_bytes());
+            heap.extend_from_slice(bytes);
+        };
+        descriptor(rice_tile.len(), &rice_tile, &mut heap);
+        descriptor(0, &[], &mut heap);
+        descriptor(0, &[], &mut heap);
+        descriptor(0, &[], &mut heap);
+        descriptor(0, &[], &mut heap);
+        descriptor(width, &raw_tile, &mut heap);
+        descriptor(0, &[], &mut heap);
+        descriptor(gzip_tile.len(), &gzip_tile, &mut heap);
+        descriptor(0, &[], &mut heap);
+
+        let primary = vec![
+            card("SIMPLE", Value::Logical(true)),
+            card("BITPIX", Value::Integer(8)),
+            card("NAXIS", Value::Integer(0)),
+        ];
+        let ext = vec![
+            card("XTENSION", Value::String(String::from("BINTABLE"))),
+            card("BITPIX", Value::Integer(8)),
+            card("NAXIS", Value::Integer(2)),
+            card("NAXIS1", Value::Integer(24)),
+            card("NAXIS2", Value::Integer(3)),
+            card("PCOUNT", Value::Integer(heap.len() as i64)),
+            card("GCOUNT", Value::Integer(1)),
+            card("TFIELDS", Value::Integer(3)),
+            card("TTYPE1", Value::String(String::from("COMPRESSED_DATA"))),
+            card("TFORM1", Value::String(String::from("1PB"))),
+            card(
+                "TTYPE2",
+                Value::String(String::from("GZIP_COMPRESSED_DATA")),
+            ),
+            card("TFORM2", Value::String(String::from("1PB"))),
+            card("TTYPE3", Value::String(String::from("UNCOMPRESSED_DATA"))),
+            card("TFORM3", Value::String(String::from("1PI"))),
+            card("ZIMAGE", Value::Logical(true)),
+            card("ZBITPIX", Value::Integer(16)),
+            card("ZNAXIS", Value::Integer(2)),
+            card("ZNAXIS1", Value::Integer(width as i64)),
+            card("ZNAXIS2", Value::Integer(3)),
+            card("ZTILE1", Value::Integer(width as i64)),
+            card("ZTILE2", Value::Integer(1)),
+            card("ZCMPTYPE", Value::String(String::from("RICE_1"))),
+            card("ZVAL1", Value::Integer(4)),
+            card("ZVAL2", Value::Integer(2)),
+        ];
+
+        let mut fits = crate::header::serialize_header(&primary).unwrap();
+        fits.extend_from_slice(&crate::header::serialize_header(&ext).unwrap());
+        let data_start = fits.len();
+        fits.extend_from_slice(&table);
+        fits.extend_from_slice(&heap);
+        fits.resize(
+            data_start + crate::block::padded_byte_len(fits.len() - data_start),
+            0,
+        );
+        fits
+    }
+
+    #[test]
+    fn read_rice_image_with_fallback_tiles() {
+        let fits = build_rice_fits_with_fallback_tiles();
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = parsed.get(1).unwrap();
+        assert_eq!(
+            read_tiled_image(&fits, hdu).unwrap(),
+            ImageData::I16(vec![42, 42, 42, 42, 1, -2, 3, -4, 5, 6, 7, 8])
+        );
+    }
+
+    #[test]
+    fn short_fallback_tile_is_rejected() {
+        let mut fits = build_rice_fits_with_fallback_tiles();
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        // Shrink the second row's UNCOMPRESSED_DATA descriptor to 3 elements.
+        let count_pos = parsed.get(1).unwrap().data_start + 24 + 16;
+        fits[count_pos..count_pos + 4].copy_from_slice(&3u32.to_be_bytes());
+
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        assert!(matches!(
+            read_tiled_image(&fits, parsed.get(1).unwrap()),
+            Err(Error::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn tile_descriptors_of_rice_image() {
+        let fits = build_rice_fits_with_fallback_tiles();
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        let hdu = parsed.get(1).unwrap();
+        let heap_start = hdu.data_start + 24 * 3;
+
+        let tiles = tile_descriptors(&fits, hdu).unwrap();
+        assert_eq!(tiles.len(), 3);
+        assert_eq!(
+            tiles[0],
+            TileDescriptor {
+                row: 0,
+                grid: vec![0, 0],
+                column: "COMPRESSED_DATA",
+                byte_range: heap_start..heap_start + 3,
+            }
+        );
+        assert_eq!(tiles[1].grid, vec![0, 1]);
+        assert_eq!(tiles[1].column, "UNCOMPRESSED_DATA");
+        assert_eq!(tiles[1].byte_range, heap_start + 3..heap_start + 11);
+        assert_eq!(tiles[2].grid, vec![0, 2]);
+        assert_eq!(tiles[2].column, "GZIP_COMPRESSED_DATA");
+        assert_eq!(tiles[2].byte_range.start, heap_start + 11);
+
+        assert!(tile_descriptors(&fits, parsed.primary()).is_err());
+    }
+
+    #[test]
+    fn images_decodes_compressed_and_plain_hdus() {
+        use crate::extension::{build_extension_header, ExtensionType};
+
+        let rows = vec![vec![1i16, 2, 3], vec![4, 5, 6]];
+        let mut fits = build_gzip_i16_fits(3, &rows);
+        let ext = build_extension_header(ExtensionType::Image, -32, &[2], 0, 1).unwrap();
+        fits.extend_from_slice(&crate::header::serialize_header(&ext).unwrap());
+        fits.extend_from_slice(&crate::image::serialize_image_f32(&[0.5, -1.5]));
+
+        let parsed = crate::hdu::parse_fits(&fits).unwrap();
+        assert_eq!(parsed.len(), 3);
+        let images: Vec<(usize, ImageData)> = parsed
+            .images(&fits)
+            .map(|(i, image)| (i, image.unwrap()))
+            .collect();
+        assert_eq!(
+            images,
+            vec![
+                (1, ImageData::I16(rows.concat())),
+                (2, ImageData::F32(vec![0.5, -1.5])),
+            ]
+        );
+    }
 }
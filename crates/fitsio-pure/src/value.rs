@@ -4,6 +4,7 @@ use core::str;
 
 /// A parsed FITS header value.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Value {
     /// FITS logical value (`T` or `F`).
     Logical(bool),
@@ -17,6 +18,9 @@ pub enum Value {
     ComplexInt(i64, i64),
     /// FITS complex float `(real, imaginary)`.
     ComplexFloat(f64, f64),
+    /// Undefined value: the card has a value indicator but a blank value
+    /// field, optionally followed by a comment.
+    Undefined,
 }
 
 /// Split a value field at the comment separator.
@@ -28,7 +32,7 @@ pub enum Value {
 /// produced by IDL and other tools omit the trailing space (e.g.
 /// `BITPIX = -32 /No. of bits per pixel`).  Both cfitsio and fitsrs accept
 /// ` /` without requiring a trailing space, so we do the same.
-fn split_comment(field: &[u8]) -> (&[u8], Option<&str>) {
+fn split_comment(field: &[u8]) -> (&[u8], Option<&[u8]>) {
     // For string values the comment starts after the closing quote, so the
     // caller must handle strings separately.  For non-string values we scan
     // for ` /` (space then slash).
@@ -42,10 +46,7 @@ fn split_comment(field: &[u8]) -> (&[u8], Option<&str>) {
             if comment_start < len && field[comment_start] == b' ' {
                 comment_start += 1;
             }
-            let comment = str::from_utf8(&field[comment_start..])
-                .ok()
-                .map(|s| s.trim_end());
-            return (value_part, comment.filter(|s| !s.is_empty()));
+            return (value_part, Some(&field[comment_start..]));
         }
         i += 1;
     }
@@ -58,7 +59,7 @@ fn split_comment(field: &[u8]) -> (&[u8], Option<&str>) {
 /// continues until the closing `'` (doubled single-quotes `''` inside the
 /// string represent a literal `'`).  Everything after the closing quote is
 /// either whitespace or a ` / ` comment separator followed by the comment.
-fn parse_string(field: &[u8]) -> Option<(Value, Option<&str>)> {
+fn parse_string(field: &[u8]) -> Option<(Value, Option<&[u8]>)> {
     if field.is_empty() || field[0] != b'\'' {
         return None;
     }
@@ -99,8 +100,17 @@ fn parse_string(field: &[u8]) -> Option<(Value, Option<&str>)> {
 }
 
 /// Given the bytes after a closing string quote, find the comment if present.
-fn find_comment_in_remainder(remainder: &[u8]) -> Option<&str> {
+fn find_comment_in_remainder(remainder: &[u8]) -> Option<&[u8]> {
     let len = remainder.len();
+    // The separator may follow the closing quote directly: `'abc'/ comment`.
+    let first = remainder.iter().position(|&b| b != b' ')?;
+    if remainder[first] == b'/' {
+        let mut comment_start = first + 1;
+        if comment_start < len && remainder[comment_start] == b' ' {
+            comment_start += 1;
+        }
+        return Some(&remainder[comment_start..]);
+    }
     let mut i = 0;
     while i + 1 < len {
         if remainder[i] == b' ' && remainder[i + 1] == b'/' {
@@ -109,10 +119,7 @@ fn find_comment_in_remainder(remainder: &[u8]) -> Option<&str> {
             if comment_start < len && remainder[comment_start] == b' ' {
                 comment_start += 1;
             }
-            let comment = str::from_utf8(&remainder[comment_start..])
-                .ok()
-                .map(|s| s.trim_end());
-            return comment.filter(|s| !s.is_empty());
+            return Some(&remainder[comment_start..]);
         }
         i += 1;
     }
@@ -144,7 +151,7 @@ fn parse_complex(text: &str) -> Option<Value> {
 }
 
 /// Parse a float string, handling FITS `D` exponent notation.
-fn parse_float_str(s: &str) -> Option<f64> {
+pub(crate) fn parse_float_str(s: &str) -> Option<f64> {
     let normalized = s.replace('D', "E").replace('d', "e");
     normalized.parse::<f64>().ok()
 }
@@ -157,13 +164,27 @@ fn parse_float_str(s: &str) -> Option<f64> {
 /// The caller is responsible for checking that bytes 8..10 of the card are
 /// `= ` (the value indicator) before calling this function.
 pub fn parse_value(value_bytes: &[u8]) -> Option<(Value, Option<&str>)> {
+    let (value, comment) = parse_value_raw(value_bytes)?;
+    let comment = comment
+        .and_then(|c| str::from_utf8(c).ok())
+        .map(str::trim_end)
+        .filter(|s| !s.is_empty());
+    Some((value, comment))
+}
+
+/// Parse a value field like [`parse_value`], returning the comment as raw,
+/// untrimmed bytes so the caller can decode it in a non-UTF-8 encoding.
+pub(crate) fn parse_value_raw(value_bytes: &[u8]) -> Option<(Value, Option<&[u8]>)> {
     if value_bytes.is_empty() {
         return None;
     }
 
-    // 1. String values: first non-space byte is a single quote.
-    if value_bytes[0] == b'\'' {
-        return parse_string(value_bytes);
+    // 1. String values: first non-space byte is a single quote. A ` / `
+    //    inside the quotes is part of the string, not a comment separator.
+    if let Some(start) = value_bytes.iter().position(|&b| b != b' ') {
+        if value_bytes[start] == b'\'' {
+            return parse_string(&value_bytes[start..]);
+        }
     }
 
     // For all other types, split off the comment first.
@@ -171,7 +192,7 @@ pub fn parse_value(value_bytes: &[u8]) -> Option<(Value, Option<&str>)> {
 
     let val_text = str::from_utf8(val_part).ok()?.trim();
     if val_text.is_empty() {
-        return None;
+        return Some((Value::Undefined, comment));
     }
 
     // 2. Logical: `T` or `F` — standard puts it in byte 30 of the card
@@ -214,9 +235,11 @@ pub fn parse_value(value_bytes: &[u8]) -> Option<(Value, Option<&str>)> {
 /// Serialize a [`Value`] into a 70-byte field suitable for bytes 10..80 of an
 /// 80-byte FITS card.
 ///
-/// Numeric and logical values are right-justified in the first 20 bytes
-/// (columns 11-30 of the card).  String values start at byte 0 with a single
-/// quote.
+/// Integers and logicals are right-justified in the first 20 bytes, so their
+/// last character lands in column 30 of the card (byte 29). Floats do the
+/// same when their shortest exact form fits in 20 characters and otherwise
+/// run further right; use [`format_value_fixed`] to always stay within
+/// column 30. String values start at byte 0 with a single quote.
 pub fn format_value(value: &Value) -> [u8; 70] {
     let mut buf = [b' '; 70];
 
@@ -230,8 +253,11 @@ pub fn format_value(value: &Value) -> [u8; 70] {
             right_justify(&s, &mut buf[..20]);
         }
         Value::Float(f) => {
+            // Fixed format when it fits in columns 11-30, otherwise the
+            // value extends further to keep every significant digit.
             let s = format_float(*f);
-            right_justify(&s, &mut buf[..20]);
+            let width = s.len().max(20);
+            right_justify(&s, &mut buf[..width]);
         }
         Value::String(s) => {
             write_string(s, &mut buf);
@@ -246,11 +272,30 @@ pub fn format_value(value: &Value) -> [u8; 70] {
             let s = alloc::format!("({}, {})", re_s, im_s);
             right_justify(s.as_bytes(), &mut buf[..50]);
         }
+        Value::Undefined => {}
     }
 
     buf
 }
 
+/// Serialize a [`Value`] in the standard fixed format.
+///
+/// Like [`format_value`], but floats are always right-justified to column
+/// 30, dropping precision if the exact value needs more than 20 characters.
+/// This is the layout the standard requires for mandatory keywords.
+/// Strings and complex values are formatted as by [`format_value`].
+pub fn format_value_fixed(value: &Value) -> [u8; 70] {
+    match value {
+        Value::Float(f) => {
+            let mut buf = [b' '; 70];
+            let s = format_float_with_max(*f, 20);
+            right_justify(s.as_bytes(), &mut buf[..20]);
+            buf
+        }
+        other => format_value(other),
+    }
+}
+
 /// Right-justify `src` within `dest`, padding the left with spaces.
 fn right_justify(src: &[u8], dest: &mut [u8]) {
     let len = src.len().min(dest.len());
@@ -267,15 +312,32 @@ fn format_integer(n: i64) -> alloc::vec::Vec<u8> {
     format!("{}", n).into_bytes()
 }
 
-fn format_float_raw(f: f64) -> alloc::string::String {
-    format_float_with_max(f, 20)
-}
-
+/// Format `f` with the fewest digits that parse back to the same value.
+///
+/// Plain decimal notation is used when it fits in 20 characters, otherwise
+/// `E` notation. The mantissa always carries a decimal point so the value
+/// reads back as a float. If even the shortest form exceeds `max_len`,
+/// precision is dropped until it fits.
 fn format_float_with_max(f: f64, max_len: usize) -> alloc::string::String {
     use alloc::format;
     if f == 0.0 {
         return alloc::string::String::from("0.0");
     }
+    let with_point = |mut s: alloc::string::String| {
+        if f.is_finite() && !s.contains('.') {
+            let at = s.find('E').unwrap_or(s.len());
+            s.insert_str(at, ".0");
+        }
+        s
+    };
+    let plain = with_point(format!("{}", f));
+    if plain.len() <= max_len.min(20) {
+        return plain;
+    }
+    let shortest = with_point(format!("{:E}", f));
+    if shortest.len() <= max_len {
+        return shortest;
+    }
     // Start with high precision and reduce until the result fits.
     let mut precision = 15usize;
     loop {
@@ -288,7 +350,7 @@ fn format_float_with_max(f: f64, max_len: usize) -> alloc::string::String {
 }
 
 fn format_float(f: f64) -> alloc::vec::Vec<u8> {
-    format_float_raw(f).into_bytes()
+    format_float_with_max(f, 70).into_bytes()
 }
 
 fn write_string(s: &str, buf: &mut [u8; 70]) {
@@ -467,6 +529,19 @@ mod tests {
         assert_eq!(comment.unwrap(), "image type");
     }
 
+    #[test]
+    fn parse_string_containing_slashes() {
+        let field = make_field("   '/data/a / b.fits' / the path");
+        let (val, comment) = parse_value(&field).unwrap();
+        assert_eq!(val, Value::String(String::from("/data/a / b.fits")));
+        assert_eq!(comment, Some("the path"));
+
+        let field = make_field("'a/b'/ no space");
+        let (val, comment) = parse_value(&field).unwrap();
+        assert_eq!(val, Value::String(String::from("a/b")));
+        assert_eq!(comment, Some("no space"));
+    }
+
     #[test]
     fn parse_string_embedded_quotes() {
         let field = make_field("'it''s ok'");
@@ -654,9 +729,9 @@ mod tests {
     }
 
     #[test]
-    fn parse_all_spaces_returns_none() {
+    fn parse_all_spaces_is_undefined() {
         let field = make_field("");
-        assert!(parse_value(&field).is_none());
+        assert_eq!(parse_value(&field), Some((Value::Undefined, None)));
     }
 
     #[test]
@@ -694,6 +769,45 @@ mod tests {
         assert_eq!(val, Value::Integer(9999999999999));
     }
 
+    #[test]
+    fn format_value_fixed_ends_at_column_30() {
+        let buf = format_value_fixed(&Value::Integer(2));
+        assert_eq!(&buf[..20], b"                   2");
+        let buf = format_value_fixed(&Value::Integer(-32));
+        assert_eq!(&buf[17..20], b"-32");
+        assert!(buf[20..].iter().all(|&b| b == b' '));
+
+        let long = 1.234567890123456e-10;
+        assert!(format_value(&Value::Float(long))[20..]
+            .iter()
+            .any(|&b| b != b' '));
+        let buf = format_value_fixed(&Value::Float(long));
+        assert_ne!(buf[19], b' ');
+        assert!(buf[20..].iter().all(|&b| b == b' '));
+        match parse_value(&buf) {
+            Some((Value::Float(f), None)) => assert!((f - long).abs() < 1e-20),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn format_float_round_trips_shortest() {
+        for f in [1.234567890123456e-10, 10.68, 10.0, -2.5e-3, 1e20, f64::MAX] {
+            let buf = format_value(&Value::Float(f));
+            let text = core::str::from_utf8(&buf).unwrap().trim();
+            assert!(text.contains('.'), "{text}");
+            assert_eq!(
+                parse_value(&buf).map(|(v, _)| v),
+                Some(Value::Float(f)),
+                "{text}"
+            );
+        }
+        assert_eq!(
+            &format_value(&Value::Float(10.68))[..20],
+            b"               10.68"
+        );
+    }
+
     #[test]
     fn format_value_field_is_70_bytes() {
         let buf = format_value(&Value::Integer(1));
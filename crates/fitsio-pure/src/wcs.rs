@@ -0,0 +1,458 @@
+//! World Coordinate System (WCS) keyword parsing.
+//!
+//! Reads the linear part of the FITS WCS description (`CTYPEi`, `CUNITi`,
+//! `CRVALi`, `CRPIXi`, `CDELTi`, `PCi_j` / `CDi_j`) for the primary
+//! coordinate system or for one of the alternate systems `A`..`Z`, which
+//! carry the letter as a keyword suffix (e.g. `CRVAL1A`). The per-column
+//! form used by event tables lives in [`column`].
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::header::{keyword_value, Card};
+use crate::value::Value;
+
+/// Per-column WCS keywords of binary tables (`TCTYPn`, `TCRVLn`, ...).
+pub mod column;
+
+pub use column::{event_world_coords, parse_column_wcs, ColumnWcs};
+
+/// The linear WCS description for one coordinate system of an HDU.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wcs {
+    /// Alternate WCS letter (`'A'`..`'Z'`), or `None` for the primary system.
+    pub alt: Option<char>,
+    /// Number of WCS axes, from `WCSAXESa` or else `NAXIS`.
+    pub naxis: usize,
+    /// Optional `WCSNAMEa` label.
+    pub name: Option<String>,
+    /// Axis types from `CTYPEia`.
+    pub ctype: Vec<Option<String>>,
+    /// Axis units from `CUNITia`.
+    pub cunit: Vec<Option<String>>,
+    /// Reference world coordinates from `CRVALia` (default 0).
+    pub crval: Vec<f64>,
+    /// Reference pixel (1-based) from `CRPIXia` (default 0).
+    pub crpix: Vec<f64>,
+    /// Coordinate increments from `CDELTia` (default 1, or 1 when `CDi_ja` is used).
+    pub cdelt: Vec<f64>,
+    /// Linear transformation matrix, row-major, `pc[i][j]` = `PCi_ja`.
+    ///
+    /// When the header uses `CDi_ja` instead, those values are stored here
+    /// and `cdelt` is all ones.
+    pub pc: Vec<Vec<f64>>,
+}
+
+impl Wcs {
+    /// Apply the linear transform to a 1-based pixel coordinate.
+    ///
+    /// Computes `crval[i] + cdelt[i] * sum_j pc[i][j] * (pixel[j] - crpix[j])`.
+    /// No projection is applied, so celestial axes yield intermediate world
+    /// coordinates. Returns `None` if `pixel` does not have `naxis` entries.
+    pub fn pixel_to_world_linear(&self, pixel: &[f64]) -> Option<Vec<f64>> {
+        if pixel.len() != self.naxis {
+            return None;
+        }
+        let offsets: Vec<f64> = pixel.iter().zip(&self.crpix).map(|(p, r)| p - r).collect();
+        let world = (0..self.naxis)
+            .map(|i| {
+                let sum: f64 = (0..self.naxis).map(|j| self.pc[i][j] * offsets[j]).sum();
+                self.crval[i] + self.cdelt[i] * sum
+            })
+            .collect();
+        Some(world)
+    }
+}
+
+/// Celestial reference system named by `RADESYSa`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RadeSys {
+    /// International Celestial Reference System.
+    Icrs,
+    /// Mean place, FK5 (IAU 1984) system.
+    Fk5,
+    /// Mean place, FK4 (Bessel-Newcomb) system.
+    Fk4,
+    /// FK4 without eccentricity terms of aberration.
+    Fk4NoE,
+    /// Geocentric apparent place.
+    Gappt,
+    /// Any other value, as written in the header.
+    Other(String),
+}
+
+/// The celestial frame of an HDU's coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceFrame {
+    /// Reference system.
+    pub system: RadeSys,
+    /// Equinox in years, or `None` where it does not apply (ICRS, GAPPT).
+    pub equinox: Option<f64>,
+}
+
+/// Read the celestial reference frame from `RADESYS` and `EQUINOX`.
+///
+/// The legacy `RADECSYS` and `EPOCH` keywords are used when the modern ones
+/// are absent. Missing values take the defaults of the WCS standard: with no
+/// system given, ICRS if there is no equinox either, FK4 for an equinox
+/// before 1984 and FK5 otherwise; with no equinox given, 1950 for FK4 and
+/// 2000 for FK5.
+pub fn reference_frame(cards: &[Card]) -> ReferenceFrame {
+    let equinox = card_float_value(cards, "EQUINOX").or_else(|| card_float_value(cards, "EPOCH"));
+    let name = card_string_value(cards, "RADESYS")
+        .or_else(|| card_string_value(cards, "RADECSYS"))
+        .filter(|s| !s.is_empty());
+
+    let system = match name.as_deref() {
+        Some("ICRS") => RadeSys::Icrs,
+        Some("FK5") => RadeSys::Fk5,
+        Some("FK4") => RadeSys::Fk4,
+        Some("FK4-NO-E") => RadeSys::Fk4NoE,
+        Some("GAPPT") => RadeSys::Gappt,
+        Some(other) => RadeSys::Other(String::from(other)),
+        None => match equinox {
+            None => RadeSys::Icrs,
+            Some(e) if e < 1984.0 => RadeSys::Fk4,
+            Some(_) => RadeSys::Fk5,
+        },
+    };
+    let equinox = equinox.or(match system {
+        RadeSys::Fk5 => Some(2000.0),
+        RadeSys::Fk4 | RadeSys::Fk4NoE => Some(1950.0),
+        _ => None,
+    });
+
+    ReferenceFrame { system, equinox }
+}
+
+fn card_float_value(cards: &[Card], keyword: &str) -> Option<f64> {
+    match keyword_value(cards, keyword) {
+        Some(Value::Float(f)) => Some(*f),
+        Some(Value::Integer(n)) => Some(*n as f64),
+        _ => None,
+    }
+}
+
+fn card_string_value(cards: &[Card], keyword: &str) -> Option<String> {
+    match keyword_value(cards, keyword) {
+        Some(Value::String(s)) => Some(s.trim().into()),
+        _ => None,
+    }
+}
+
+fn card_int_value(cards: &[Card], keyword: &str) -> Option<i64> {
+    match keyword_value(cards, keyword) {
+        Some(Value::Integer(n)) => Some(*n),
+        _ => None,
+    }
+}
+
+/// Parse the primary WCS of an HDU.
+///
+/// Returns `None` if the header has no axes, more than the 99 the WCS
+/// keywords can number, or carries no WCS keywords.
+pub fn parse_wcs(cards: &[Card]) -> Option<Wcs> {
+    parse_wcs_with_suffix(cards, None)
+}
+
+/// Parse the alternate WCS identified by `alt` (`'A'`..`'Z'`).
+///
+/// Returns `None` if `alt` is not an uppercase letter or the header has no
+/// keywords for that alternate system.
+pub fn parse_wcs_alt(cards: &[Card], alt: char) -> Option<Wcs> {
+    if !alt.is_ascii_uppercase() {
+        return None;
+    }
+    parse_wcs_with_suffix(cards, Some(alt))
+}
+
+/// Read the `CTYPEn` and `CUNITn` labels of axes `1..=naxis`.
+///
+/// Each entry is `(ctype, cunit)` with surrounding blanks trimmed; a missing
+/// keyword gives `None`. Unlike [`parse_wcs`], nothing else about the WCS is
+/// required, so this also suits headers that only name their axes.
+pub fn axis_labels(cards: &[Card], naxis: usize) -> Vec<(Option<String>, Option<String>)> {
+    (1..=naxis)
+        .map(|i| {
+            (
+                card_string_value(cards, &alloc::format!("CTYPE{}", i)),
+                card_string_value(cards, &alloc::format!("CUNIT{}", i)),
+            )
+        })
+        .collect()
+}
+
+fn parse_wcs_with_suffix(cards: &[Card], alt: Option<char>) -> Option<Wcs> {
+    let suffix = alt.map(String::from).unwrap_or_default();
+    let key = |root: &str, axes: &str| alloc::format!("{}{}{}", root, axes, suffix);
+
+    let naxis = card_int_value(cards, &key("WCSAXES", ""))
+        .or_else(|| card_int_value(cards, "NAXIS"))
+        .filter(|&n| (1..=99).contains(&n))? as usize;
+
+    let mut found = false;
+    let mut ctype = Vec::with_capacity(naxis);
+    let mut cunit = Vec::with_capacity(naxis);
+    let mut crval = Vec::with_capacity(naxis);
+    let mut crpix = Vec::with_capacity(naxis);
+    let mut cdelt = Vec::with_capacity(naxis);
+
+    for i in 1..=naxis {
+        let n = alloc::format!("{}", i);
+        let t = card_string_value(cards, &key("CTYPE", &n));
+        let u = card_string_value(cards, &key("CUNIT", &n));
+        let v = card_float_value(cards, &key("CRVAL", &n));
+        let p = card_float_value(cards, &key("CRPIX", &n));
+        let d = card_float_value(cards, &key("CDELT", &n));
+        found |= t.is_some() || v.is_some() || p.is_some() || d.is_some();
+        ctype.push(t);
+        cunit.push(u);
+        crval.push(v.unwrap_or(0.0));
+        crpix.push(p.unwrap_or(0.0));
+        cdelt.push(d.unwrap_or(1.0));
+    }
+
+    let mut pc = vec![vec![0.0; naxis]; naxis];
+    let mut has_cd = false;
+    for (i, row) in pc.iter_mut().enumerate() {
+        for (j, cell) in row.iter_mut().enumerate() {
+            let ij = alloc::format!("{}_{}", i + 1, j + 1);
+            if let Some(cd) = card_float_value(cards, &key("CD", &ij)) {
+                *cell = cd;
+                has_cd = true;
+            } else if let Some(v) = card_float_value(cards, &key("PC", &ij)) {
+                *cell = v;
+                found = true;
+            } else if i == j {
+                *cell = 1.0;
+            }
+        }
+    }
+    if has_cd {
+        // CDi_j already folds in the scale; a missing diagonal term is zero.
+        for (i, row) in pc.iter_mut().enumerate() {
+            let ii = alloc::format!("{}_{}", i + 1, i + 1);
+            if card_float_value(cards, &key("CD", &ii)).is_none() {
+                row[i] = 0.0;
+            }
+        }
+        cdelt.iter_mut().for_each(|d| *d = 1.0);
+        found = true;
+    }
+
+    if !found {
+        return None;
+    }
+
+    Some(Wcs {
+        alt,
+        naxis,
+        name: card_string_value(cards, &key("WCSNAME", "")),
+        ctype,
+        cunit,
+        crval,
+        crpix,
+        cdelt,
+        pc,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(keyword: &str, value: Value) -> Card {
+        let mut kw = [b' '; 8];
+        kw[..keyword.len()].copy_from_slice(keyword.as_bytes());
+        Card {
+            keyword: kw,
+            value: Some(value),
+            comment: None,
+        }
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(String::from(s))
+    }
+
+    fn image_with_two_wcs() -> Vec<Card> {
+        vec![
+            card("SIMPLE", Value::Logical(true)),
+            card("BITPIX", Value::Integer(-32)),
+            card("NAXIS", Value::Integer(2)),
+            card("NAXIS1", Value::Integer(100)),
+            card("NAXIS2", Value::Integer(100)),
+            card("CTYPE1", string("RA---TAN")),
+            card("CTYPE2", string("DEC--TAN")),
+            card("CRVAL1", Value::Float(10.0)),
+            card("CRVAL2", Value::Float(20.0)),
+            card("CRPIX1", Value::Float(50.0)),
+            card("CRPIX2", Value::Float(50.0)),
+            card("CDELT1", Value::Float(-0.001)),
+            card("CDELT2", Value::Float(0.001)),
+            card("WCSNAMEA", string("DETECTOR")),
+            card("CTYPE1A", string("DETX")),
+            card("CTYPE2A", string("DETY")),
+            card("CUNIT1A", string("mm")),
+            card("CUNIT2A", string("mm")),
+            card("CRVAL1A", Value::Float(0.0)),
+            card("CRVAL2A", Value::Float(0.0)),
+            card("CRPIX1A", Value::Float(1.0)),
+            card("CRPIX2A", Value::Float(1.0)),
+            card("CDELT1A", Value::Float(0.015)),
+            card("CDELT2A", Value::Float(0.015)),
+        ]
+    }
+
+    #[test]
+    fn primary_and_alternate_are_distinct() {
+        let cards = image_with_two_wcs();
+
+        let primary = parse_wcs(&cards).unwrap();
+        assert_eq!(primary.alt, None);
+        assert_eq!(primary.naxis, 2);
+        assert_eq!(primary.ctype[0].as_deref(), Some("RA---TAN"));
+        assert_eq!(primary.crval, vec![10.0, 20.0]);
+        assert_eq!(primary.name, None);
+
+        let alt = parse_wcs_alt(&cards, 'A').unwrap();
+        assert_eq!(alt.alt, Some('A'));
+        assert_eq!(alt.name.as_deref(), Some("DETECTOR"));
+        assert_eq!(alt.ctype[1].as_deref(), Some("DETY"));
+        assert_eq!(alt.cunit[0].as_deref(), Some("mm"));
+        assert_eq!(alt.cdelt, vec![0.015, 0.015]);
+
+        let world = alt.pixel_to_world_linear(&[11.0, 21.0]).unwrap();
+        assert!((world[0] - 0.15).abs() < 1e-12);
+        assert!((world[1] - 0.3).abs() < 1e-12);
+
+        assert!(parse_wcs_alt(&cards, 'B').is_none());
+        assert!(parse_wcs_alt(&cards, 'a').is_none());
+    }
+
+    #[test]
+    fn wcsaxes_overrides_naxis() {
+        let mut cards = image_with_two_wcs();
+        cards.push(card("WCSAXES", Value::Integer(3)));
+        cards.push(card("CTYPE3", string("FREQ")));
+        cards.push(card("CRVAL3", Value::Float(1.4e9)));
+
+        let wcs = parse_wcs(&cards).unwrap();
+        assert_eq!(wcs.naxis, 3);
+        assert_eq!(wcs.ctype[2].as_deref(), Some("FREQ"));
+        assert_eq!(wcs.crval[2], 1.4e9);
+        assert_eq!(wcs.pc[2], vec![0.0, 0.0, 1.0]);
+
+        // The alternate system has no WCSAXESA, so it falls back to NAXIS.
+        assert_eq!(parse_wcs_alt(&cards, 'A').unwrap().naxis, 2);
+    }
+
+    #[test]
+    fn too_many_axes_is_rejected() {
+        let mut cards = image_with_two_wcs();
+        cards.push(card("WCSAXES", Value::Integer(100_000_000)));
+        assert!(parse_wcs(&cards).is_none());
+
+        cards.push(card("WCSAXESA", Value::Integer(99)));
+        assert_eq!(parse_wcs_alt(&cards, 'A').unwrap().naxis, 99);
+    }
+
+    #[test]
+    fn cd_matrix_replaces_pc_and_cdelt() {
+        let cards = vec![
+            card("NAXIS", Value::Integer(2)),
+            card("CRPIX1", Value::Float(1.0)),
+            card("CRPIX2", Value::Float(1.0)),
+            card("CD1_1", Value::Float(2.0)),
+            card("CD1_2", Value::Float(0.5)),
+            card("CD2_2", Value::Float(3.0)),
+        ];
+        let wcs = parse_wcs(&cards).unwrap();
+        assert_eq!(wcs.cdelt, vec![1.0, 1.0]);
+        assert_eq!(wcs.pc, vec![vec![2.0, 0.5], vec![0.0, 3.0]]);
+        assert_eq!(wcs.pixel_to_world_linear(&[2.0, 3.0]), Some(vec![3.0, 6.0]));
+        assert_eq!(wcs.pixel_to_world_linear(&[2.0]), None);
+    }
+
+    #[test]
+    fn no_wcs_keywords_returns_none() {
+        let cards = vec![
+            card("SIMPLE", Value::Logical(true)),
+            card("NAXIS", Value::Integer(2)),
+        ];
+        assert!(parse_wcs(&cards).is_none());
+    }
+
+    #[test]
+    fn axis_labels_of_spectral_cube() {
+        let cards = vec![
+            card("NAXIS", Value::Integer(3)),
+            card("CTYPE1", string("RA---SIN")),
+            card("CUNIT1", string("deg     ")),
+            card("CTYPE2", string("DEC--SIN")),
+            card("CUNIT2", string("deg")),
+            card("CTYPE3", string("FREQ    ")),
+            card("CUNIT3", string("Hz")),
+        ];
+        let s = |v: &str| Some(String::from(v));
+        assert_eq!(
+            axis_labels(&cards, 3),
+            vec![
+                (s("RA---SIN"), s("deg")),
+                (s("DEC--SIN"), s("deg")),
+                (s("FREQ"), s("Hz")),
+            ]
+        );
+        assert_eq!(
+            axis_labels(&cards[..2], 2),
+            vec![(s("RA---SIN"), None), (None, None)]
+        );
+    }
+
+    #[test]
+    fn reference_frame_icrs_and_defaults() {
+        let icrs = vec![card("RADESYS", string("ICRS    "))];
+        assert_eq!(
+            reference_frame(&icrs),
+            ReferenceFrame {
+                system: RadeSys::Icrs,
+                equinox: None,
+            }
+        );
+
+        assert_eq!(reference_frame(&[]).system, RadeSys::Icrs);
+        assert_eq!(
+            reference_frame(&[card("EQUINOX", Value::Float(2000.0))]),
+            ReferenceFrame {
+                system: RadeSys::Fk5,
+                equinox: Some(2000.0),
+            }
+        );
+        assert_eq!(
+            reference_frame(&[card("RADESYS", string("FK5"))]).equinox,
+            Some(2000.0)
+        );
+    }
+
+    #[test]
+    fn reference_frame_legacy_keywords() {
+        let cards = vec![
+            card("RADECSYS", string("FK4")),
+            card("EPOCH", Value::Float(1950.0)),
+        ];
+        assert_eq!(
+            reference_frame(&cards),
+            ReferenceFrame {
+                system: RadeSys::Fk4,
+                equinox: Some(1950.0),
+            }
+        );
+
+        // EPOCH alone, before 1984, implies FK4.
+        assert_eq!(
+            reference_frame(&[card("EPOCH", Value::Integer(1950))]).system,
+            RadeSys::Fk4
+        );
+    }
+}
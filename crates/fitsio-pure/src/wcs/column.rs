@@ -0,0 +1,215 @@
+//! Per-column WCS keywords of binary tables.
+//!
+//! Event lists describe the coordinate system of their pixel-like columns
+//! (e.g. detector or sky `X`/`Y`) with the table form of the WCS keywords:
+//! `TCTYPn`, `TCUNIn`, `TCRVLn`, `TCRPXn` and `TCDLTn`, where `n` is the
+//! 1-based column number.
+
+use alloc::string::String;
+
+use super::{card_float_value, card_string_value};
+use crate::bintable::{apply_column_scaling, extract_column_scaling, read_binary_column_range};
+use crate::error::{Error, Result};
+use crate::hdu::Hdu;
+use crate::header::Card;
+
+/// The linear WCS of one table column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnWcs {
+    /// 0-based index of the column.
+    pub column: usize,
+    /// Axis type from `TCTYPn`.
+    pub ctype: Option<String>,
+    /// Axis unit from `TCUNIn`.
+    pub cunit: Option<String>,
+    /// Reference world coordinate from `TCRVLn` (default 0).
+    pub crval: f64,
+    /// Reference pixel from `TCRPXn` (default 0).
+    pub crpix: f64,
+    /// Coordinate increment from `TCDLTn` (default 1).
+    pub cdelt: f64,
+}
+
+impl ColumnWcs {
+    /// Convert a column value to its world coordinate.
+    ///
+    /// Computes `crval + cdelt * (pixel - crpix)`. No projection is applied,
+    /// so celestial columns yield intermediate world coordinates.
+    pub fn pixel_to_world_linear(&self, pixel: f64) -> f64 {
+        self.crval + self.cdelt * (pixel - self.crpix)
+    }
+}
+
+/// Parse the WCS keywords of column `col_index` (0-based).
+///
+/// Returns `None` if the column has none of `TCTYPn`, `TCRVLn`, `TCRPXn`
+/// or `TCDLTn`.
+pub fn parse_column_wcs(cards: &[Card], col_index: usize) -> Option<ColumnWcs> {
+    let n = col_index + 1;
+    let key = |root: &str| alloc::format!("{}{}", root, n);
+
+    let ctype = card_string_value(cards, &key("TCTYP"));
+    let crval = card_float_value(cards, &key("TCRVL"));
+    let crpix = card_float_value(cards, &key("TCRPX"));
+    let cdelt = card_float_value(cards, &key("TCDLT"));
+    if ctype.is_none() && crval.is_none() && crpix.is_none() && cdelt.is_none() {
+        return None;
+    }
+
+    Some(ColumnWcs {
+        column: col_index,
+        ctype,
+        cunit: card_string_value(cards, &key("TCUNI")),
+        crval: crval.unwrap_or(0.0),
+        crpix: crpix.unwrap_or(0.0),
+        cdelt: cdelt.unwrap_or(1.0),
+    })
+}
+
+/// Map the detector coordinates of one event row to world coordinates.
+///
+/// Reads the scalar columns `x_col` and `y_col` (0-based) of `row`, applies
+/// their `TSCALn`/`TZEROn` scaling and then the linear column WCS of each.
+/// Returns `Error::MissingKeyword` if either column has no WCS keywords and
+/// `Error::InvalidValue` if a column does not hold exactly one number per row.
+pub fn event_world_coords(
+    fits_data: &[u8],
+    hdu: &Hdu,
+    row: usize,
+    x_col: usize,
+    y_col: usize,
+) -> Result<(f64, f64)> {
+    let world = |col: usize| -> Result<f64> {
+        let wcs = parse_column_wcs(&hdu.cards, col).ok_or(Error::MissingKeyword("TCTYPn"))?;
+        let raw = read_binary_column_range(fits_data, hdu, col, row, 1)?;
+        let (tscal, tzero) = extract_column_scaling(&hdu.cards, col + 1);
+        match apply_column_scaling(&raw, tscal, tzero)[..] {
+            [pixel] => Ok(wcs.pixel_to_world_linear(pixel)),
+            _ => Err(Error::InvalidValue),
+        }
+    };
+    Ok((world(x_col)?, world(y_col)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::Value;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    fn card(keyword: &str, value: Value) -> Card {
+        let mut kw = [b' '; 8];
+        kw[..keyword.len()].copy_from_slice(keyword.as_bytes());
+        Card {
+            keyword: kw,
+            value: Some(value),
+            comment: None,
+        }
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(String::from(s))
+    }
+
+    fn event_table() -> Vec<Card> {
+        vec![
+            card("XTENSION", string("BINTABLE")),
+            card("BITPIX", Value::Integer(8)),
+            card("NAXIS", Value::Integer(2)),
+            card("NAXIS1", Value::Integer(12)),
+            card("NAXIS2", Value::Integer(0)),
+            card("PCOUNT", Value::Integer(0)),
+            card("GCOUNT", Value::Integer(1)),
+            card("TFIELDS", Value::Integer(3)),
+            card("TTYPE1", string("X")),
+            card("TFORM1", string("1E")),
+            card("TCTYP1", string("RA---TAN")),
+            card("TCUNI1", string("deg")),
+            card("TCRVL1", Value::Float(83.6)),
+            card("TCRPX1", Value::Float(4096.5)),
+            card("TCDLT1", Value::Float(-1.0e-4)),
+            card("TTYPE2", string("Y")),
+            card("TFORM2", string("1E")),
+            card("TCTYP2", string("DEC--TAN")),
+            card("TCUNI2", string("deg")),
+            card("TCRVL2", Value::Float(22.0)),
+            card("TCRPX2", Value::Integer(4096)),
+            card("TCDLT2", Value::Float(1.0e-4)),
+            card("TTYPE3", string("PHA")),
+            card("TFORM3", string("1J")),
+        ]
+    }
+
+    #[test]
+    fn event_columns_carry_sky_wcs() {
+        let cards = event_table();
+
+        let x = parse_column_wcs(&cards, 0).unwrap();
+        assert_eq!(x.column, 0);
+        assert_eq!(x.ctype.as_deref(), Some("RA---TAN"));
+        assert_eq!(x.cunit.as_deref(), Some("deg"));
+        assert!((x.pixel_to_world_linear(4106.5) - 83.599).abs() < 1e-12);
+
+        let y = parse_column_wcs(&cards, 1).unwrap();
+        assert_eq!(y.ctype.as_deref(), Some("DEC--TAN"));
+        assert_eq!(y.crpix, 4096.0);
+        assert!((y.pixel_to_world_linear(4106.0) - 22.001).abs() < 1e-12);
+
+        assert!(parse_column_wcs(&cards, 2).is_none());
+    }
+
+    #[test]
+    fn event_row_maps_to_sky() {
+        use crate::bintable::{
+            build_fits_with_binary_table, BinaryColumnData, BinaryColumnDescriptor,
+            BinaryColumnType,
+        };
+        use crate::hdu::parse_fits;
+
+        let column = |name: &str, col_type| BinaryColumnDescriptor {
+            name: Some(String::from(name)),
+            repeat: 1,
+            col_type,
+            byte_width: 4,
+            tdim: None,
+        };
+        let columns = vec![
+            column("X", BinaryColumnType::Float),
+            column("Y", BinaryColumnType::Float),
+            column("PHA", BinaryColumnType::Int),
+        ];
+        let data = vec![
+            BinaryColumnData::Float(vec![4096.5, 4106.5]),
+            BinaryColumnData::Float(vec![4096.0, 4106.0]),
+            BinaryColumnData::Int(vec![10, 20]),
+        ];
+        let mut bytes = build_fits_with_binary_table(&columns, &data, 2).unwrap();
+        let mut fits = parse_fits(&bytes).unwrap();
+        let mut cards = fits.get(1).unwrap().cards.clone();
+        cards.retain(|c| &c.keyword != b"END     ");
+        cards.extend(
+            event_table()
+                .into_iter()
+                .filter(|c| c.keyword.starts_with(b"TC")),
+        );
+        let header = crate::header::serialize_header(&cards).unwrap();
+        let start = fits.get(1).unwrap().header_start;
+        let data_start = fits.get(1).unwrap().data_start;
+        bytes.splice(start..data_start, header);
+        fits = parse_fits(&bytes).unwrap();
+        let hdu = fits.get(1).unwrap();
+
+        let (ra, dec) = event_world_coords(&bytes, hdu, 1, 0, 1).unwrap();
+        assert!((ra - 83.599).abs() < 1e-9);
+        assert!((dec - 22.001).abs() < 1e-9);
+        assert!(matches!(
+            event_world_coords(&bytes, hdu, 0, 0, 2),
+            Err(Error::MissingKeyword("TCTYPn"))
+        ));
+        assert!(matches!(
+            event_world_coords(&bytes, hdu, 2, 0, 1),
+            Err(Error::InvalidValue)
+        ));
+    }
+}
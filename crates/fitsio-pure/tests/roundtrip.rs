@@ -75,7 +75,7 @@ fn build_image_extension(
 fn roundtrip_image_u8() {
     let pixels: Vec<u8> = (0..=255).collect();
     let data = ImageData::U8(pixels.clone());
-    let bytes = build_image_hdu(8, &[256], &data).unwrap();
+    let bytes = build_image_hdu(8, &data.with_shape(&[256]).unwrap()).unwrap();
     let fits = parse_fits(&bytes).unwrap();
     let hdu = fits.primary();
     let read_back = read_image_data(&bytes, hdu).unwrap();
@@ -86,7 +86,7 @@ fn roundtrip_image_u8() {
 fn roundtrip_image_i16() {
     let pixels: Vec<i16> = vec![0, 1, -1, i16::MIN, i16::MAX, 256, -256, 12345];
     let data = ImageData::I16(pixels.clone());
-    let bytes = build_image_hdu(16, &[8], &data).unwrap();
+    let bytes = build_image_hdu(16, &data.with_shape(&[8]).unwrap()).unwrap();
     let fits = parse_fits(&bytes).unwrap();
     let hdu = fits.primary();
     let read_back = read_image_data(&bytes, hdu).unwrap();
@@ -97,7 +97,7 @@ fn roundtrip_image_i16() {
 fn roundtrip_image_i32() {
     let pixels: Vec<i32> = vec![0, 1, -1, i32::MIN, i32::MAX, -42, 1000000];
     let data = ImageData::I32(pixels.clone());
-    let bytes = build_image_hdu(32, &[7], &data).unwrap();
+    let bytes = build_image_hdu(32, &data.with_shape(&[7]).unwrap()).unwrap();
     let fits = parse_fits(&bytes).unwrap();
     let hdu = fits.primary();
     let read_back = read_image_data(&bytes, hdu).unwrap();
@@ -108,7 +108,7 @@ fn roundtrip_image_i32() {
 fn roundtrip_image_i64() {
     let pixels: Vec<i64> = vec![0, i64::MIN, i64::MAX, -1, 1];
     let data = ImageData::I64(pixels.clone());
-    let bytes = build_image_hdu(64, &[5], &data).unwrap();
+    let bytes = build_image_hdu(64, &data.with_shape(&[5]).unwrap()).unwrap();
     let fits = parse_fits(&bytes).unwrap();
     let hdu = fits.primary();
     let read_back = read_image_data(&bytes, hdu).unwrap();
@@ -119,7 +119,7 @@ fn roundtrip_image_i64() {
 fn roundtrip_image_f32() {
     let pixels: Vec<f32> = vec![0.0, 1.5, -2.5, f32::MAX, f32::MIN_POSITIVE, 1e30];
     let data = ImageData::F32(pixels.clone());
-    let bytes = build_image_hdu(-32, &[6], &data).unwrap();
+    let bytes = build_image_hdu(-32, &data.with_shape(&[6]).unwrap()).unwrap();
     let fits = parse_fits(&bytes).unwrap();
     let hdu = fits.primary();
     let read_back = read_image_data(&bytes, hdu).unwrap();
@@ -130,7 +130,7 @@ fn roundtrip_image_f32() {
 fn roundtrip_image_f64() {
     let pixels: Vec<f64> = vec![0.0, 1.5, -2.5, f64::MAX, f64::MIN_POSITIVE, 1e200];
     let data = ImageData::F64(pixels.clone());
-    let bytes = build_image_hdu(-64, &[6], &data).unwrap();
+    let bytes = build_image_hdu(-64, &data.with_shape(&[6]).unwrap()).unwrap();
     let fits = parse_fits(&bytes).unwrap();
     let hdu = fits.primary();
     let read_back = read_image_data(&bytes, hdu).unwrap();
@@ -147,7 +147,7 @@ fn roundtrip_2d_image() {
     let height = 8;
     let pixels: Vec<i16> = (0..(width * height) as i16).collect();
     let data = ImageData::I16(pixels.clone());
-    let bytes = build_image_hdu(16, &[width, height], &data).unwrap();
+    let bytes = build_image_hdu(16, &data.with_shape(&[width, height]).unwrap()).unwrap();
     let fits = parse_fits(&bytes).unwrap();
     let hdu = fits.primary();
 
@@ -178,7 +178,7 @@ fn roundtrip_3d_image() {
     let total = nx * ny * nz;
     let pixels: Vec<f32> = (0..total).map(|i| (i as f32) + 0.25).collect();
     let data = ImageData::F32(pixels.clone());
-    let bytes = build_image_hdu(-32, &[nx, ny, nz], &data).unwrap();
+    let bytes = build_image_hdu(-32, &data.with_shape(&[nx, ny, nz]).unwrap()).unwrap();
     let fits = parse_fits(&bytes).unwrap();
     let hdu = fits.primary();
 
@@ -702,7 +702,7 @@ fn all_hdu_bytes_are_block_aligned() {
             -64 => ImageData::F64(vec![42.0; size]),
             _ => unreachable!(),
         };
-        let bytes = build_image_hdu(bitpix, &[size], &data).unwrap();
+        let bytes = build_image_hdu(bitpix, &data.with_shape(&[size]).unwrap()).unwrap();
         assert_eq!(
             bytes.len() % BLOCK_SIZE,
             0,
@@ -737,7 +737,7 @@ fn roundtrip_image_section_and_region() {
     let height = 5;
     let pixels: Vec<i32> = (0..30).collect();
     let data = ImageData::I32(pixels.clone());
-    let bytes = build_image_hdu(32, &[width, height], &data).unwrap();
+    let bytes = build_image_hdu(32, &data.with_shape(&[width, height]).unwrap()).unwrap();
     let fits = parse_fits(&bytes).unwrap();
     let hdu = fits.primary();
 
@@ -768,7 +768,7 @@ fn roundtrip_image_section_and_region() {
 #[test]
 fn roundtrip_zero_length_image() {
     let data = ImageData::F32(Vec::new());
-    let bytes = build_image_hdu(-32, &[], &data).unwrap();
+    let bytes = build_image_hdu(-32, &data.with_shape(&[]).unwrap()).unwrap();
     let fits = parse_fits(&bytes).unwrap();
     let hdu = fits.primary();
 
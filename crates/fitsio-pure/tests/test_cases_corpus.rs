@@ -150,7 +150,7 @@ fn all_files_parse() {
 
 #[test]
 fn random_groups_metadata() {
-    let (_, fits) = match load("nasa-samples/Random_Groups.fits") {
+    let (data, fits) = match load("nasa-samples/Random_Groups.fits") {
         Some(v) => v,
         None => return,
     };
@@ -171,6 +171,11 @@ fn random_groups_metadata() {
         other => panic!("Expected RandomGroups, got {:?}", other),
     }
     assert_eq!(fits.primary().data_len, 572832);
+
+    // The groups array must not be mistaken for a primary image.
+    assert!(fits.random_groups().is_some());
+    assert_eq!(fits.images(&data).count(), 0);
+    assert!(fits.read_primary_image(&data).is_err());
 }
 
 // ---------------------------------------------------------------------------